@@ -1,7 +1,7 @@
 use biotest::Format;
 use cocktail::tokenizer::minimizer::{method::Random, Forward};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use minimizer_iter::MinimizerBuilder;
+use minimizer_iter::{BaseOrder, MinimizerBuilder};
 #[cfg(feature = "nightly")]
 use minimizers::{order::RandomOrder, Minimizer, ModSampling, SamplingScheme};
 use nohash_hasher::BuildNoHashHasher;
@@ -32,6 +32,109 @@ fn minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
     });
 }
 
+fn minimizer_u128(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
+    let id = format!("minimizer u128 m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            for x in MinimizerBuilder::<u128>::new()
+                .minimizer_size(m)
+                .width(w)
+                .iter(seq)
+            {
+                black_box(x);
+            }
+        })
+    });
+}
+
+fn minimizer_u128_advance(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
+    let id = format!("minimizer u128 advance m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            let mut iter = MinimizerBuilder::<u128>::new()
+                .minimizer_size(m)
+                .width(w)
+                .iter(seq);
+            while let Some(x) = iter.advance() {
+                black_box(x);
+            }
+        })
+    });
+}
+
+fn total_minimizers(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
+    let records: Vec<&[u8]> = seq.chunks(150).collect();
+    let id = format!("total minimizers m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            black_box(
+                MinimizerBuilder::<u64>::new()
+                    .minimizer_size(m)
+                    .width(w)
+                    .total_minimizers(records.iter().copied()),
+            )
+        })
+    });
+    let id = format!("total minimizers naive m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for record in records.iter() {
+                total += MinimizerBuilder::<u64>::new()
+                    .minimizer_size(m)
+                    .width(w)
+                    .iter_pos(record)
+                    .count();
+            }
+            black_box(total)
+        })
+    });
+}
+
+fn ascii_minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
+    let id = format!("ascii minimizer m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            for x in MinimizerBuilder::<u64>::new()
+                .minimizer_size(m)
+                .width(w)
+                .iter_ascii(seq)
+            {
+                black_box(x);
+            }
+        })
+    });
+    let id = format!("ascii minimizer (table) m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            for x in MinimizerBuilder::<u64>::new()
+                .minimizer_size(m)
+                .width(w)
+                .encoding(0b00, 0b01, 0b11, 0b10)
+                .iter(seq)
+            {
+                black_box(x);
+            }
+        })
+    });
+}
+
+fn lsf_minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
+    let id = format!("lsf minimizer m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            for x in MinimizerBuilder::<u64>::new()
+                .minimizer_size(m)
+                .width(w)
+                .base_order(BaseOrder::LeastSignificantFirst)
+                .iter_pos(seq)
+            {
+                black_box(x);
+            }
+        })
+    });
+}
+
 fn mod_minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
     let id = format!("mod-minimizer m={m} w={w}");
     c.bench_function(id.as_str(), |b| {
@@ -63,6 +166,22 @@ fn lex_minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
     });
 }
 
+fn lex_fast_minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
+    let id = format!("lex minimizer (no hasher) m={m} w={w}");
+    c.bench_function(id.as_str(), |b| {
+        b.iter(|| {
+            for x in MinimizerBuilder::<u64>::new()
+                .minimizer_size(m)
+                .width(w)
+                .lexicographic()
+                .iter(seq)
+            {
+                black_box(x);
+            }
+        })
+    });
+}
+
 fn lex_mod_minimizer(c: &mut Criterion, seq: &[u8], m: usize, w: u16) {
     let id = format!("lex mod-minimizer m={m} w={w}");
     c.bench_function(id.as_str(), |b| {
@@ -153,7 +272,12 @@ fn all_benches(c: &mut Criterion) {
     for (k, m) in ks.iter().copied().zip(ms.iter().copied()) {
         let w = (k - m + 1) as u16;
         minimizer(c, &seq, m, w);
+        lsf_minimizer(c, &seq, m, w);
+        minimizer_u128(c, &seq, m, w);
+        minimizer_u128_advance(c, &seq, m, w);
+        total_minimizers(c, &seq, m, w);
         lex_minimizer(c, &seq, m, w);
+        lex_fast_minimizer(c, &seq, m, w);
         #[cfg(feature = "nightly")]
         ragnar_minimizer(c, &seq, m, w as usize);
         if k <= 32 {
@@ -166,6 +290,7 @@ fn all_benches(c: &mut Criterion) {
         ragnar_mod_minimizer(c, &seq, m, w as usize);
         canon_mod_minimizer(c, &seq, m, w);
     }
+    ascii_minimizer(c, &seq, 21, 11);
 }
 
 criterion_group!(benches, all_benches);
@@ -1,11 +1,38 @@
 //! Algorithms to compute minimizers.
 
-pub trait MinimizerAlgorithm {}
+pub trait MinimizerAlgorithm {
+    /// Number of bits encoding each base of the minimizers, which bounds the minimizer size of each integer type.
+    const BITS_PER_BASE: u32 = 2;
+}
 
 /// "Classic" minimizers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Minimizer {}
 impl MinimizerAlgorithm for Minimizer {}
 
 /// Mod-minimizers, introduced in [The mod-minimizer: a simple and efficient sampling algorithm for long k-mers (Groot Koerkamp & Pibiri '24)](https://doi.org/10.1101/2024.05.25.595898).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct ModMinimizer {}
 impl MinimizerAlgorithm for ModMinimizer {}
+
+/// Canonical minimizers following the conventions of [minimap2](https://github.com/lh3/minimap2)'s `mm_sketch`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Minimap2 {}
+impl MinimizerAlgorithm for Minimap2 {}
+
+/// Lexicographic minimizers, ordered by the value of their encoding without any hasher.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Lexicographic {}
+impl MinimizerAlgorithm for Lexicographic {}
+
+/// Minimizers over a binary alphabet, encoding each base on a single bit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Binary {}
+impl MinimizerAlgorithm for Binary {
+    const BITS_PER_BASE: u32 = 1;
+}
+
+/// Minimizers ordered by their [ntHash](https://doi.org/10.1093/bioinformatics/btw397) instead of a generic hasher.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NtHash {}
+impl MinimizerAlgorithm for NtHash {}
@@ -1,9 +1,22 @@
-use crate::algorithm::{Minimizer, MinimizerAlgorithm, ModMinimizer};
+use crate::algorithm::{
+    Binary, Lexicographic, Minimap2, Minimizer, MinimizerAlgorithm, ModMinimizer, NtHash,
+};
+use crate::encoding::AsciiEncoding;
+use crate::error::{BuildError, ExpansionError};
+use crate::hash::{BuildHasherRef, BuildMaxHasher, BuildRankHasher, BuildStableHasher};
+use crate::index::MinimizerIndex;
 use crate::iterator::*;
+use crate::queue::{MinPosWindow, MinWindow};
+use crate::sink::MinimizerSink;
+use crate::Strand;
+use core::cmp::min;
 use core::hash::{BuildHasher, Hash};
 use core::marker::PhantomData;
+use core::ops::ControlFlow;
 use minimizer_queue::DefaultHashBuilder;
-use num_traits::PrimInt;
+use num_traits::{AsPrimitive, PrimInt};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// A builder for iterators over minimizers.
 ///
@@ -35,15 +48,24 @@ pub struct MinimizerBuilder<
     width: u16,
     hasher: S,
     encoding: [u8; 256],
+    complement: [u8; 256],
+    rc_encoding: [u8; 256],
+    rc_codes: [u8; 4],
+    t: Option<usize>,
+    r: usize,
+    even_width: bool,
+    base_order: BaseOrder,
+    num_ranks: Option<usize>,
     _marker: PhantomData<(T, A)>,
 }
 
 impl<T: PrimInt + Hash> MinimizerBuilder<T> {
     /// Sets up the `MinimizerBuilder` with default values:
-    /// - minimizer_size = 21
-    /// - width = 11 (31 - 21 + 1)
+    /// - minimizer_size = [`DEFAULT_MINIMIZER_SIZE`](Self::DEFAULT_MINIMIZER_SIZE) = 21
+    /// - width = [`DEFAULT_WIDTH`](Self::DEFAULT_WIDTH) = 11 (31 - 21 + 1)
     /// - hasher = [`DefaultHashBuilder`]
     /// - encoding: A = `00`, C = `01`, G = `10`, T = `11`
+    /// - complement: [`COMPLEMENT`], following the IUPAC codes
     #[inline]
     pub fn new() -> Self {
         Self::_new()
@@ -58,128 +80,2215 @@ impl<T: PrimInt + Hash> Default for MinimizerBuilder<T> {
 }
 
 impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, Minimizer, S, false> {
+    /// Sets the order in which the bases are packed into the minimizers, the first base being the most significant by default.
+    /// With [`BaseOrder::LeastSignificantFirst`], the first base is stored in the lowest bits, which matches the convention of some other tools;
+    /// since the minimizers are hashed in their packed form, this also changes which ones are selected.
+    ///
+    /// This applies to [`iter`](Self::iter), [`iter_pos`](Self::iter_pos), their variants with a borrowed builder, a custom queue or ASCII codes,
+    /// [`iter_pos_centered`](Self::iter_pos_centered), [`iter_ties`](Self::iter_ties), [`iter_range`](Self::iter_range), [`resume`](Self::resume)
+    /// and the methods built on them. The other iterators of this builder panic with another order,
+    /// and so does converting the builder to another algorithm or to canonical minimizers.
+    pub fn base_order(mut self, base_order: BaseOrder) -> Self {
+        self.base_order = base_order;
+        self
+    }
+
+    /// Panics if the bases are not packed in the default order, for the iterators which do not support another one.
+    #[inline]
+    fn assert_default_base_order(&self) {
+        assert!(
+            self.base_order == BaseOrder::MostSignificantFirst,
+            "this iterator only supports BaseOrder::MostSignificantFirst"
+        );
+    }
+
     /// Builds an iterator over the minimizers and their positions in the given sequence.
     #[inline]
     pub fn iter(self, seq: &[u8]) -> MinimizerIterator<T, S> {
-        MinimizerIterator::new(
+        self.assert_ranks(self.minimizer_size);
+        let mut iter = MinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        );
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Builds an iterator over the positions of the minimizers in the given sequence.
+    #[inline]
+    pub fn iter_pos(self, seq: &[u8]) -> MinimizerPosIterator<T, S> {
+        self.assert_ranks(self.minimizer_size);
+        let mut iter = MinimizerPosIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        );
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, borrowing the builder
+    /// so that it can be reused for many sequences without cloning its hasher or copying its encoding.
+    #[inline]
+    pub fn iter_ref<'a>(&'a self, seq: &'a [u8]) -> MinimizerRefIterator<'a, T, S> {
+        self.assert_ranks(self.minimizer_size);
+        let mut iter = MinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            BuildHasherRef(&self.hasher),
+            &self.encoding,
+        );
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Builds an iterator over the positions of the minimizers in the given sequence, borrowing the builder
+    /// so that it can be reused for many sequences without cloning its hasher or copying its encoding.
+    #[inline]
+    pub fn iter_pos_ref<'a>(&'a self, seq: &'a [u8]) -> MinimizerPosRefIterator<'a, T, S> {
+        self.assert_ranks(self.minimizer_size);
+        let mut iter = MinimizerPosIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            BuildHasherRef(&self.hasher),
+            &self.encoding,
+        );
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Returns the minimizer of the first window of the given sequence and its position,
+    /// or `None` if the sequence is shorter than a window.
+    #[inline]
+    pub fn first(self, seq: &[u8]) -> Option<(T, usize)>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).next()
+    }
+
+    /// Returns the number of minimizers in the given sequence, without building the minimizers themselves.
+    #[inline]
+    pub fn count(self, seq: &[u8]) -> usize
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter_pos(seq).count()
+    }
+
+    /// Returns the minimizers and their positions in the given sequence, expanding its IUPAC degenerate bases (R, Y, S, W, K, M, B, D, H, V and N).
+    /// Each window containing degenerate bases yields the minimizer of each of its concrete assignments,
+    /// while the other windows yield their usual minimizer.
+    /// The result is sorted by position then by value, without duplicates.
+    ///
+    /// The number of assignments of a window grows exponentially with its number of degenerate bases,
+    /// so this fails if a window has more than `max_expansions` assignments.
+    pub fn expand_degenerate(
+        self,
+        seq: &[u8],
+        max_expansions: usize,
+    ) -> Result<Vec<(T, usize)>, ExpansionError>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let base_width = self.kmer_size();
+        let degenerate: Vec<usize> = (0..seq.len())
+            .filter(|&i| degenerate_bases(seq[i]).is_some())
+            .collect();
+        let mut mins = Vec::new();
+        // the windows without degenerate bases lie in the runs of concrete bases
+        let mut run_start = 0;
+        for &run_end in degenerate.iter().chain([&seq.len()]) {
+            let run = &seq[run_start..run_end];
+            mins.extend(self.iter_ref(run).map(|(min, pos)| (min, run_start + pos)));
+            run_start = run_end + 1;
+        }
+        let mut window = vec![0u8; base_width];
+        let (mut lo, mut hi) = (0, 0);
+        for start in 0..(seq.len() + 1).saturating_sub(base_width) {
+            while lo < degenerate.len() && degenerate[lo] < start {
+                lo += 1;
+            }
+            while hi < degenerate.len() && degenerate[hi] < start + base_width {
+                hi += 1;
+            }
+            let positions = &degenerate[lo..hi];
+            if positions.is_empty() {
+                continue;
+            }
+            let expansions = positions.iter().fold(1usize, |acc, &i| {
+                acc.saturating_mul(degenerate_bases(seq[i]).unwrap().len())
+            });
+            if expansions > max_expansions {
+                return Err(ExpansionError {
+                    pos: start,
+                    expansions,
+                    max_expansions,
+                });
+            }
+            window.copy_from_slice(&seq[start..(start + base_width)]);
+            let mut choices = vec![0; positions.len()];
+            'assignments: loop {
+                for (&i, &choice) in positions.iter().zip(choices.iter()) {
+                    window[i - start] = degenerate_bases(seq[i]).unwrap()[choice];
+                }
+                if let Some((min, pos)) = self.iter_ref(&window).next() {
+                    mins.push((min, start + pos));
+                }
+                for (&i, choice) in positions.iter().zip(choices.iter_mut()) {
+                    *choice += 1;
+                    if *choice < degenerate_bases(seq[i]).unwrap().len() {
+                        continue 'assignments;
+                    }
+                    *choice = 0;
+                }
+                break;
+            }
+        }
+        mins.sort_unstable_by_key(|&(min, pos)| (pos, min));
+        mins.dedup();
+        Ok(mins)
+    }
+
+    /// Returns the observed density of the minimizers in the given sequence, i.e. the fraction of m-mers that are selected.
+    #[inline]
+    pub fn density(self, seq: &[u8]) -> f64
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let num_mmers = (seq.len() + 1).saturating_sub(self.minimizer_size);
+        if num_mmers == 0 {
+            return 0.0;
+        }
+        self.count(seq) as f64 / num_mmers as f64
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence as slices borrowed from the sequence, with its position.
+    /// Each slice is `&seq[pos..(pos + minimizer_size)]`, so no decoding is needed.
+    #[inline]
+    pub fn iter_kmers(self, seq: &[u8]) -> KmerIterator<MinimizerPosIterator<T, S>> {
+        KmerIterator::new(seq, self.minimizer_size, self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the positions of the minimizers in the given sequence as `u32`, to save memory when collecting them.
+    /// Positions are checked to fit in a `u32` in debug builds only.
+    #[inline]
+    pub fn iter_pos_u32(self, seq: &[u8]) -> U32PosIterator<MinimizerPosIterator<T, S>> {
+        U32PosIterator::new(self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, with the previous minimizer of each one,
+    /// i.e. `(prev, min, pos)` where `prev` is `None` for the first minimizer.
+    #[inline]
+    pub fn iter_transitions(self, seq: &[u8]) -> TransitionIterator<MinimizerIterator<T, S>, T> {
+        TransitionIterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the gaps between the positions of consecutive minimizers in the given sequence.
+    /// The first item is the position of the first minimizer, so that the prefix sums of the gaps give back the positions.
+    #[inline]
+    pub fn iter_gaps(self, seq: &[u8]) -> GapIterator<MinimizerPosIterator<T, S>> {
+        GapIterator::new(self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence and their positions counted from the end of the sequence,
+    /// i.e. `seq.len() - pos - minimizer_size`, which is the position of the minimizer in the reverse complement of the sequence.
+    #[inline]
+    pub fn positions_from_end(self, seq: &[u8]) -> FromEndIterator<MinimizerIterator<T, S>> {
+        let last_mmer = seq.len().saturating_sub(self.minimizer_size);
+        FromEndIterator::new(self.iter(seq), last_mmer)
+    }
+
+    /// Builds an iterator over the positions of the minimizers in the given sequence counted from the end of the sequence,
+    /// i.e. `seq.len() - pos - minimizer_size`.
+    #[inline]
+    pub fn iter_pos_from_end(self, seq: &[u8]) -> PosFromEndIterator<MinimizerPosIterator<T, S>> {
+        let last_mmer = seq.len().saturating_sub(self.minimizer_size);
+        PosFromEndIterator::new(self.iter_pos(seq), last_mmer)
+    }
+
+    /// Builds an iterator over the gaps longer than `threshold` between the positions of consecutive minimizers in the given sequence,
+    /// as ranges from the position of a minimizer to the position of the next one, e.g. to flag regions poorly covered by anchors.
+    /// Like [`max_gap`](Self::max_gap), it considers the start of the sequence and the last m-mer as endpoints.
+    #[inline]
+    pub fn gaps_longer_than(
+        self,
+        seq: &[u8],
+        threshold: usize,
+    ) -> LongGapIterator<MinimizerPosIterator<T, S>> {
+        let last_mmer = seq.len().saturating_sub(self.minimizer_size);
+        LongGapIterator::new(self.iter_pos(seq), threshold, last_mmer)
+    }
+
+    /// Builds a stream over the minimizers and their positions in the sequence read asynchronously from `reader`.
+    /// Every byte of the reader is considered as a base, and the window is rolled across the chunks of the reader.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn stream<R>(self, reader: R) -> MinimizerStream<R, T, S> {
+        self.assert_default_base_order();
+        MinimizerStream::new(
+            reader,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the positions of the minimizers in the given sequence relative to the center of a window,
+    /// i.e. `pos - (window_start + width / 2)` where `window_start` is the start of the first window selecting the minimizer.
+    #[inline]
+    pub fn iter_pos_centered(self, seq: &[u8]) -> CenteredMinimizerPosIterator<T, S> {
+        let mut iter = CenteredMinimizerPosIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        );
+        iter.inner.base_order = self.base_order;
+        iter
+    }
+
+    /// Resumes an iterator over the minimizers and their positions in the given sequence from a saved state,
+    /// see [`MinimizerIterator::save_state`]. The sequence must be identical to the one of the saved iterator.
+    #[inline]
+    pub fn resume(self, seq: &[u8], state: IterState<T>) -> MinimizerIterator<T, S>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let mut iter = MinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        );
+        iter.base_order = self.base_order;
+        iter.restore(state);
+        iter
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence with a boolean indicating
+    /// whether the window selecting the minimizer contained several m-mers with the minimal hash.
+    #[inline]
+    pub fn iter_ties(self, seq: &[u8]) -> MinimizerTieIterator<T, S> {
+        let mut iter = MinimizerTieIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        );
+        iter.inner.base_order = self.base_order;
+        iter
+    }
+
+    /// Builds an iterator over the minimizers of both strands of the given sequence, yielding `(fwd, fwd_pos, rc, rc_pos)`
+    /// each time the minimizer of one of the strands changes. Positions are given in forward coordinates.
+    #[inline]
+    pub fn iter_both_strands(self, seq: &[u8]) -> BothStrandsMinimizerIterator<T, S>
+    where
+        S: Clone,
+    {
+        self.assert_default_base_order();
+        BothStrandsMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+            self.complement,
+        )
+    }
+
+    /// Builds an iterator over the minimizers of a spliced sequence given as `(segment, genomic_start)` pairs.
+    /// The segments are read as a single sequence, and positions are reported in genomic coordinates.
+    #[inline]
+    pub fn iter_spliced<'a>(
+        self,
+        segments: &'a [(&'a [u8], usize)],
+    ) -> SplicedMinimizerIterator<'a, T, S> {
+        self.assert_default_base_order();
+        SplicedMinimizerIterator::new(
+            segments,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the first `len` bases of a 2-bit packed sequence.
+    /// Each byte holds 4 bases, the first one in the 2 most significant bits, and the encoding of the builder is ignored.
+    ///
+    /// The packed bytes are only borrowed, so a memory-mapped file can be passed directly with `&mmap[..]`.
+    #[inline]
+    pub fn iter_packed(self, packed: &[u8], len: usize) -> PackedMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        PackedMinimizerIterator::new(packed, len, self.minimizer_size, self.width, self.hasher)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence,
+    /// restarting the window after each base whose quality score in `qual` is below `min_q` and after each base other than A, C, G and T.
+    #[inline]
+    pub fn iter_with_qual<'a>(
+        self,
+        seq: &'a [u8],
+        qual: &'a [u8],
+        min_q: u8,
+    ) -> QualityMinimizerIterator<'a, T, S> {
+        self.assert_default_base_order();
+        QualityMinimizerIterator::new(
+            seq,
+            qual,
+            min_q,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, never selecting the m-mers
+    /// for which `penalized(mmer, pos)` holds. Each window selects its smallest m-mer that is not penalized instead,
+    /// see [`PenalizedMinimizerIterator`] for the impact on the density.
+    #[inline]
+    pub fn iter_penalized<'a, F: Fn(T, usize) -> bool>(
+        self,
+        seq: &'a [u8],
+        penalized: F,
+    ) -> PenalizedMinimizerIterator<'a, T, S, F>
+    where
+        S: Clone,
+    {
+        self.assert_default_base_order();
+        PenalizedMinimizerIterator::new(
+            seq,
+            penalized,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given soft-masked sequence,
+    /// ignoring the m-mers containing a lowercase base.
+    #[inline]
+    pub fn iter_soft_masked<'a>(
+        self,
+        seq: &'a [u8],
+    ) -> PenalizedMinimizerIterator<'a, T, S, impl Fn(T, usize) -> bool + 'a>
+    where
+        S: Clone,
+    {
+        let minimizer_size = self.minimizer_size;
+        self.iter_penalized(seq, move |_, pos| {
+            seq[pos..(pos + minimizer_size)]
+                .iter()
+                .any(u8::is_ascii_lowercase)
+        })
+    }
+
+    /// Builds an iterator over the minimizers of the given nucleotide sequence translated in its six frames,
+    /// yielding the frame (1, 2, 3, -1, -2, -3), the minimizer and its position in the translated frame.
+    /// The minimizer size and the width are counted in amino acids.
+    /// The translation always uses the standard genetic code and each amino acid is packed with its fixed 5-bit code following [`AMINO_ACIDS`],
+    /// neither of which can be configured: the encoding of the builder is ignored.
+    #[inline]
+    pub fn iter_six_frame(self, dna: &[u8]) -> SixFrameMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        SixFrameMinimizerIterator::new(dna, self.minimizer_size, self.width, self.hasher)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence of 2-bit codes,
+    /// where each byte is the code of a base (0, 1, 2 or 3) rather than an ASCII letter.
+    /// The encoding of the builder is ignored, which avoids a lookup per base for pre-encoded sequences.
+    #[inline]
+    pub fn iter_raw_codes(self, codes: &[u8]) -> RawCodesMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        RawCodesMinimizerIterator::new(codes, self.minimizer_size, self.width, self.hasher)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given ASCII sequence, encoding the bases with `(b >> 1) & 3` instead of a lookup table.
+    /// This gives A = `00`, C = `01`, G = `11` and T = `10` in both cases and ignores the encoding of the builder,
+    /// so it selects the same minimizers as [`iter`](Self::iter) with `.encoding(0b00, 0b01, 0b11, 0b10)`.
+    /// It is only valid for sequences made of A, C, G and T.
+    #[inline]
+    pub fn iter_ascii(self, seq: &[u8]) -> AsciiMinimizerIterator<T, S> {
+        let mut iter = MinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            AsciiEncoding {},
+        );
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Builds an iterator over the minimizer of every window of the given sequence, yielding the index of the window,
+    /// the minimizer and its offset in the window.
+    /// Consecutive windows sharing the same minimizer are not collapsed, so it yields `seq.len() - base_width + 1` items.
+    #[inline]
+    pub fn iter_windows(self, seq: &[u8]) -> WindowMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        WindowMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the sequence made of `first` followed by `second`, without copying them.
+    /// The windows straddling the junction are considered, and the positions are given in the joined sequence.
+    #[inline]
+    pub fn iter_joined<'a>(
+        self,
+        first: &'a [u8],
+        second: &'a [u8],
+    ) -> JoinedMinimizerIterator<'a, T, S> {
+        self.assert_default_base_order();
+        JoinedMinimizerIterator::new(
+            first,
+            second,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the sequence produced by the given iterator of bases,
+    /// without storing the sequence.
+    #[inline]
+    pub fn iter_from<I: Iterator<Item = u8>>(self, bases: I) -> BasesMinimizerIterator<I, T, S> {
+        self.assert_default_base_order();
+        BasesMinimizerIterator::new(
+            bases,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizer of every k-mer of the given sequence and its position, with `k = width + minimizer_size - 1`.
+    /// Each k-mer spans exactly one window, so it yields `seq.len() - k + 1` items without collapsing repeated minimizers.
+    #[inline]
+    pub fn iter_per_kmer(self, seq: &[u8]) -> KmerMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        KmerMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, selected with the given empty queue.
+    /// The hasher of the builder is ignored, and the width of the window must match the one of the queue.
+    #[inline]
+    pub fn iter_with_queue<Q: MinWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> MinimizerIterator<T, S, Q> {
+        assert_eq!(
+            queue.width(),
+            self.width as usize,
+            "the width of the queue must match the width of the builder"
+        );
+        let mut iter =
+            MinimizerIterator::with_queue(seq, self.minimizer_size, queue, self.encoding);
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Builds an iterator over the positions of the minimizers in the given sequence, selected with the given empty queue.
+    /// The hasher of the builder is ignored, and the width of the window must match the one of the queue.
+    #[inline]
+    pub fn iter_pos_with_queue<Q: MinPosWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> MinimizerPosIterator<T, S, Q> {
+        assert_eq!(
+            queue.width(),
+            self.width as usize,
+            "the width of the queue must match the width of the builder"
+        );
+        let mut iter =
+            MinimizerPosIterator::with_queue(seq, self.minimizer_size, queue, self.encoding);
+        iter.base_order = self.base_order;
+        iter
+    }
+
+    /// Returns the distinct minimizers and their positions selected by the windows containing the base at position `p`,
+    /// i.e. the windows starting in `p + 1 - base_width..=p`, clamped to the bounds of the sequence.
+    pub fn minimizers_covering(self, seq: &[u8], p: usize) -> Vec<(T, usize)>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        assert!(p < seq.len(), "position {p} is out of bounds");
+        let base_width = self.width as usize + self.minimizer_size - 1;
+        let from = (p + 1).saturating_sub(base_width);
+        let to = min(seq.len(), p + base_width);
+        let mut mins: Vec<_> = self
+            .iter_windows(&seq[from..to])
+            .map(|(i, min, offset)| (min, from + i + offset))
+            .collect();
+        // windows are ordered and positions are non-decreasing
+        mins.dedup_by_key(|&mut (_, pos)| pos);
+        mins
+    }
+
+    /// Builds an iterator over the minimizers of a pair of reads, yielding the mate (1 or 2), the minimizer and its position in the read.
+    /// If `rc_mate2` is set, the minimizers of the second mate are computed on its reverse complement,
+    /// and their positions are given on the reverse complement.
+    #[inline]
+    pub fn iter_pairs<'a>(
+        self,
+        reads: (&'a [u8], &[u8]),
+        rc_mate2: bool,
+    ) -> PairedMinimizerIterator<'a, T, S>
+    where
+        S: Clone,
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.assert_default_base_order();
+        PairedMinimizerIterator::new(
+            reads,
+            rc_mate2,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+            self.complement,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, only considering the windows starting at a multiple of `stride`.
+    /// The last window is only considered if its start is a multiple of `stride`, and positions remain absolute.
+    #[inline]
+    pub fn iter_strided(self, seq: &[u8], stride: usize) -> StridedMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        StridedMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            stride,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers and their positions in `seq[start..end]`,
+    /// yielding exactly the minimizers of [`iter`](Self::iter) on the whole sequence that are positioned in `start..end`.
+    /// Only the windows overlapping the range are processed, so the rest of the sequence is never scanned.
+    #[inline]
+    pub fn iter_range(
+        self,
+        seq: &[u8],
+        start: usize,
+        end: usize,
+    ) -> RangeIterator<MinimizerIterator<T, S>> {
+        assert!(
+            start <= end && end <= seq.len(),
+            "invalid range {start}..{end} for a sequence of length {}",
+            seq.len()
+        );
+        // the windows selecting a minimizer in the range start at most width - 1 m-mers before it
+        let from = start.saturating_sub(self.width as usize - 1);
+        let to = min(
+            seq.len(),
+            end + self.width as usize + self.minimizer_size - 2,
+        );
+        let mut iter = MinimizerIterator::new(
+            &seq[from..to],
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        );
+        iter.base_order = self.base_order;
+        RangeIterator::new(iter, from, start, end)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given circular sequence.
+    /// Windows straddling the origin are considered and positions are given modulo the length of the sequence.
+    #[inline]
+    pub fn iter_circular(self, seq: &[u8]) -> CircularMinimizerIterator<T, S> {
+        self.assert_default_base_order();
+        CircularMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence with their stable [`fingerprint`] and their positions.
+    /// The fingerprint does not depend on the hasher, which makes it suitable as a portable key.
+    #[inline]
+    pub fn iter_fingerprint(self, seq: &[u8]) -> FingerprintIterator<MinimizerIterator<T, S>> {
+        FingerprintIterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence with their positions, keyed by their 128-bit [`fingerprint128`].
+    /// The minimizers are still selected with the hasher, and the key is only computed for the yielded minimizers.
+    /// Distinct minimizers never share a key, which makes it suitable for exact indexes where 64-bit hashes may collide.
+    #[inline]
+    pub fn key128(self, seq: &[u8]) -> Key128Iterator<MinimizerIterator<T, S>> {
+        Key128Iterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence,
+    /// with the 1-based number of occurrences of each minimizer so far in the sequence.
+    #[inline]
+    pub fn iter_with_running_count(
+        self,
+        seq: &[u8],
+    ) -> RunningCountIterator<MinimizerIterator<T, S>, T> {
+        RunningCountIterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence,
+    /// skipping a minimizer if the same value was yielded less than `distance` bases before, e.g. to thin the anchors in tandem repeats.
+    /// Unlike a global deduplication, occurrences far enough apart are all kept.
+    #[inline]
+    pub fn suppress_within(
+        self,
+        seq: &[u8],
+        distance: usize,
+    ) -> SuppressIterator<MinimizerIterator<T, S>, T>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        SuppressIterator::new(self.iter(seq), distance)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence,
+    /// skipping a minimizer if it is less than `distance` bases away from the last yielded one, e.g. to build a sparser index.
+    /// The first minimizer is always yielded.
+    #[inline]
+    pub fn min_distance(
+        self,
+        seq: &[u8],
+        distance: usize,
+    ) -> MinDistanceIterator<MinimizerIterator<T, S>> {
+        MinDistanceIterator::new(self.iter(seq), distance)
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence with the interval of bases for which each of them is selected,
+    /// yielding `(minimizer, start, end)` with `end` excluded, which can be used to partition the sequence by minimizer.
+    /// See [`OwnershipIterator`] for the overlap between consecutive intervals.
+    #[inline]
+    pub fn ownership_intervals(self, seq: &[u8]) -> OwnershipIterator<T, S> {
+        OwnershipIterator::new(self.iter_windows(seq))
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence,
+    /// excluding those only selected by windows within `width` windows of either end of the sequence.
+    /// This removes the end effects when comparing the density with [`expected_density`](Self::expected_density).
+    #[inline]
+    pub fn trim_ends(self, seq: &[u8]) -> TrimEndsIterator<T, S> {
+        TrimEndsIterator::new(self.ownership_intervals(seq))
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence whose hash is strictly smaller than
+    /// the hashes of the previous and the next minimizers, giving a sparser set of anchors without changing the width.
+    /// See [`LocalMinimaIterator`] for the handling of the first and last minimizers and of ties.
+    #[inline]
+    pub fn iter_local_minima(self, seq: &[u8]) -> LocalMinimaIterator<MinimizerIterator<T, S>, T, S>
+    where
+        S: Clone,
+    {
+        let hasher = self.hasher.clone();
+        LocalMinimaIterator::new(self.iter(seq), hasher)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, with the number of G/C bases in each minimizer.
+    /// The codes of C and G are taken from the encoding of the builder.
+    #[inline]
+    pub fn iter_with_gc(self, seq: &[u8]) -> GcCountIterator<MinimizerIterator<T, S>, T> {
+        let minimizer_size = self.minimizer_size;
+        let encoding = self.encoding;
+        GcCountIterator::new(self.iter(seq), minimizer_size, &encoding)
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence, with a hash of each minimizer computed by `secondary_hasher`.
+    /// The secondary hash does not affect the selection, which makes it useful to detect collisions of the primary hasher.
+    #[inline]
+    pub fn iter_with_secondary_hasher<H: BuildHasher>(
+        self,
+        seq: &[u8],
+        secondary_hasher: H,
+    ) -> SecondaryHashIterator<MinimizerIterator<T, S>, H> {
+        SecondaryHashIterator::new(self.iter(seq), secondary_hasher)
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence and their positions, keeping only the minimizers in `set`.
+    #[inline]
+    pub fn retain_values(
+        self,
+        seq: &[u8],
+        set: Arc<HashSet<T>>,
+    ) -> RetainIterator<MinimizerIterator<T, S>, T> {
+        RetainIterator::new(self.iter(seq), set)
+    }
+
+    /// Builds an iterator over the minimizers in the given sequence and their positions, never selecting the values in `set`.
+    /// Each window selects its smallest m-mer outside of `set` instead, so this changes which minimizer represents the window,
+    /// see [`PenalizedMinimizerIterator`] for the impact on the density.
+    #[inline]
+    pub fn exclude_values<'a>(
+        self,
+        seq: &'a [u8],
+        set: Arc<HashSet<T>>,
+    ) -> PenalizedMinimizerIterator<'a, T, S, impl Fn(T, usize) -> bool + 'a>
+    where
+        S: Clone,
+        T: 'a,
+    {
+        self.iter_penalized(seq, move |mmer, _| set.contains(&mmer))
+    }
+
+    /// Builds an index over the minimizers and their positions in the given sequence.
+    #[inline]
+    pub fn index(self, seq: &[u8]) -> MinimizerIndex<T>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).collect()
+    }
+
+    /// Calls `f` on the minimizers and their positions in the given sequence until it returns [`ControlFlow::Break`],
+    /// in which case the rest of the sequence is not processed.
+    #[inline]
+    pub fn for_each_until<F: FnMut((T, usize)) -> ControlFlow<()>>(
+        self,
+        seq: &[u8],
+        f: F,
+    ) -> ControlFlow<()>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).try_for_each(f)
+    }
+
+    /// Feeds the minimizers and their positions in the given sequence to `sink`, without collecting them.
+    /// Unlike [`iter`](Self::iter), the rolling loop pushes each minimizer to the sink as soon as it is selected.
+    #[inline]
+    pub fn into_counter<K: MinimizerSink<T>>(self, seq: &[u8], sink: &mut K)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).feed(sink);
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, Minimizer, S, true> {
+    /// Accepts even widths for canonical minimizers.
+    /// In a window of even width, ties between multiple minimizers are broken with the orientation of the central (minimizer_size + 1)-mer
+    /// instead of the central m-mer, so that reverse complementing the sequence still gives the mirrored minimizers.
+    /// This does not hold in windows whose central (minimizer_size + 1)-mer is its own reverse complement, e.g. `GGCC`, which requires an odd minimizer size:
+    /// ties then keep the leftmost minimizer on both strands, so the minimizers of the reverse complement are not mirrored.
+    /// Odd widths keep the usual tie-breaking rule.
+    /// This applies to [`iter`](Self::iter), [`iter_pos`](Self::iter_pos) and the methods built on them;
+    /// [`iter_windows`](Self::iter_windows) and [`iter_circular`](Self::iter_circular) still require an odd width.
+    pub fn even_width_canonical(mut self) -> Self {
+        self.even_width = true;
+        self
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`].
+    /// It requires an odd width to break ties between multiple minimizers, unless [`even_width_canonical`](Self::even_width_canonical) is set.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> CanonicalMinimizerIterator<T, S> {
+        self.assert_ranks(self.minimizer_size);
+        if self.even_width {
+            return CanonicalMinimizerIterator::new_even_width(
+                seq,
+                self.minimizer_size,
+                self.width,
+                self.hasher,
+                self.encoding,
+                self.complement,
+            );
+        }
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+            self.complement,
+        )
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence, reported on the annotated `strand`:
+    /// each minimizer is yielded as the m-mer read on that strand, along with the strand.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_stranded(
+        self,
+        seq: &[u8],
+        strand: Strand,
+    ) -> StrandedIterator<CanonicalMinimizerIterator<T, S>> {
+        let minimizer_size = self.minimizer_size;
+        let rc_codes = self.rc_codes;
+        StrandedIterator::new(self.iter(seq), strand, minimizer_size, rc_codes)
+    }
+
+    /// Builds an iterator over the canonical minimizers in the given sequence and their positions counted from the end of the sequence
+    /// with their [`Strand`], i.e. `seq.len() - pos - minimizer_size` for a minimizer starting at `pos`.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn positions_from_end(
+        self,
+        seq: &[u8],
+    ) -> CanonicalFromEndIterator<CanonicalMinimizerIterator<T, S>> {
+        let last_mmer = seq.len().saturating_sub(self.minimizer_size);
+        CanonicalFromEndIterator::new(self.iter(seq), last_mmer)
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// and a boolean indicating whether the minimizer is its own reverse complement, i.e. `(min, pos, strand, is_palindrome)`.
+    /// The strand of a palindromic minimizer is ambiguous, which matters when counting minimizers per strand.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_with_palindrome(
+        self,
+        seq: &[u8],
+    ) -> PalindromeIterator<CanonicalMinimizerIterator<T, S>> {
+        let minimizer_size = self.minimizer_size;
+        let rc_codes = self.rc_codes;
+        PalindromeIterator::new(self.iter(seq), minimizer_size, rc_codes)
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// with the previous canonical minimizer of each one, i.e. `(prev, min, pos, strand)` where `prev` is `None` for the first minimizer.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_transitions(
+        self,
+        seq: &[u8],
+    ) -> CanonicalTransitionIterator<CanonicalMinimizerIterator<T, S>, T> {
+        CanonicalTransitionIterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// keyed by the 128-bit [`fingerprint128`] of the canonical minimizer, i.e. `(key, min, pos, strand)`.
+    /// The minimizers are still selected with the hasher, and the key is only computed for the yielded minimizers.
+    /// Distinct canonical minimizers never share a key, which makes it suitable for exact indexes where 64-bit hashes may collide.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn key128(self, seq: &[u8]) -> CanonicalKey128Iterator<CanonicalMinimizerIterator<T, S>> {
+        CanonicalKey128Iterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// skipping a minimizer if the same canonical value was yielded less than `distance` bases before, on either strand.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn suppress_within(
+        self,
+        seq: &[u8],
+        distance: usize,
+    ) -> CanonicalSuppressIterator<CanonicalMinimizerIterator<T, S>, T>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        CanonicalSuppressIterator::new(self.iter(seq), distance)
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// skipping a minimizer if it is less than `distance` bases away from the last yielded one, e.g. to build a sparser index.
+    /// The first minimizer is always yielded.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn min_distance(
+        self,
+        seq: &[u8],
+        distance: usize,
+    ) -> CanonicalMinDistanceIterator<CanonicalMinimizerIterator<T, S>> {
+        CanonicalMinDistanceIterator::new(self.iter(seq), distance)
+    }
+
+    /// Builds an iterator over the positions of the canonical minimizers in the given sequence with their [`Strand`].
+    /// It requires an odd width to break ties between multiple minimizers, unless [`even_width_canonical`](Self::even_width_canonical) is set.
+    #[inline]
+    pub fn iter_pos(self, seq: &[u8]) -> CanonicalMinimizerPosIterator<T, S> {
+        self.assert_ranks(self.minimizer_size);
+        if self.even_width {
+            return CanonicalMinimizerPosIterator::new_even_width(
+                seq,
+                self.minimizer_size,
+                self.width,
+                self.hasher,
+                self.encoding,
+                self.complement,
+            );
+        }
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalMinimizerPosIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+            self.complement,
+        )
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// borrowing the builder so that it can be reused for many sequences without cloning its hasher or copying its encoding.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_ref<'a>(&'a self, seq: &'a [u8]) -> CanonicalMinimizerRefIterator<'a, T, S> {
+        self.assert_ranks(self.minimizer_size);
+        if !self.even_width {
+            assert_eq!(
+                self.width % 2,
+                1,
+                "width must be odd to break ties between multiple minimizers"
+            );
+        }
+        CanonicalMinimizerIterator::with_rc_encoding(
+            seq,
+            self.minimizer_size,
+            self.width,
+            BuildHasherRef(&self.hasher),
+            &self.encoding,
+            &self.rc_encoding,
+        )
+    }
+
+    /// Builds an iterator over the positions of the canonical minimizers in the given sequence with their [`Strand`],
+    /// borrowing the builder so that it can be reused for many sequences without cloning its hasher or copying its encoding.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_pos_ref<'a>(&'a self, seq: &'a [u8]) -> CanonicalMinimizerPosRefIterator<'a, T, S> {
+        self.assert_ranks(self.minimizer_size);
+        if !self.even_width {
+            assert_eq!(
+                self.width % 2,
+                1,
+                "width must be odd to break ties between multiple minimizers"
+            );
+        }
+        CanonicalMinimizerPosIterator::with_rc_encoding(
+            seq,
+            self.minimizer_size,
+            self.width,
+            BuildHasherRef(&self.hasher),
+            &self.encoding,
+            &self.rc_encoding,
+        )
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`], selected with the given empty queue.
+    /// The hasher of the builder is ignored, and the width of the window must match the one of the queue.
+    /// It requires an odd width to break ties between multiple minimizers, unless [`even_width_canonical`](Self::even_width_canonical) is set.
+    #[inline]
+    pub fn iter_with_queue<Q: MinWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> CanonicalMinimizerIterator<T, S, Q> {
+        assert_eq!(
+            queue.width(),
+            self.width as usize,
+            "the width of the queue must match the width of the builder"
+        );
+        if !self.even_width {
+            assert_eq!(
+                self.width % 2,
+                1,
+                "width must be odd to break ties between multiple minimizers"
+            );
+        }
+        CanonicalMinimizerIterator::with_queue(
+            seq,
+            self.minimizer_size,
+            queue,
+            self.encoding,
+            self.rc_encoding,
+        )
+    }
+
+    /// Builds an iterator over the positions of the canonical minimizers in the given sequence with their [`Strand`], selected with the given empty queue.
+    /// The hasher of the builder is ignored, and the width of the window must match the one of the queue.
+    /// It requires an odd width to break ties between multiple minimizers, unless [`even_width_canonical`](Self::even_width_canonical) is set.
+    #[inline]
+    pub fn iter_pos_with_queue<Q: MinPosWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> CanonicalMinimizerPosIterator<T, S, Q> {
+        assert_eq!(
+            queue.width(),
+            self.width as usize,
+            "the width of the queue must match the width of the builder"
+        );
+        if !self.even_width {
+            assert_eq!(
+                self.width % 2,
+                1,
+                "width must be odd to break ties between multiple minimizers"
+            );
+        }
+        CanonicalMinimizerPosIterator::with_queue(
+            seq,
+            self.minimizer_size,
+            queue,
+            self.encoding,
+            self.rc_encoding,
+        )
+    }
+
+    /// Returns the canonical minimizer of the first window of the given sequence and its position,
+    /// or `None` if the sequence is shorter than a window.
+    #[inline]
+    pub fn first(self, seq: &[u8]) -> Option<(T, usize, Strand)>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).next()
+    }
+
+    /// Returns the number of canonical minimizers in the given sequence, without building the minimizers themselves.
+    #[inline]
+    pub fn count(self, seq: &[u8]) -> usize
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter_pos(seq).count()
+    }
+
+    /// Returns the observed density of the canonical minimizers in the given sequence, i.e. the fraction of m-mers that are selected.
+    #[inline]
+    pub fn density(self, seq: &[u8]) -> f64
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let num_mmers = (seq.len() + 1).saturating_sub(self.minimizer_size);
+        if num_mmers == 0 {
+            return 0.0;
+        }
+        self.count(seq) as f64 / num_mmers as f64
+    }
+
+    /// Builds an iterator over the canonical minimizers in the given sequence as slices borrowed from the sequence, with its position and its [`Strand`].
+    /// Each slice is `&seq[pos..(pos + minimizer_size)]`, so no decoding is needed.
+    #[inline]
+    pub fn iter_kmers(
+        self,
+        seq: &[u8],
+    ) -> CanonicalKmerIterator<CanonicalMinimizerPosIterator<T, S>> {
+        CanonicalKmerIterator::new(seq, self.minimizer_size, self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the canonical minimizer of every window of the given sequence, yielding the index of the window,
+    /// the minimizer, its offset in the window and its [`Strand`].
+    /// Consecutive windows sharing the same minimizer are not collapsed, so it yields `seq.len() - base_width + 1` items.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_windows(self, seq: &[u8]) -> CanonicalWindowMinimizerIterator<T, S> {
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalWindowMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+            self.complement,
+        )
+    }
+
+    /// Returns the distinct canonical minimizers and their positions with their [`Strand`],
+    /// selected by the windows containing the base at position `p`,
+    /// i.e. the windows starting in `p + 1 - base_width..=p`, clamped to the bounds of the sequence.
+    pub fn minimizers_covering(self, seq: &[u8], p: usize) -> Vec<(T, usize, Strand)>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        assert!(p < seq.len(), "position {p} is out of bounds");
+        let base_width = self.width as usize + self.minimizer_size - 1;
+        let from = (p + 1).saturating_sub(base_width);
+        let to = min(seq.len(), p + base_width);
+        let mut mins: Vec<_> = self
+            .iter_windows(&seq[from..to])
+            .map(|(i, min, offset, strand)| (min, from + i + offset, strand))
+            .collect();
+        // windows are ordered and positions are non-decreasing
+        mins.dedup_by_key(|&mut (_, pos, _)| pos);
+        mins
+    }
+
+    /// Builds an iterator over the canonical minimizers and their positions in the given circular sequence with their [`Strand`].
+    /// Windows straddling the origin are considered and positions are given modulo the length of the sequence.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter_circular(self, seq: &[u8]) -> CanonicalCircularMinimizerIterator<T, S> {
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalCircularMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+            self.complement,
+        )
+    }
+
+    /// Calls `f` on the canonical minimizers and their positions with their [`Strand`] in the given sequence until it returns [`ControlFlow::Break`],
+    /// in which case the rest of the sequence is not processed.
+    #[inline]
+    pub fn for_each_until<F: FnMut((T, usize, Strand)) -> ControlFlow<()>>(
+        self,
+        seq: &[u8],
+        f: F,
+    ) -> ControlFlow<()>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).try_for_each(f)
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher, const CANONICAL: bool>
+    MinimizerBuilder<T, Minimizer, S, CANONICAL>
+{
+    /// Checks the parameters of the builder, returning every problem at once instead of panicking when building an iterator.
+    pub fn validate(&self) -> Result<(), Vec<BuildError>> {
+        let mut errors = self._errors();
+        errors.extend(self._rank_error(self.minimizer_size));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the expected density of random minimizers, i.e. the expected fraction of m-mers selected in a random sequence: `2 / (width + 1)`.
+    /// It can be compared with the observed density given by `density`.
+    pub fn expected_density(&self) -> f64 {
+        2.0 / (self.width as f64 + 1.0)
+    }
+
+    /// Returns the expected number of distinct minimizers in a random sequence of length `seq_len`, e.g. to pre-size a hash map before scanning it.
+    ///
+    /// It assumes that the bases are uniform and independent and that the hasher behaves like a random order.
+    /// For large minimizer sizes, this is close to `expected_density * (seq_len - minimizer_size + 1)`, i.e. all selected m-mers are distinct.
+    /// For small minimizer sizes, the `4^minimizer_size` m-mers (about half as many for canonical minimizers) occur many times,
+    /// and the m-mers with a small hash are selected repeatedly while those with a large hash are rarely selected, which this estimate accounts for.
+    /// The repeats of real sequences are not accounted for, so it usually overestimates the number of distinct minimizers of genomic data.
+    pub fn expected_distinct(&self, seq_len: usize) -> f64 {
+        self._expected_distinct(seq_len, self.minimizer_size)
+    }
+
+    /// Returns the hash used to order the given m-mer, i.e. the value the iterators compare to select minimizers.
+    /// The m-mer is packed with the encoding and the base order of the builder, canonicalized for canonical minimizers, and hashed with its hasher,
+    /// so that queries can be hashed exactly like the indexed minimizers.
+    /// It panics if the length of the m-mer differs from the minimizer size.
+    pub fn hash_kmer(&self, kmer: &[u8]) -> u64
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        assert_eq!(
+            kmer.len(),
+            self.minimizer_size,
+            "the length of the m-mer must be equal to minimizer_size"
+        );
+        let pack = |mmer: T, &base: &u8| (mmer << 2) | self.encoding[base as usize].as_();
+        let mmer = match self.base_order {
+            BaseOrder::MostSignificantFirst => kmer.iter().fold(T::zero(), pack),
+            BaseOrder::LeastSignificantFirst => kmer.iter().rev().fold(T::zero(), pack),
+        };
+        if CANONICAL {
+            self.hasher.hash_one(self.canonical_kmer(mmer).0)
+        } else {
+            self.hasher.hash_one(mmer)
+        }
+    }
+}
+
+impl<S: BuildHasher, const CANONICAL: bool> MinimizerBuilder<u64, Minimizer, S, CANONICAL> {
+    /// Selects canonical minimizers following the conventions of minimap2's `mm_sketch`,
+    /// see [`Minimap2Iterator`] for the exact conventions.
+    /// The hasher and the encoding are ignored in favor of those of minimap2.
+    /// It panics if the bases are not packed in the default order [`BaseOrder::MostSignificantFirst`].
+    pub fn minimap2_compat(self) -> MinimizerBuilder<u64, Minimap2, S, true> {
+        assert!(
+            self.base_order == BaseOrder::MostSignificantFirst,
+            "minimap2 minimizers only support BaseOrder::MostSignificantFirst"
+        );
+        MinimizerBuilder::<u64, Minimap2, S, true> {
+            minimizer_size: self.minimizer_size,
+            width: self.width,
+            hasher: self.hasher,
+            encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: BuildHasher> MinimizerBuilder<u64, Minimap2, S, true> {
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// following the conventions of minimap2's `mm_sketch`.
+    /// It requires minimizer_size ≤ 28 and width < 256, as minimap2 does.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> Minimap2Iterator {
+        Minimap2Iterator::new(seq, self.minimizer_size, self.width)
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher, const CANONICAL: bool>
+    MinimizerBuilder<T, Minimizer, S, CANONICAL>
+{
+    /// Orders the minimizers by their [ntHash](https://doi.org/10.1093/bioinformatics/btw397), which is rolled in constant time per base.
+    /// The hasher is ignored, and the encoding is only used to pack the minimizers.
+    /// It panics if the bases are not packed in the default order [`BaseOrder::MostSignificantFirst`].
+    pub fn nthash(self) -> MinimizerBuilder<T, NtHash, S, CANONICAL> {
+        assert!(
+            self.base_order == BaseOrder::MostSignificantFirst,
+            "ntHash minimizers only support BaseOrder::MostSignificantFirst"
+        );
+        MinimizerBuilder::<T, NtHash, S, CANONICAL> {
+            minimizer_size: self.minimizer_size,
+            width: self.width,
+            hasher: self.hasher,
+            encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Selects minimizers over a binary alphabet, where the encoding maps each base to a single bit,
+    /// e.g. `.encoding(0, 1, 0, 1)` for purines and pyrimidines.
+    /// The minimizer size can then be as large as the number of bits of `T`, e.g. 64 for `u64`,
+    /// so it must be set after this call.
+    /// It panics if the bases are not packed in the default order [`BaseOrder::MostSignificantFirst`].
+    pub fn binary(self) -> MinimizerBuilder<T, Binary, S, CANONICAL> {
+        assert!(
+            self.base_order == BaseOrder::MostSignificantFirst,
+            "binary minimizers only support BaseOrder::MostSignificantFirst"
+        );
+        MinimizerBuilder::<T, Binary, S, CANONICAL> {
+            minimizer_size: self.minimizer_size,
+            width: self.width,
+            hasher: self.hasher,
+            encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Orders the minimizers lexicographically, following the order of the encoding.
+    /// The packed m-mers are used as their own hash, so the hasher is ignored and never called.
+    /// It panics if the bases are not packed in the default order [`BaseOrder::MostSignificantFirst`].
+    pub fn lexicographic(self) -> MinimizerBuilder<T, Lexicographic, S, CANONICAL> {
+        assert!(
+            self.base_order == BaseOrder::MostSignificantFirst,
+            "lexicographic minimizers only support BaseOrder::MostSignificantFirst"
+        );
+        MinimizerBuilder::<T, Lexicographic, S, CANONICAL> {
+            minimizer_size: self.minimizer_size,
+            width: self.width,
+            hasher: self.hasher,
+            encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, Binary, S, false> {
+    /// Checks the parameters of the builder, returning every problem at once instead of panicking when building an iterator.
+    pub fn validate(&self) -> Result<(), Vec<BuildError>> {
+        let errors = self._errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Builds an iterator over the minimizers and their positions in the given sequence over a binary alphabet.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> BinaryMinimizerIterator<T, S> {
+        BinaryMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.hasher,
+            self.encoding,
+        )
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, Lexicographic, S, false> {
+    /// Builds an iterator over the lexicographic minimizers and their positions in the given sequence.
+    /// It requires `minimizer_size ≤ 32`.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> LexMinimizerIterator<T> {
+        LexMinimizerIterator::new(seq, self.minimizer_size, self.width, self.encoding)
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, NtHash, S, false> {
+    /// Builds an iterator over the minimizers and their positions in the given sequence, ordered by their forward ntHash.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> NtHashMinimizerIterator<T> {
+        NtHashMinimizerIterator::new(seq, self.minimizer_size, self.width, self.encoding)
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, NtHash, S, true> {
+    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with their [`Strand`],
+    /// ordered by their canonical ntHash.
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> CanonicalNtHashMinimizerIterator<T> {
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalNtHashMinimizerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self.encoding,
+            self.complement,
+        )
+    }
+}
+
+/// The default parameter r of mod-minimizers.
+const R: usize = 4;
+
+impl<T: PrimInt + Hash> MinimizerBuilder<T, ModMinimizer> {
+    /// Sets up the `MinimizerBuilder` for mod-minimizers with default values:
+    /// - minimizer_size = [`DEFAULT_MINIMIZER_SIZE`](Self::DEFAULT_MINIMIZER_SIZE) = 21
+    /// - width = [`DEFAULT_WIDTH`](Self::DEFAULT_WIDTH) = 11 (31 - 21 + 1)
+    /// - hasher = [`DefaultHashBuilder`]
+    /// - encoding: A = `00`, C = `01`, G = `10`, T = `11`
+    /// - complement: [`COMPLEMENT`], following the IUPAC codes
+    #[inline]
+    pub fn new_mod() -> Self {
+        Self::_new()
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher, const CANONICAL: bool>
+    MinimizerBuilder<T, ModMinimizer, S, CANONICAL>
+{
+    /// Sets the size of the t-mers used to select mod-minimizers.
+    /// It must satisfy 1 ≤ t ≤ minimizer_size, and defaults to `r + ((minimizer_size - r) % width)`.
+    pub fn t(mut self, t: usize) -> Self {
+        assert!(t >= 1, "t must be ≥ 1");
+        self.t = Some(t);
+        self
+    }
+
+    /// Sets the parameter r used to derive the default size of the t-mers, i.e. `t = r + ((minimizer_size - r) % width)`.
+    /// It must satisfy 1 ≤ r ≤ minimizer_size, and defaults to 4. It is ignored if t is set explicitly.
+    pub fn r(mut self, r: usize) -> Self {
+        assert!(r >= 1, "r must be ≥ 1");
+        self.r = r;
+        self
+    }
+
+    /// Checks the parameters of the builder, returning every problem at once instead of panicking when building an iterator.
+    pub fn validate(&self) -> Result<(), Vec<BuildError>> {
+        let mut errors = self._errors();
+        let t = match self.t {
+            Some(t) if t == 0 || t > self.minimizer_size => {
+                errors.push(BuildError::InvalidT {
+                    t,
+                    minimizer_size: self.minimizer_size,
+                });
+                None
+            }
+            Some(t) => Some(t),
+            None if self.minimizer_size < self.r => {
+                errors.push(BuildError::MinimizerSizeTooSmall {
+                    minimizer_size: self.minimizer_size,
+                    r: self.r,
+                });
+                None
+            }
+            None => (self.minimizer_size - self.r)
+                .checked_rem(self.width as usize)
+                .map(|rem| self.r + rem),
+        };
+        if let Some(t) = t {
+            if self.width as usize + self.minimizer_size - t > u16::MAX as usize {
+                errors.push(BuildError::TooManyTmers {
+                    t,
+                    minimizer_size: self.minimizer_size,
+                    width: self.width,
+                });
+            }
+            let rem = (self.minimizer_size - t).checked_rem(self.width as usize);
+            if CANONICAL && rem.is_some_and(|rem| rem != 0) {
+                errors.push(BuildError::NonCanonicalT {
+                    t,
+                    minimizer_size: self.minimizer_size,
+                    width: self.width,
+                });
+            }
+            errors.extend(self._rank_error(t));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the expected density of random mod-minimizers, i.e. the expected fraction of m-mers selected in a random sequence:
+    /// `(2 + ⌊(ℓ - t) / width⌋) / (ℓ - t + 2)` where `ℓ = width + minimizer_size - 1`.
+    /// It can be compared with the observed density given by `density`.
+    pub fn expected_density(&self) -> f64 {
+        let t = self._t();
+        let w = self.width as usize;
+        let l = w + self.minimizer_size - 1;
+        (2 + (l - t) / w) as f64 / (l - t + 2) as f64
+    }
+
+    /// Returns the expected number of distinct mod-minimizers in a random sequence of length `seq_len`, e.g. to pre-size a hash map before scanning it.
+    ///
+    /// It makes the same assumptions as for random minimizers, with the hasher ordering the t-mers instead of the m-mers.
+    pub fn expected_distinct(&self, seq_len: usize) -> f64 {
+        self._expected_distinct(seq_len, self._t())
+    }
+
+    fn _t(&self) -> usize {
+        match self.t {
+            Some(t) => {
+                assert!(
+                    t <= self.minimizer_size,
+                    "t must be ≤ minimizer_size={}",
+                    self.minimizer_size
+                );
+                t
+            }
+            None => {
+                assert!(
+                    self.minimizer_size >= self.r,
+                    "mod-minimizers require minimizer_size ≥ r={}",
+                    self.r
+                );
+                self.r + ((self.minimizer_size - self.r) % self.width as usize)
+            }
+        }
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, ModMinimizer, S, false> {
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> ModSamplingIterator<T, S> {
+        self.assert_ranks(self._t());
+        ModSamplingIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self._t(),
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence,
+    /// with the packed t-mer that selected each of them and its offset from the start of the mod-minimizer.
+    #[inline]
+    pub fn iter_with_tmer(self, seq: &[u8]) -> ModSamplingTmerIterator<T, S> {
+        self.assert_ranks(self._t());
+        ModSamplingTmerIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self._t(),
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the positions of the mod-minimizers in the given sequence.
+    #[inline]
+    pub fn iter_pos(self, seq: &[u8]) -> ModSamplingPosIterator<T, S> {
+        self.assert_ranks(self._t());
+        ModSamplingPosIterator::new(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self._t(),
+            self.hasher,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence, selected with the given empty queue over the t-mers.
+    /// The hasher of the builder is ignored, and the width of the queue must be `width + minimizer_size - t`.
+    #[inline]
+    pub fn iter_with_queue<Q: MinPosWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> ModSamplingIterator<T, S, Q> {
+        ModSamplingIterator::with_queue(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self._t(),
+            queue,
+            self.encoding,
+        )
+    }
+
+    /// Builds an iterator over the positions of the mod-minimizers in the given sequence, selected with the given empty queue over the t-mers.
+    /// The hasher of the builder is ignored, and the width of the queue must be `width + minimizer_size - t`.
+    #[inline]
+    pub fn iter_pos_with_queue<Q: MinPosWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> ModSamplingPosIterator<T, S, Q> {
+        ModSamplingPosIterator::with_queue(
+            seq,
+            self.minimizer_size,
+            self.width,
+            self._t(),
+            queue,
+            self.encoding,
+        )
+    }
+
+    /// Returns the mod-minimizer of the first window of the given sequence and its position,
+    /// or `None` if the sequence is shorter than a window.
+    #[inline]
+    pub fn first(self, seq: &[u8]) -> Option<(T, usize)>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).next()
+    }
+
+    /// Returns the number of mod-minimizers in the given sequence, without building the minimizers themselves.
+    #[inline]
+    pub fn count(self, seq: &[u8]) -> usize
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter_pos(seq).count()
+    }
+
+    /// Returns the observed density of the mod-minimizers in the given sequence, i.e. the fraction of m-mers that are selected.
+    #[inline]
+    pub fn density(self, seq: &[u8]) -> f64
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let num_mmers = (seq.len() + 1).saturating_sub(self.minimizer_size);
+        if num_mmers == 0 {
+            return 0.0;
+        }
+        self.count(seq) as f64 / num_mmers as f64
+    }
+
+    /// Builds an iterator over the mod-minimizers in the given sequence as slices borrowed from the sequence, with its position.
+    /// Each slice is `&seq[pos..(pos + minimizer_size)]`, so no decoding is needed.
+    #[inline]
+    pub fn iter_kmers(self, seq: &[u8]) -> KmerIterator<ModSamplingPosIterator<T, S>> {
+        KmerIterator::new(seq, self.minimizer_size, self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the positions of the mod-minimizers in the given sequence as `u32`, to save memory when collecting them.
+    /// Positions are checked to fit in a `u32` in debug builds only.
+    #[inline]
+    pub fn iter_pos_u32(self, seq: &[u8]) -> U32PosIterator<ModSamplingPosIterator<T, S>> {
+        U32PosIterator::new(self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the gaps between the positions of consecutive mod-minimizers in the given sequence.
+    /// The first item is the position of the first minimizer, so that the prefix sums of the gaps give back the positions.
+    #[inline]
+    pub fn iter_gaps(self, seq: &[u8]) -> GapIterator<ModSamplingPosIterator<T, S>> {
+        GapIterator::new(self.iter_pos(seq))
+    }
+
+    /// Builds an iterator over the gaps longer than `threshold` between the positions of consecutive mod-minimizers in the given sequence,
+    /// as ranges from the position of a mod-minimizer to the position of the next one, e.g. to flag regions poorly covered by anchors.
+    /// Like [`max_gap`](Self::max_gap), it considers the start of the sequence and the last m-mer as endpoints.
+    #[inline]
+    pub fn gaps_longer_than(
+        self,
+        seq: &[u8],
+        threshold: usize,
+    ) -> LongGapIterator<ModSamplingPosIterator<T, S>> {
+        let last_mmer = seq.len().saturating_sub(self.minimizer_size);
+        LongGapIterator::new(self.iter_pos(seq), threshold, last_mmer)
+    }
+
+    /// Builds an iterator over the mod-minimizers in the given sequence with their stable [`fingerprint`] and their positions.
+    /// The fingerprint does not depend on the hasher, which makes it suitable as a portable key.
+    #[inline]
+    pub fn iter_fingerprint(self, seq: &[u8]) -> FingerprintIterator<ModSamplingIterator<T, S>> {
+        FingerprintIterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the mod-minimizers in the given sequence with their positions, keyed by their 128-bit [`fingerprint128`].
+    /// The mod-minimizers are still selected with the hasher, and the key is only computed for the yielded mod-minimizers.
+    /// Distinct mod-minimizers never share a key, which makes it suitable for exact indexes where 64-bit hashes may collide.
+    #[inline]
+    pub fn key128(self, seq: &[u8]) -> Key128Iterator<ModSamplingIterator<T, S>> {
+        Key128Iterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence,
+    /// with the 1-based number of occurrences of each minimizer so far in the sequence.
+    #[inline]
+    pub fn iter_with_running_count(
+        self,
+        seq: &[u8],
+    ) -> RunningCountIterator<ModSamplingIterator<T, S>, T> {
+        RunningCountIterator::new(self.iter(seq))
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence,
+    /// skipping a minimizer if the same value was yielded less than `distance` bases before, e.g. to thin the anchors in tandem repeats.
+    /// Unlike a global deduplication, occurrences far enough apart are all kept.
+    #[inline]
+    pub fn suppress_within(
+        self,
+        seq: &[u8],
+        distance: usize,
+    ) -> SuppressIterator<ModSamplingIterator<T, S>, T>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        SuppressIterator::new(self.iter(seq), distance)
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence,
+    /// skipping a mod-minimizer if it is less than `distance` bases away from the last yielded one, e.g. to build a sparser index.
+    /// The first mod-minimizer is always yielded.
+    #[inline]
+    pub fn min_distance(
+        self,
+        seq: &[u8],
+        distance: usize,
+    ) -> MinDistanceIterator<ModSamplingIterator<T, S>> {
+        MinDistanceIterator::new(self.iter(seq), distance)
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence whose hash is strictly smaller than
+    /// the hashes of the previous and the next mod-minimizers, giving a sparser set of anchors without changing the width.
+    /// See [`LocalMinimaIterator`] for the handling of the first and last mod-minimizers and of ties.
+    #[inline]
+    pub fn iter_local_minima(
+        self,
+        seq: &[u8],
+    ) -> LocalMinimaIterator<ModSamplingIterator<T, S>, T, S>
+    where
+        S: Clone,
+    {
+        let hasher = self.hasher.clone();
+        LocalMinimaIterator::new(self.iter(seq), hasher)
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence, with the number of G/C bases in each minimizer.
+    /// The codes of C and G are taken from the encoding of the builder.
+    #[inline]
+    pub fn iter_with_gc(self, seq: &[u8]) -> GcCountIterator<ModSamplingIterator<T, S>, T> {
+        let minimizer_size = self.minimizer_size;
+        let encoding = self.encoding;
+        GcCountIterator::new(self.iter(seq), minimizer_size, &encoding)
+    }
+
+    /// Builds an iterator over the mod-minimizers and their positions in the given sequence, with a hash of each minimizer computed by `secondary_hasher`.
+    /// The secondary hash does not affect the selection, which makes it useful to detect collisions of the primary hasher.
+    #[inline]
+    pub fn iter_with_secondary_hasher<H: BuildHasher>(
+        self,
+        seq: &[u8],
+        secondary_hasher: H,
+    ) -> SecondaryHashIterator<ModSamplingIterator<T, S>, H> {
+        SecondaryHashIterator::new(self.iter(seq), secondary_hasher)
+    }
+
+    /// Builds an iterator over the mod-minimizers in the given sequence and their positions, keeping only the mod-minimizers in `set`.
+    #[inline]
+    pub fn retain_values(
+        self,
+        seq: &[u8],
+        set: Arc<HashSet<T>>,
+    ) -> RetainIterator<ModSamplingIterator<T, S>, T> {
+        RetainIterator::new(self.iter(seq), set)
+    }
+
+    /// Builds an index over the mod-minimizers and their positions in the given sequence.
+    #[inline]
+    pub fn index(self, seq: &[u8]) -> MinimizerIndex<T>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).collect()
+    }
+
+    /// Calls `f` on the mod-minimizers and their positions in the given sequence until it returns [`ControlFlow::Break`],
+    /// in which case the rest of the sequence is not processed.
+    #[inline]
+    pub fn for_each_until<F: FnMut((T, usize)) -> ControlFlow<()>>(
+        self,
+        seq: &[u8],
+        f: F,
+    ) -> ControlFlow<()>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).try_for_each(f)
+    }
+
+    /// Feeds the mod-minimizers and their positions in the given sequence to `sink`, without collecting them.
+    /// Unlike [`iter`](Self::iter), the rolling loop pushes each mod-minimizer to the sink as soon as it is selected.
+    #[inline]
+    pub fn into_counter<K: MinimizerSink<T>>(self, seq: &[u8], sink: &mut K)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).feed(sink);
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, ModMinimizer, S, true> {
+    /// Builds an iterator over the canonical mod-minimizers and their positions in the given sequence with their [`Strand`].
+    /// It requires an odd width to break ties between multiple minimizers.
+    #[inline]
+    pub fn iter(self, seq: &[u8]) -> CanonicalModSamplingIterator<T, S> {
+        self.assert_ranks(self._t());
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalModSamplingIterator::new(
             seq,
             self.minimizer_size,
             self.width,
+            self._t(),
             self.hasher,
             self.encoding,
+            self.complement,
         )
     }
 
-    /// Builds an iterator over the positions of the minimizers in the given sequence.
+    /// Builds an iterator over the positions of the canonical mod-minimizers in the given sequence with their [`Strand`].
+    /// It requires an odd width to break ties between multiple minimizers.
     #[inline]
-    pub fn iter_pos(self, seq: &[u8]) -> MinimizerPosIterator<T, S> {
-        MinimizerPosIterator::new(
+    pub fn iter_pos(self, seq: &[u8]) -> CanonicalModSamplingPosIterator<T, S> {
+        self.assert_ranks(self._t());
+        assert_eq!(
+            self.width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        CanonicalModSamplingPosIterator::new(
             seq,
             self.minimizer_size,
             self.width,
+            self._t(),
             self.hasher,
             self.encoding,
+            self.complement,
         )
     }
-}
 
-impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, Minimizer, S, true> {
-    /// Builds an iterator over the canonical minimizers and their positions in the given sequence with a boolean indicating a reverse complement.
+    /// Builds an iterator over the canonical mod-minimizers and their positions in the given sequence with their [`Strand`],
+    /// selected with the given empty queue over the t-mers.
+    /// The hasher of the builder is ignored, and the width of the queue must be `width + minimizer_size - t`.
     /// It requires an odd width to break ties between multiple minimizers.
     #[inline]
-    pub fn iter(self, seq: &[u8]) -> CanonicalMinimizerIterator<T, S> {
-        assert_eq!(
-            self.width % 2,
-            1,
-            "width must be odd to break ties between multiple minimizers"
-        );
-        CanonicalMinimizerIterator::new(
+    pub fn iter_with_queue<Q: MinPosWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> CanonicalModSamplingIterator<T, S, Q> {
+        CanonicalModSamplingIterator::with_queue(
             seq,
             self.minimizer_size,
             self.width,
-            self.hasher,
+            self._t(),
+            queue,
             self.encoding,
+            self.complement,
         )
     }
 
-    /// Builds an iterator over the positions of the canonical minimizers in the given sequence with a boolean indicating a reverse complement.
+    /// Builds an iterator over the positions of the canonical mod-minimizers in the given sequence with their [`Strand`],
+    /// selected with the given empty queue over the t-mers.
+    /// The hasher of the builder is ignored, and the width of the queue must be `width + minimizer_size - t`.
     /// It requires an odd width to break ties between multiple minimizers.
     #[inline]
-    pub fn iter_pos(self, seq: &[u8]) -> CanonicalMinimizerPosIterator<T, S> {
-        assert_eq!(
-            self.width % 2,
-            1,
-            "width must be odd to break ties between multiple minimizers"
-        );
-        CanonicalMinimizerPosIterator::new(
+    pub fn iter_pos_with_queue<Q: MinPosWindow<T>>(
+        self,
+        seq: &[u8],
+        queue: Q,
+    ) -> CanonicalModSamplingPosIterator<T, S, Q> {
+        CanonicalModSamplingPosIterator::with_queue(
             seq,
             self.minimizer_size,
             self.width,
-            self.hasher,
+            self._t(),
+            queue,
             self.encoding,
+            self.complement,
         )
     }
+
+    /// Returns the canonical mod-minimizer of the first window of the given sequence and its position,
+    /// or `None` if the sequence is shorter than a window.
+    #[inline]
+    pub fn first(self, seq: &[u8]) -> Option<(T, usize, Strand)>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).next()
+    }
+
+    /// Returns the number of canonical mod-minimizers in the given sequence, without building the minimizers themselves.
+    #[inline]
+    pub fn count(self, seq: &[u8]) -> usize
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter_pos(seq).count()
+    }
+
+    /// Returns the observed density of the canonical mod-minimizers in the given sequence, i.e. the fraction of m-mers that are selected.
+    #[inline]
+    pub fn density(self, seq: &[u8]) -> f64
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let num_mmers = (seq.len() + 1).saturating_sub(self.minimizer_size);
+        if num_mmers == 0 {
+            return 0.0;
+        }
+        self.count(seq) as f64 / num_mmers as f64
+    }
+
+    /// Builds an iterator over the canonical mod-minimizers in the given sequence as slices borrowed from the sequence, with its position and its [`Strand`].
+    /// Each slice is `&seq[pos..(pos + minimizer_size)]`, so no decoding is needed.
+    #[inline]
+    pub fn iter_kmers(
+        self,
+        seq: &[u8],
+    ) -> CanonicalKmerIterator<CanonicalModSamplingPosIterator<T, S>> {
+        CanonicalKmerIterator::new(seq, self.minimizer_size, self.iter_pos(seq))
+    }
+
+    /// Calls `f` on the canonical mod-minimizers and their positions with their [`Strand`] in the given sequence until it returns [`ControlFlow::Break`],
+    /// in which case the rest of the sequence is not processed.
+    #[inline]
+    pub fn for_each_until<F: FnMut((T, usize, Strand)) -> ControlFlow<()>>(
+        self,
+        seq: &[u8],
+        f: F,
+    ) -> ControlFlow<()>
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.iter(seq).try_for_each(f)
+    }
 }
 
-const R: usize = 4;
+impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher, const CANONICAL: bool>
+    MinimizerBuilder<T, A, S, CANONICAL>
+{
+    /// The default size of the minimizers.
+    pub const DEFAULT_MINIMIZER_SIZE: usize = 21;
+    /// The default width of the window, for k-mers of size 31.
+    pub const DEFAULT_WIDTH: u16 = 31 - Self::DEFAULT_MINIMIZER_SIZE as u16 + 1;
+}
+
+impl<T: PrimInt + Hash, A: MinimizerAlgorithm> MinimizerBuilder<T, A, DefaultHashBuilder> {
+    fn _new() -> Self {
+        let mut encoding = [0u8; 256];
+        encoding[b'A' as usize] = 0b00;
+        encoding[b'a' as usize] = 0b00;
+        encoding[b'C' as usize] = 0b01;
+        encoding[b'c' as usize] = 0b01;
+        encoding[b'G' as usize] = 0b10;
+        encoding[b'g' as usize] = 0b10;
+        encoding[b'T' as usize] = 0b11;
+        encoding[b't' as usize] = 0b11;
+        Self {
+            minimizer_size: Self::DEFAULT_MINIMIZER_SIZE,
+            width: Self::DEFAULT_WIDTH,
+            hasher: DefaultHashBuilder::default(),
+            encoding,
+            complement: COMPLEMENT,
+            rc_encoding: rc_encoding(&encoding, &COMPLEMENT),
+            rc_codes: rc_codes(&encoding, &COMPLEMENT),
+            t: None,
+            r: R,
+            even_width: false,
+            base_order: BaseOrder::MostSignificantFirst,
+            num_ranks: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the seed of the default hasher.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.hasher = DefaultHashBuilder::with_seed(seed);
+        self
+    }
+}
+
+/// Returns the largest gap between consecutive positions sorted in increasing order,
+/// including the gaps from 0 to the first one and from the last one to the last m-mer, or 0 if there is no position.
+fn max_gap(positions: impl IntoIterator<Item = usize>, num_mmers: usize) -> usize {
+    let mut max_gap = 0;
+    let mut prev = None;
+    for pos in positions {
+        max_gap = max_gap.max(prev.map_or(pos, |prev| pos - prev));
+        prev = Some(pos);
+    }
+    match prev {
+        Some(prev) => max_gap.max(num_mmers - 1 - prev),
+        None => 0,
+    }
+}
+
+/// The minimizers selected by the builders of each algorithm, canonical or not,
+/// over which the helpers comparing the minimizers of sequences are written once.
+/// It is public to bound these helpers, but not exported since it is only implemented by the builders.
+pub trait Selection<T> {
+    /// A minimizer and its position, followed by its [`Strand`] for canonical minimizers.
+    type Item: Copy + Ord;
+
+    /// Returns the value of a minimizer.
+    fn value(item: Self::Item) -> T;
+
+    /// Iterates over the minimizers and their positions in the given sequence, borrowing the builder.
+    fn select<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = Self::Item> + 'a;
+
+    /// Iterates over the positions of the minimizers in the given sequence, borrowing the builder.
+    fn select_pos<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = usize> + 'a;
+
+    /// Returns the expected density of the minimizers, used to reserve the collected minimizers.
+    fn density_hint(&self) -> f64;
+}
+
+impl<T: PrimInt + Hash + 'static, S: BuildHasher> Selection<T>
+    for MinimizerBuilder<T, Minimizer, S, false>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
 
-impl<T: PrimInt + Hash> MinimizerBuilder<T, ModMinimizer> {
-    /// Sets up the `MinimizerBuilder` for mod-minimizers with default values:
-    /// - minimizer_size = 21
-    /// - width = 11 (31 - 21 + 1)
-    /// - hasher = [`DefaultHashBuilder`]
-    /// - encoding: A = `00`, C = `01`, G = `10`, T = `11`
     #[inline]
-    pub fn new_mod() -> Self {
-        Self::_new()
+    fn value((value, _): Self::Item) -> T {
+        value
+    }
+
+    #[inline]
+    fn select<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = Self::Item> + 'a {
+        self.iter_ref(seq)
+    }
+
+    #[inline]
+    fn select_pos<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.iter_pos_ref(seq)
+    }
+
+    #[inline]
+    fn density_hint(&self) -> f64 {
+        self.expected_density()
     }
 }
 
-impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, ModMinimizer, S, false> {
-    /// Builds an iterator over the mod-minimizers and their positions in the given sequence.
+impl<T: PrimInt + Hash + 'static, S: BuildHasher> Selection<T>
+    for MinimizerBuilder<T, Minimizer, S, true>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, Strand);
+
     #[inline]
-    pub fn iter(self, seq: &[u8]) -> ModSamplingIterator<T, S> {
-        assert!(
-            self.minimizer_size >= R,
-            "mod-minimizers require minimizer_size ≥ r={R}"
-        );
+    fn value((value, _, _): Self::Item) -> T {
+        value
+    }
+
+    #[inline]
+    fn select<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = Self::Item> + 'a {
+        self.iter_ref(seq)
+    }
+
+    #[inline]
+    fn select_pos<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.iter_pos_ref(seq).map(|(pos, _)| pos)
+    }
+
+    #[inline]
+    fn density_hint(&self) -> f64 {
+        self.expected_density()
+    }
+}
+
+impl<T: PrimInt + Hash + 'static, S: BuildHasher> Selection<T>
+    for MinimizerBuilder<T, ModMinimizer, S, false>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    #[inline]
+    fn value((value, _): Self::Item) -> T {
+        value
+    }
+
+    #[inline]
+    fn select<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = Self::Item> + 'a {
+        self.assert_ranks(self._t());
         ModSamplingIterator::new(
             seq,
             self.minimizer_size,
             self.width,
-            R + ((self.minimizer_size - R) % self.width as usize),
-            self.hasher,
+            self._t(),
+            BuildHasherRef(&self.hasher),
             self.encoding,
         )
     }
 
-    /// Builds an iterator over the positions of the mod-minimizers in the given sequence.
     #[inline]
-    pub fn iter_pos(self, seq: &[u8]) -> ModSamplingPosIterator<T, S> {
-        assert!(
-            self.minimizer_size >= R,
-            "mod-minimizers require minimizer_size ≥ r={R}"
-        );
+    fn select_pos<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.assert_ranks(self._t());
         ModSamplingPosIterator::new(
             seq,
             self.minimizer_size,
             self.width,
-            R + ((self.minimizer_size - R) % self.width as usize),
-            self.hasher,
+            self._t(),
+            BuildHasherRef(&self.hasher),
             self.encoding,
         )
     }
+
+    #[inline]
+    fn density_hint(&self) -> f64 {
+        self.expected_density()
+    }
 }
 
-impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, ModMinimizer, S, true> {
-    /// Builds an iterator over the canonical mod-minimizers and their positions in the given sequence with a boolean indicating a reverse complement.
-    /// It requires an odd width to break ties between multiple minimizers.
+impl<T: PrimInt + Hash + 'static, S: BuildHasher> Selection<T>
+    for MinimizerBuilder<T, ModMinimizer, S, true>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, Strand);
+
     #[inline]
-    pub fn iter(self, seq: &[u8]) -> CanonicalModSamplingIterator<T, S> {
-        assert!(
-            self.minimizer_size >= R,
-            "mod-minimizers require minimizer_size ≥ r={R}"
-        );
+    fn value((value, _, _): Self::Item) -> T {
+        value
+    }
+
+    #[inline]
+    fn select<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = Self::Item> + 'a {
+        self.assert_ranks(self._t());
         assert_eq!(
             self.width % 2,
             1,
@@ -189,20 +2298,16 @@ impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, ModMinimizer, S, tru
             seq,
             self.minimizer_size,
             self.width,
-            R + ((self.minimizer_size - R) % self.width as usize),
-            self.hasher,
+            self._t(),
+            BuildHasherRef(&self.hasher),
             self.encoding,
+            self.complement,
         )
     }
 
-    /// Builds an iterator over the positions of the canonical mod-minimizers in the given sequence with a boolean indicating a reverse complement.
-    /// It requires an odd width to break ties between multiple minimizers.
     #[inline]
-    pub fn iter_pos(self, seq: &[u8]) -> CanonicalModSamplingPosIterator<T, S> {
-        assert!(
-            self.minimizer_size >= R,
-            "mod-minimizers require minimizer_size ≥ r={R}"
-        );
+    fn select_pos<'a>(&'a self, seq: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.assert_ranks(self._t());
         assert_eq!(
             self.width % 2,
             1,
@@ -212,46 +2317,249 @@ impl<T: PrimInt + Hash, S: BuildHasher> MinimizerBuilder<T, ModMinimizer, S, tru
             seq,
             self.minimizer_size,
             self.width,
-            R + ((self.minimizer_size - R) % self.width as usize),
-            self.hasher,
+            self._t(),
+            BuildHasherRef(&self.hasher),
             self.encoding,
+            self.complement,
         )
+        .map(|(pos, _)| pos)
+    }
+
+    #[inline]
+    fn density_hint(&self) -> f64 {
+        self.expected_density()
     }
 }
 
-impl<T: PrimInt + Hash, A: MinimizerAlgorithm> MinimizerBuilder<T, A, DefaultHashBuilder> {
-    fn _new() -> Self {
-        let mut encoding = [0u8; 256];
-        encoding[b'A' as usize] = 0b00;
-        encoding[b'a' as usize] = 0b00;
-        encoding[b'C' as usize] = 0b01;
-        encoding[b'c' as usize] = 0b01;
-        encoding[b'G' as usize] = 0b10;
-        encoding[b'g' as usize] = 0b10;
-        encoding[b'T' as usize] = 0b11;
-        encoding[b't' as usize] = 0b11;
-        Self {
-            minimizer_size: 21,
-            width: 31 - 21 + 1,
-            hasher: DefaultHashBuilder::default(),
-            encoding,
-            _marker: PhantomData,
+impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher, const CANONICAL: bool>
+    MinimizerBuilder<T, A, S, CANONICAL>
+where
+    Self: Selection<T>,
+{
+    /// Returns the total number of minimizers in the given sequences, without building the minimizers themselves.
+    /// Sequences shorter than a window are skipped, and the builder is borrowed by the iterator of each sequence instead of being cloned.
+    pub fn total_minimizers<'a, I: IntoIterator<Item = &'a [u8]>>(self, seqs: I) -> usize {
+        let base_width = self.kmer_size();
+        seqs.into_iter()
+            .filter(|seq| seq.len() >= base_width)
+            .map(|seq| self.select_pos(seq).count())
+            .sum()
+    }
+
+    /// Returns a bitset of length `seq.len()` marking the positions of the minimizers in the given sequence,
+    /// packed in bytes with position `i` stored in the bit `i % 8` of the byte `i / 8`.
+    pub fn position_mask(self, seq: &[u8]) -> Vec<u8> {
+        let mut mask = vec![0u8; seq.len().div_ceil(8)];
+        for pos in self.select_pos(seq) {
+            mask[pos / 8] |= 1 << (pos % 8);
         }
+        mask
     }
 
-    /// Sets the seed of the default hasher.
-    pub fn seed(mut self, seed: u64) -> Self {
-        self.hasher = DefaultHashBuilder::with_seed(seed);
-        self
+    /// Returns the largest gap between the positions of consecutive minimizers in the given sequence,
+    /// including the gaps from the start of the sequence to the first one and from the last one to the last m-mer.
+    /// It is at most `width` for any sequence, or 0 if the sequence is shorter than a window.
+    pub fn max_gap(self, seq: &[u8]) -> usize {
+        let num_mmers = (seq.len() + 1).saturating_sub(self.minimizer_size);
+        if CANONICAL {
+            // breaking ties may select a position before the previous one, so the positions are sorted first
+            let mut positions: Vec<usize> = self.select_pos(seq).collect();
+            positions.sort_unstable();
+            positions.dedup();
+            max_gap(positions, num_mmers)
+        } else {
+            max_gap(self.select_pos(seq), num_mmers)
+        }
+    }
+
+    /// Returns the distinct minimizers shared by the two given sequences, in their order of appearance in `seq_b`.
+    /// Canonical minimizers are compared by canonical value, so that the result does not depend on the strand of the sequences.
+    pub fn shared_minimizers(self, seq_a: &[u8], seq_b: &[u8]) -> Vec<T> {
+        let mut mins_a: HashSet<T> = self.select(seq_a).map(Self::value).collect();
+        self.select(seq_b)
+            .map(Self::value)
+            .filter(|min| mins_a.remove(min))
+            .collect()
+    }
+
+    /// Returns the Jaccard similarity `|A ∩ B| / |A ∪ B|` between the sets of distinct minimizers of the two given sequences,
+    /// or `0.0` if neither sequence has a minimizer.
+    /// Canonical minimizers are compared by canonical value, so that the result does not depend on the strand of the sequences.
+    ///
+    /// This estimates the Jaccard similarity of the m-mer sets rather than the k-mer sets:
+    /// it is not corrected for the density of the scheme, and a single mutation changes several minimizers.
+    pub fn jaccard(self, seq_a: &[u8], seq_b: &[u8]) -> f64 {
+        let mins_a: HashSet<T> = self.select(seq_a).map(Self::value).collect();
+        let mins_b: HashSet<T> = self.select(seq_b).map(Self::value).collect();
+        let intersection = mins_a.intersection(&mins_b).count();
+        let union = mins_a.len() + mins_b.len() - intersection;
+        if union == 0 {
+            return 0.0;
+        }
+        intersection as f64 / union as f64
+    }
+
+    /// Returns the anchors built from each pair of equal minimizers of `seq_a` and `seq_b`, sorted by their position in `seq_a` then in `seq_b`.
+    fn _anchors<X>(
+        &self,
+        seq_a: &[u8],
+        seq_b: &[u8],
+        anchor: impl Fn(<Self as Selection<T>>::Item, <Self as Selection<T>>::Item) -> X,
+    ) -> Vec<X> {
+        let mut mins_b: HashMap<T, Vec<_>> = HashMap::new();
+        for min in self.select(seq_b) {
+            mins_b.entry(Self::value(min)).or_default().push(min);
+        }
+        let mut anchors = Vec::new();
+        for min_a in self.select(seq_a) {
+            if let Some(mins) = mins_b.get(&Self::value(min_a)) {
+                anchors.extend(mins.iter().map(|&min_b| anchor(min_a, min_b)));
+            }
+        }
+        anchors
+    }
+
+    /// Collects the minimizers of the given sequence and their positions sorted by value, ties being sorted by position.
+    fn _sorted_by_value(&self, seq: &[u8]) -> Vec<<Self as Selection<T>>::Item> {
+        let num_mmers = (seq.len() + 1).saturating_sub(self.minimizer_size);
+        let mut mins = Vec::with_capacity((num_mmers as f64 * self.density_hint()) as usize);
+        mins.extend(self.select(seq));
+        // positions are distinct, so an unstable sort keeps ties in order of position
+        mins.sort_unstable();
+        mins
+    }
+}
+
+impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher> MinimizerBuilder<T, A, S, false>
+where
+    Self: Selection<T, Item = (T, usize)>,
+{
+    /// Returns the anchors between the two given sequences, i.e. the pairs of positions `(pos_a, pos_b)` of equal minimizers,
+    /// sorted by `pos_a` then `pos_b`, ready to be chained.
+    /// A value occurring several times in both sequences yields every pair of positions.
+    pub fn anchors(self, seq_a: &[u8], seq_b: &[u8]) -> Vec<(usize, usize)> {
+        self._anchors(seq_a, seq_b, |(_, pos_a), (_, pos_b)| (pos_a, pos_b))
+    }
+
+    /// Returns the minimizers of the given sequence and their positions sorted by value, ties being sorted by position.
+    #[inline]
+    pub fn sorted_by_value(self, seq: &[u8]) -> Vec<(T, usize)> {
+        self._sorted_by_value(seq)
+    }
+}
+
+impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher> MinimizerBuilder<T, A, S, true>
+where
+    Self: Selection<T, Item = (T, usize, Strand)>,
+{
+    /// Returns the anchors between the two given sequences, i.e. the pairs of positions `(pos_a, pos_b)` of equal canonical minimizers,
+    /// with a boolean indicating whether both occurrences are on the same strand, sorted by `pos_a` then `pos_b`.
+    /// A value occurring several times in both sequences yields every pair of positions.
+    pub fn anchors(self, seq_a: &[u8], seq_b: &[u8]) -> Vec<(usize, usize, bool)> {
+        self._anchors(
+            seq_a,
+            seq_b,
+            |(_, pos_a, strand_a), (_, pos_b, strand_b)| (pos_a, pos_b, strand_a == strand_b),
+        )
+    }
+
+    /// Returns the canonical minimizers of the given sequence and their positions sorted by value, ties being sorted by position.
+    /// Minimizers are sorted by canonical value, so the result does not depend on the strand of the sequence.
+    #[inline]
+    pub fn sorted_by_value(self, seq: &[u8]) -> Vec<(T, usize, Strand)> {
+        self._sorted_by_value(seq)
     }
 }
 
 impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher, const CANONICAL: bool>
     MinimizerBuilder<T, A, S, CANONICAL>
 {
+    /// Collects the problems shared by every algorithm.
+    fn _errors(&self) -> Vec<BuildError> {
+        let mut errors = Vec::new();
+        let max_size = (T::zero().count_zeros() / A::BITS_PER_BASE) as usize;
+        if self.minimizer_size > max_size {
+            errors.push(BuildError::MinimizerSizeTooLarge {
+                minimizer_size: self.minimizer_size,
+                max_size,
+            });
+        }
+        if CANONICAL && self.width % 2 != 1 && !self.even_width {
+            errors.push(BuildError::EvenWidth { width: self.width });
+        }
+        errors
+    }
+
+    /// Returns an error if ranks were given to [`uhs_order`](Self::uhs_order) without one for each packed m-mer of `size` bases.
+    fn _rank_error(&self, size: usize) -> Option<BuildError> {
+        let ranks = self.num_ranks?;
+        (1usize.checked_shl(2 * size as u32) != Some(ranks))
+            .then_some(BuildError::RankCount { ranks, size })
+    }
+
+    /// Panics when building an iterator if ranks were given to [`uhs_order`](Self::uhs_order) without one for each packed m-mer of `size` bases,
+    /// instead of panicking when hashing an m-mer without a rank.
+    #[inline]
+    fn assert_ranks(&self, size: usize) {
+        if let Some(error) = self._rank_error(size) {
+            panic!("{error}");
+        }
+    }
+
+    /// Estimates the number of distinct m-mers selected in a random sequence of length `seq_len` by mod-sampling the smallest t-mers,
+    /// where `t = minimizer_size` gives random minimizers.
+    ///
+    /// Each window contains `W = width + minimizer_size - t` t-mers, and the m-mer at position `p` is selected
+    /// whenever the smallest t-mer of a window containing it lies at a position `p + j * width`.
+    /// The `c = minimizer_size - t + 1` t-mers of the m-mer belong to all these windows and have the same hashes at each of its occurrences,
+    /// so the m-mer is only selected through the smallest of them, with quantile `q`, or through a smaller t-mer following it.
+    /// A t-mer of quantile `q` lying within the m-mer selects it in at least one of its `n` windows
+    /// with probability `(1 - q)^(width - 1) * (1 + (n - 1) * q)`, as the other t-mers of the m-mer are larger.
+    /// Each m-mer is assumed to occur a Poisson number of times, so that it is selected at least once with probability `1 - exp(-occurrences * p)`,
+    /// which is averaged over the quantile `q` and the position of the smallest t-mer of the m-mer.
+    fn _expected_distinct(&self, seq_len: usize, t: usize) -> f64 {
+        const STEPS: usize = 1 << 12;
+        if seq_len < self.kmer_size() {
+            return 0.0;
+        }
+        let mut space = 4f64.powi(self.minimizer_size as i32);
+        if CANONICAL {
+            space /= 2.0;
+        }
+        let occurrences = (seq_len + 1 - self.minimizer_size) as f64 / space;
+        let w = self.width as usize;
+        let width_t = w + self.minimizer_size - t;
+        let inner_len = self.minimizer_size - t;
+        let c = (inner_len + 1) as f64;
+        let first_outer = (inner_len / w + 1) * w;
+        let num_inner = (inner_len / w + 1) as f64;
+        let mut total = 0.0;
+        for i in 0..STEPS {
+            let q = (i as f64 + 0.5) / STEPS as f64;
+            let x = 1.0 - q;
+            // probability of selecting the m-mer through a t-mer following it, smaller than the t-mers of the m-mer
+            let mut outer = 0.0;
+            for offset in (first_outer..width_t).step_by(w) {
+                let n = min(w, width_t - offset) as f64;
+                outer += n * (1.0 - x.powi(w as i32 - 1)) / (w - 1) as f64
+                    - (n - 1.0) * (1.0 - x.powi(w as i32)) / w as f64;
+            }
+            // the smallest t-mer of the m-mer is not at a position selecting it
+            let mut selected = -(1.0 - num_inner / c) * (-occurrences * outer).exp_m1();
+            for offset in (0..=inner_len).step_by(w) {
+                let n = min(w, width_t - offset) as f64;
+                let inner = x.powi(w as i32 - 1) * (1.0 + (n - 1.0) * q);
+                selected -= (-occurrences * (inner + outer)).exp_m1() / c;
+            }
+            total += c * x.powi(inner_len as i32) * selected;
+        }
+        space * total / STEPS as f64
+    }
+
     /// Sets the size of the minimizers.
     pub fn minimizer_size(mut self, minimizer_size: usize) -> Self {
-        let max_size = (T::zero().count_zeros() / 2) as usize;
+        let max_size = (T::zero().count_zeros() / A::BITS_PER_BASE) as usize;
+        assert!(minimizer_size > 0, "minimizer_size must be ≥ 1");
         assert!(
             minimizer_size <= max_size,
             "With this integer type, minimizer_size must be ≤ {max_size}. Please select a smaller size or a larger type."
@@ -260,19 +2568,122 @@ impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher, const CANONICAL:
         self
     }
 
+    /// Sets the size of the minimizers to the largest size fitting in the integer type, e.g. 32 for `u64`.
+    /// Like [`minimizer_size`](Self::minimizer_size), it must be called before [`k`](Self::k).
+    pub fn max_minimizer_size(self) -> Self {
+        let max_size = (T::zero().count_zeros() / A::BITS_PER_BASE) as usize;
+        self.minimizer_size(max_size)
+    }
+
     /// Sets the width of the window.
     pub const fn width(mut self, width: u16) -> Self {
+        assert!(width > 0, "width must be ≥ 1");
         self.width = width;
         self
     }
 
+    /// Sets the width of the window from the size `k` of the k-mers covered by a window, i.e. `width = k - minimizer_size + 1`.
+    /// It must be called after [`minimizer_size`](Self::minimizer_size).
+    pub fn k(self, k: usize) -> Self {
+        assert!(
+            k >= self.minimizer_size,
+            "k must be ≥ minimizer_size = {}, got {k}",
+            self.minimizer_size
+        );
+        let width = k - self.minimizer_size + 1;
+        assert!(
+            width <= u16::MAX as usize,
+            "k - minimizer_size + 1 must be ≤ {}, got {width}",
+            u16::MAX
+        );
+        self.width(width as u16)
+    }
+
+    /// Sets the size `m` of the minimizers and the width of the window from the size `k` of the k-mers covered by a window,
+    /// i.e. `minimizer_size = m` and `width = k - m + 1`.
+    pub fn from_k_and_m(self, k: usize, m: usize) -> Self {
+        self.minimizer_size(m).k(k)
+    }
+
+    /// Returns the size of the k-mers covered by a window, i.e. `width + minimizer_size - 1`.
+    #[inline]
+    pub const fn kmer_size(&self) -> usize {
+        self.width as usize + self.minimizer_size - 1
+    }
+
+    /// Sets the hasher used to compute minimizers to a [`StableHasher`](crate::hash::StableHasher) with seed 0,
+    /// so that the selected minimizers are identical across platforms and versions of the crate.
+    /// Use [`hasher`](Self::hasher) with [`BuildStableHasher::with_seed`] to select another seed.
+    pub fn stable_hasher(self) -> MinimizerBuilder<T, A, BuildStableHasher, CANONICAL> {
+        self.hasher(BuildStableHasher::default())
+    }
+
+    /// Orders the m-mers with the given precomputed ranks instead of a hash, e.g. to plug in a universal hitting set (UHS) order
+    /// such as those computed by DOCKS or PASHA, where the m-mers of the UHS have the lowest ranks.
+    ///
+    /// The ranks are indexed by the packed m-mers, so there must be `4^minimizer_size` of them, or `4^t` for mod-minimizers which order t-mers.
+    /// Classic minimizers thus need about 4.4 × 10^12 ranks for `minimizer_size = 21`, so only mod-minimizers with a small `t` are practical.
+    /// `validate` reports a wrong number of ranks, and building the main iterators panics with it instead of panicking while hashing an m-mer without a rank.
+    /// Canonical minimizers only look up the ranks of canonical m-mers.
+    /// Ties between equal ranks are broken as ties between equal hashes.
+    /// It panics if the number of ranks is not a power of 4.
+    pub fn uhs_order(
+        self,
+        ranks: &[u32],
+    ) -> MinimizerBuilder<T, A, BuildRankHasher<'_>, CANONICAL> {
+        assert!(
+            ranks.len().is_power_of_two() && ranks.len().trailing_zeros().is_multiple_of(2),
+            "the number of ranks must be a power of 4, got {}",
+            ranks.len()
+        );
+        let mut builder = self.hasher(BuildRankHasher(ranks));
+        builder.num_ranks = Some(ranks.len());
+        builder
+    }
+
+    /// Selects the maximizer of each window, i.e. the m-mer with the largest hash, instead of the minimizer.
+    /// This wraps the current hasher in a [`BuildMaxHasher`] negating its hashes, so it composes with canonical minimizers and mod-minimizers.
+    /// Algorithms that do not rely on a hasher, such as [`lexicographic`](Self::lexicographic), are not affected.
+    pub fn select_max(self) -> MinimizerBuilder<T, A, BuildMaxHasher<S>, CANONICAL> {
+        MinimizerBuilder::<T, A, BuildMaxHasher<S>, CANONICAL> {
+            minimizer_size: self.minimizer_size,
+            width: self.width,
+            hasher: BuildMaxHasher(self.hasher),
+            encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
+            _marker: self._marker,
+        }
+    }
+
     /// Sets the hasher used to compute minimizers.
+    ///
+    /// The hasher must take every bit of the minimizers into account.
+    /// A no-op hasher such as `BuildNoHashHasher::<u64>` only supports minimizers of at most 64 bits: with a wider type such as `u128`,
+    /// it panics when hashing the first m-mer.
+    /// A custom hasher keeping only the 64 lowest bits would instead silently select wrong minimizers for `minimizer_size > 32`,
+    /// which is checked by a `debug_assert!` when building an iterator.
+    /// For lexicographic minimizers, prefer [`lexicographic`](Self::lexicographic) which does not rely on a hasher.
     pub fn hasher<H: BuildHasher>(self, hasher: H) -> MinimizerBuilder<T, A, H, CANONICAL> {
         MinimizerBuilder::<T, A, H, CANONICAL> {
             minimizer_size: self.minimizer_size,
             width: self.width,
             hasher,
             encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: None,
             _marker: self._marker,
         }
     }
@@ -287,16 +2698,53 @@ impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher, const CANONICAL:
         self.encoding[b'g' as usize] = g;
         self.encoding[b'T' as usize] = t;
         self.encoding[b't' as usize] = t;
+        self.rc_encoding = rc_encoding(&self.encoding, &self.complement);
+        self.rc_codes = rc_codes(&self.encoding, &self.complement);
         self
     }
 
+    /// Sets the complement of each byte, used to compute reverse complements for canonical minimizers.
+    /// It only needs to be changed when the sequences use an alphabet beyond the IUPAC codes.
+    pub fn complement(mut self, complement: [u8; 256]) -> Self {
+        self.complement = complement;
+        self.rc_encoding = rc_encoding(&self.encoding, &self.complement);
+        self.rc_codes = rc_codes(&self.encoding, &self.complement);
+        self
+    }
+
+    /// Computes the canonical form of a packed m-mer with a boolean indicating whether it is the reverse complement,
+    /// using the minimizer size, the encoding and the complement table of the builder.
+    #[inline]
+    pub fn canonical_kmer(&self, mmer: T) -> (T, bool)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let rc_mmer = reverse_complement_kmer(mmer, self.minimizer_size, &self.rc_codes);
+        let canonical_mmer = mmer.min(rc_mmer);
+        (canonical_mmer, canonical_mmer == rc_mmer)
+    }
+
     /// Compute canonical minimizers.
+    /// It panics if the bases are not packed in the default order [`BaseOrder::MostSignificantFirst`].
     pub fn canonical(self) -> MinimizerBuilder<T, A, S, true> {
+        assert!(
+            self.base_order == BaseOrder::MostSignificantFirst,
+            "canonical minimizers only support BaseOrder::MostSignificantFirst"
+        );
         MinimizerBuilder::<T, A, S, true> {
             minimizer_size: self.minimizer_size,
             width: self.width,
             hasher: self.hasher,
             encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
             _marker: self._marker,
         }
     }
@@ -308,6 +2756,14 @@ impl<T: PrimInt + Hash, A: MinimizerAlgorithm, S: BuildHasher, const CANONICAL:
             width: self.width,
             hasher: self.hasher,
             encoding: self.encoding,
+            complement: self.complement,
+            rc_encoding: self.rc_encoding,
+            rc_codes: self.rc_codes,
+            t: self.t,
+            r: self.r,
+            even_width: self.even_width,
+            base_order: self.base_order,
+            num_ranks: self.num_ranks,
             _marker: self._marker,
         }
     }
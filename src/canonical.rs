@@ -0,0 +1,125 @@
+use crate::error::KeyError;
+use num_traits::PrimInt;
+
+/// Number of bits of the value in a key built by [`pack_key`], enough for minimizers of size ≤ 20.
+pub const KEY_VALUE_BITS: u32 = 40;
+/// Number of bits of the position in a key built by [`pack_key`].
+pub const KEY_POS_BITS: u32 = 23;
+
+/// Packs a minimizer, its position and its strand into a single `u64` key,
+/// e.g. to pass it across an FFI boundary.
+///
+/// The value takes the 40 most significant bits, the position the next 23 bits, and the strand the least significant bit:
+/// `key = value << 24 | pos << 1 | is_rc`, where `is_rc` is 1 for [`Strand::Reverse`].
+/// It fails if the value is negative or does not fit in 40 bits, i.e. for minimizers of size > 20, or if the position does not fit in 23 bits.
+pub fn pack_key<T: PrimInt>(value: T, pos: usize, strand: Strand) -> Result<u64, KeyError> {
+    if value < T::zero() {
+        return Err(KeyError::NegativeValue);
+    }
+    let value = value
+        .to_u64()
+        .filter(|&value| value >> KEY_VALUE_BITS == 0)
+        .ok_or(KeyError::ValueTooLarge)?;
+    if pos >> KEY_POS_BITS != 0 {
+        return Err(KeyError::PositionTooLarge { pos });
+    }
+    Ok(value << (KEY_POS_BITS + 1) | (pos as u64) << 1 | strand.is_rc() as u64)
+}
+
+/// Unpacks a key built by [`pack_key`] into the minimizer, its position and its strand.
+pub fn unpack_key(key: u64) -> (u64, usize, Strand) {
+    let value = key >> (KEY_POS_BITS + 1);
+    let pos = ((key >> 1) & ((1 << KEY_POS_BITS) - 1)) as usize;
+    (value, pos, Strand::from(key & 1 == 1))
+}
+
+/// A canonical minimizer with its position and its strand.
+///
+/// The canonical iterators yield tuples `(value, pos, strand)`, which can be converted into this struct with [`From`]
+/// to access them by name, e.g. with `.map(CanonicalMinimizer::from)`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CanonicalMinimizer<T> {
+    /// The canonical minimizer.
+    pub value: T,
+    /// The position of the first base of the minimizer.
+    pub pos: usize,
+    /// The strand of the canonical minimizer, i.e. [`Strand::Reverse`] if it is the reverse complement of the forward m-mer.
+    pub strand: Strand,
+}
+
+impl<T> CanonicalMinimizer<T> {
+    /// Returns the minimizer as a tuple `(value, pos, strand)`, as yielded by the canonical iterators.
+    #[inline]
+    pub fn into_tuple(self) -> (T, usize, Strand) {
+        (self.value, self.pos, self.strand)
+    }
+}
+
+impl<T: PrimInt> CanonicalMinimizer<T> {
+    /// Packs the minimizer into a single `u64` key, see [`pack_key`] for the layout.
+    #[inline]
+    pub fn pack_key(&self) -> Result<u64, KeyError> {
+        pack_key(self.value, self.pos, self.strand)
+    }
+}
+
+impl CanonicalMinimizer<u64> {
+    /// Unpacks a key built by [`pack_key`].
+    #[inline]
+    pub fn from_key(key: u64) -> Self {
+        unpack_key(key).into()
+    }
+}
+
+impl<T> From<(T, usize, Strand)> for CanonicalMinimizer<T> {
+    #[inline]
+    fn from((value, pos, strand): (T, usize, Strand)) -> Self {
+        Self { value, pos, strand }
+    }
+}
+
+impl<T> From<CanonicalMinimizer<T>> for (T, usize, Strand) {
+    #[inline]
+    fn from(min: CanonicalMinimizer<T>) -> Self {
+        min.into_tuple()
+    }
+}
+
+/// A strand of a sequence.
+///
+/// The canonical iterators indicate the strand of each minimizer, i.e. [`Strand::Reverse`] if the canonical minimizer
+/// is the reverse complement of the forward m-mer. It converts from and into a boolean `is_rc` with [`From`],
+/// `true` being [`Strand::Reverse`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Strand {
+    /// The strand of the sequence as given.
+    Forward,
+    /// The reverse complement of the sequence.
+    Reverse,
+}
+
+impl Strand {
+    /// Returns whether this is the reverse complement strand.
+    #[inline]
+    pub const fn is_rc(self) -> bool {
+        matches!(self, Self::Reverse)
+    }
+}
+
+impl From<bool> for Strand {
+    #[inline]
+    fn from(is_rc: bool) -> Self {
+        if is_rc {
+            Self::Reverse
+        } else {
+            Self::Forward
+        }
+    }
+}
+
+impl From<Strand> for bool {
+    #[inline]
+    fn from(strand: Strand) -> Self {
+        strand.is_rc()
+    }
+}
@@ -0,0 +1,37 @@
+//! Encodings of the bases of a sequence into 2-bit codes.
+
+/// A mapping from the bytes of a sequence to the codes of their bases.
+///
+/// It is implemented by lookup tables of 256 codes, owned or borrowed, and by [`AsciiEncoding`] which computes the codes without a table.
+pub trait BaseEncoding {
+    /// Returns the code of the given byte.
+    fn code(&self, base: u8) -> u8;
+}
+
+impl BaseEncoding for [u8; 256] {
+    #[inline]
+    fn code(&self, base: u8) -> u8 {
+        self[base as usize]
+    }
+}
+
+impl BaseEncoding for &[u8; 256] {
+    #[inline]
+    fn code(&self, base: u8) -> u8 {
+        self[base as usize]
+    }
+}
+
+/// Encodes each ASCII base as `(b >> 1) & 3` instead of looking it up in a table.
+///
+/// This gives A = `00`, C = `01`, G = `11` and T = `10` in both cases.
+/// It is only meaningful for sequences made of A, C, G and T: any other byte is silently mapped to one of these codes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AsciiEncoding {}
+
+impl BaseEncoding for AsciiEncoding {
+    #[inline]
+    fn code(&self, base: u8) -> u8 {
+        (base >> 1) & 0b11
+    }
+}
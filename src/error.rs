@@ -0,0 +1,121 @@
+use core::fmt;
+
+/// A problem with the parameters of a [`MinimizerBuilder`](crate::MinimizerBuilder).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BuildError {
+    /// The minimizers do not fit in the integer type.
+    MinimizerSizeTooLarge {
+        minimizer_size: usize,
+        max_size: usize,
+    },
+    /// Mod-minimizers require `minimizer_size ≥ r`.
+    MinimizerSizeTooSmall { minimizer_size: usize, r: usize },
+    /// Canonical minimizers require an odd width to break ties between multiple minimizers.
+    EvenWidth { width: u16 },
+    /// Mod-minimizers require `1 ≤ t ≤ minimizer_size`.
+    InvalidT { t: usize, minimizer_size: usize },
+    /// Mod-minimizers require `width + minimizer_size - t`, the number of t-mers in a window, to fit in a `u16`.
+    TooManyTmers {
+        t: usize,
+        minimizer_size: usize,
+        width: u16,
+    },
+    /// The ranks given to `uhs_order` must have `4^size` entries, one for each packed m-mer, or t-mer for mod-minimizers, of `size` bases.
+    RankCount { ranks: usize, size: usize },
+    /// Canonical mod-minimizers require `minimizer_size - t` to be a multiple of the width.
+    NonCanonicalT {
+        t: usize,
+        minimizer_size: usize,
+        width: u16,
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinimizerSizeTooLarge { max_size, .. } => write!(
+                f,
+                "With this integer type, minimizer_size must be ≤ {max_size}. Please select a smaller size or a larger type."
+            ),
+            Self::MinimizerSizeTooSmall { r, .. } => {
+                write!(f, "mod-minimizers require minimizer_size ≥ r={r}")
+            }
+            Self::EvenWidth { .. } => write!(
+                f,
+                "width must be odd to break ties between multiple minimizers"
+            ),
+            Self::InvalidT { t: 0, .. } => write!(f, "t must be ≥ 1"),
+            Self::InvalidT { minimizer_size, .. } => {
+                write!(f, "t must be ≤ minimizer_size={minimizer_size}")
+            }
+            Self::TooManyTmers {
+                t,
+                minimizer_size,
+                width,
+            } => write!(
+                f,
+                "width + minimizer_size - t must be ≤ {}, got {}",
+                u16::MAX,
+                *width as usize + minimizer_size - t
+            ),
+            Self::RankCount { ranks, size } => write!(
+                f,
+                "the ranks must have 4^{size} entries, one for each packed m-mer of {size} bases, got {ranks}"
+            ),
+            Self::NonCanonicalT { .. } => write!(
+                f,
+                "(minimizer_size - t) must be a multiple of the width to preserve canonical minimizers"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// A minimizer that does not fit in a key built by [`pack_key`](crate::pack_key).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyError {
+    /// The value does not fit in 40 bits, i.e. the minimizer size is larger than 20.
+    ValueTooLarge,
+    /// The value is negative, which packed minimizers never are.
+    NegativeValue,
+    /// The position does not fit in 23 bits.
+    PositionTooLarge { pos: usize },
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ValueTooLarge => write!(f, "the value of the minimizer must fit in 40 bits"),
+            Self::NegativeValue => write!(f, "the value of the minimizer must be non-negative"),
+            Self::PositionTooLarge { pos } => {
+                write!(f, "the position must fit in 23 bits, got {pos}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+/// A window with too many degenerate bases for [`expand_degenerate`](crate::MinimizerBuilder::expand_degenerate).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExpansionError {
+    /// The start of the window in the sequence.
+    pub pos: usize,
+    /// The number of concrete windows represented by the window, saturated at `usize::MAX`.
+    pub expansions: usize,
+    /// The maximum number of expansions allowed per window.
+    pub max_expansions: usize,
+}
+
+impl fmt::Display for ExpansionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the window starting at position {} expands to {} concrete windows, more than the maximum of {}",
+            self.pos, self.expansions, self.max_expansions
+        )
+    }
+}
+
+impl std::error::Error for ExpansionError {}
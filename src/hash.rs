@@ -0,0 +1,266 @@
+//! Hashers used to order minimizers.
+
+use core::hash::{BuildHasher, Hasher};
+
+/// Added to the seed to obtain the initial state, so that the zero seed does not give a zero state.
+const SEED_OFFSET: u64 = 0x9e3779b97f4a7c15;
+
+/// The 64-bit finalizer of MurmurHash3.
+#[inline]
+const fn fmix64(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51afd7ed558ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ceb9fe1a85ec53);
+    x ^ (x >> 33)
+}
+
+/// A hasher with a fixed, documented output, independent of the platform and of the version of the crate.
+///
+/// The state starts at `seed ^ 0x9e3779b97f4a7c15`, and each 64-bit word `w` updates it with `state = fmix64(state ^ w)`,
+/// where `fmix64` is the 64-bit finalizer of MurmurHash3.
+/// Narrower integers are widened to `u64`, `u128` is split into its low then high words,
+/// and byte slices are read as little-endian words, the last one being padded with zeros.
+/// This output is part of the stability guarantee and will not change.
+#[derive(Clone, Copy, Debug)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    #[inline]
+    fn write_word(&mut self, w: u64) {
+        self.state = fmix64(self.state ^ w);
+    }
+}
+
+impl Hasher for StableHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_word(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_word(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_word(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_word(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write_word(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_word(i as u64);
+        self.write_word((i >> 64) as u64);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_word(i as u64);
+    }
+}
+
+/// A builder of [`StableHasher`] with a given seed, 0 by default.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BuildStableHasher {
+    seed: u64,
+}
+
+impl BuildStableHasher {
+    /// Creates a builder of [`StableHasher`] with the given seed.
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for BuildStableHasher {
+    type Hasher = StableHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> StableHasher {
+        StableHasher {
+            state: self.seed ^ SEED_OFFSET,
+        }
+    }
+}
+
+/// A borrowed hasher builder, used by the iterators borrowing a [`MinimizerBuilder`](crate::MinimizerBuilder).
+#[derive(Debug)]
+pub struct BuildHasherRef<'a, S>(pub &'a S);
+
+impl<S> Clone for BuildHasherRef<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for BuildHasherRef<'_, S> {}
+
+impl<S: BuildHasher> BuildHasher for BuildHasherRef<'_, S> {
+    type Hasher = S::Hasher;
+
+    #[inline]
+    fn build_hasher(&self) -> S::Hasher {
+        self.0.build_hasher()
+    }
+}
+
+/// A hasher returning the bitwise negation of the hash computed by `H`, so that the minimum of its hashes is the maximum of the hashes of `H`.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxHasher<H>(H);
+
+impl<H: Hasher> Hasher for MaxHasher<H> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        !self.0.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.0.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.0.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.0.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.0.write_u64(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.0.write_u128(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.0.write_usize(i);
+    }
+}
+
+/// A builder of [`MaxHasher`], used to select maximizers instead of minimizers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildMaxHasher<S>(pub S);
+
+impl<S: BuildHasher> BuildHasher for BuildMaxHasher<S> {
+    type Hasher = MaxHasher<S::Hasher>;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        MaxHasher(self.0.build_hasher())
+    }
+}
+
+/// A hasher returning the rank of a packed m-mer in a precomputed order, e.g. a universal hitting set (UHS) order.
+///
+/// The hashed value is used as an index in the ranks, so it must be written as a single integer, as the packed m-mers are.
+/// It panics in [`finish`](Hasher::finish) if the value has no rank.
+#[derive(Clone, Copy, Debug)]
+pub struct RankHasher<'a> {
+    ranks: &'a [u32],
+    index: u128,
+}
+
+impl Hasher for RankHasher<'_> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        match usize::try_from(self.index)
+            .ok()
+            .and_then(|index| self.ranks.get(index))
+        {
+            Some(&rank) => rank as u64,
+            None => panic!(
+                "the packed m-mer {} has no rank, the ranks must have 4^size entries, got {}",
+                self.index,
+                self.ranks.len()
+            ),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut index = [0u8; 16];
+        let len = bytes.len().min(16);
+        index[..len].copy_from_slice(&bytes[..len]);
+        self.index = u128::from_le_bytes(index);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.index = i as u128;
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.index = i as u128;
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.index = i as u128;
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.index = i as u128;
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.index = i;
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.index = i as u128;
+    }
+}
+
+/// A builder of [`RankHasher`] borrowing the ranks of the packed m-mers, indexed by their packed value.
+#[derive(Clone, Copy, Debug)]
+pub struct BuildRankHasher<'a>(pub &'a [u32]);
+
+impl<'a> BuildHasher for BuildRankHasher<'a> {
+    type Hasher = RankHasher<'a>;
+
+    #[inline]
+    fn build_hasher(&self) -> RankHasher<'a> {
+        RankHasher {
+            ranks: self.0,
+            index: 0,
+        }
+    }
+}
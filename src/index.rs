@@ -0,0 +1,84 @@
+//! Random-access index over the minimizers of a sequence.
+
+/// Minimizers of a sequence sorted by position, supporting range and nearest-position queries.
+///
+/// # Examples
+///
+/// ```
+/// use minimizer_iter::MinimizerBuilder;
+///
+/// let index = MinimizerBuilder::<u64>::new()
+///     .minimizer_size(3)
+///     .width(4)
+///     .index(b"TGATTGCACAATC");
+///
+/// for (minimizer, position) in index.minimizers_in_range(4, 10) {
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MinimizerIndex<T> {
+    minimizers: Vec<(T, usize)>,
+}
+
+impl<T> MinimizerIndex<T> {
+    /// Returns the number of minimizers in the index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.minimizers.len()
+    }
+
+    /// Returns `true` if the index contains no minimizers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.minimizers.is_empty()
+    }
+
+    /// Returns the minimizers and their positions, sorted by position.
+    #[inline]
+    pub fn as_slice(&self) -> &[(T, usize)] {
+        &self.minimizers
+    }
+
+    /// Returns an iterator over the minimizers and their positions, sorted by position.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, (T, usize)> {
+        self.minimizers.iter()
+    }
+
+    /// Returns the minimizers whose position lies in `start..end`.
+    pub fn minimizers_in_range(&self, start: usize, end: usize) -> &[(T, usize)] {
+        let lo = self.minimizers.partition_point(|&(_, pos)| pos < start);
+        let hi = self.minimizers.partition_point(|&(_, pos)| pos < end);
+        &self.minimizers[lo..hi.max(lo)]
+    }
+
+    /// Returns the minimizer whose position is the closest to `pos`, preferring the leftmost one on ties.
+    pub fn nearest_to(&self, pos: usize) -> Option<&(T, usize)> {
+        let i = self.minimizers.partition_point(|&(_, p)| p < pos);
+        let right = self.minimizers.get(i);
+        let left = i.checked_sub(1).map(|j| &self.minimizers[j]);
+        match (left, right) {
+            (Some(l), Some(r)) => Some(if pos - l.1 <= r.1 - pos { l } else { r }),
+            (l, r) => l.or(r),
+        }
+    }
+}
+
+impl<T> FromIterator<(T, usize)> for MinimizerIndex<T> {
+    fn from_iter<I: IntoIterator<Item = (T, usize)>>(iter: I) -> Self {
+        let mut minimizers: Vec<_> = iter.into_iter().collect();
+        minimizers.sort_by_key(|&(_, pos)| pos);
+        Self { minimizers }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MinimizerIndex<T> {
+    type Item = &'a (T, usize);
+    type IntoIter = core::slice::Iter<'a, (T, usize)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.minimizers.iter()
+    }
+}
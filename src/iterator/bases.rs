@@ -0,0 +1,145 @@
+use super::{check_hasher, check_sizes, mask};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// The rolling state of an iterator over minimizers, updated one base at a time.
+/// Each base is encoded on `BITS` bits, e.g. 5 bits for amino acids.
+pub(crate) struct RollingState<T: PrimInt + Hash, S: BuildHasher, const BITS: usize = 2> {
+    queue: MinimizerQueue<T, S>,
+    minimizer_size: usize,
+    mmer: T,
+    mmer_mask: T,
+    encoding: [u8; 256],
+    base_width: usize,
+    min_pos: Option<usize>,
+    pub(crate) end: usize,
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> RollingState<T, S> {
+    pub(crate) fn new(minimizer_size: usize, width: u16, hasher: S, encoding: [u8; 256]) -> Self {
+        Self::with_mask(
+            minimizer_size,
+            width,
+            hasher,
+            encoding,
+            mask(minimizer_size),
+        )
+    }
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher, const BITS: usize> RollingState<T, S, BITS> {
+    /// Creates a rolling state keeping the bits of `mmer_mask`, which must cover `BITS * minimizer_size` bits.
+    pub(crate) fn with_mask(
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+        mmer_mask: T,
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        Self {
+            queue,
+            minimizer_size,
+            mmer: T::zero(),
+            mmer_mask,
+            encoding,
+            base_width: width as usize + minimizer_size - 1,
+            min_pos: None,
+            end: 0,
+        }
+    }
+
+    /// Clears the window to start over on another sequence.
+    pub(crate) fn reset(&mut self) {
+        self.queue.clear();
+        self.mmer = T::zero();
+        self.min_pos = None;
+        self.end = 0;
+    }
+}
+
+impl<T: PrimInt + Hash + 'static, S: BuildHasher, const BITS: usize> RollingState<T, S, BITS>
+where
+    u8: AsPrimitive<T>,
+{
+    /// Adds the next base to the window, returning the minimizer and its position if a new one is selected.
+    #[inline]
+    pub(crate) fn push(&mut self, b: u8) -> Option<(T, usize)> {
+        self.mmer = ((self.mmer << BITS) & self.mmer_mask)
+            | (unsafe { self.encoding.get_unchecked(b as usize) }.as_());
+        self.end += 1;
+        if self.end < self.minimizer_size {
+            return None;
+        }
+        self.queue.insert(self.mmer);
+        if self.end < self.base_width {
+            return None;
+        }
+        let (x, offset) = self.queue.get_min_pos();
+        let pos = self.end - self.base_width + offset;
+        if self.min_pos == Some(pos) {
+            return None;
+        }
+        self.min_pos = Some(pos);
+        Some((x, pos))
+    }
+}
+
+/// An iterator over the minimizers and their positions in a sequence given as an iterator of bases.
+///
+/// The bases are consumed one at a time and never stored, so the sequence does not need to be materialized,
+/// and it yields exactly the same minimizers as [`MinimizerIterator`](super::MinimizerIterator) on the whole sequence.
+/// Each minimizer is yielded as soon as the window selecting it is complete, so nothing is withheld when the bases run out
+/// and there is no pending minimizer to flush.
+pub struct BasesMinimizerIterator<I, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+    pub(crate) bases: I,
+    pub(crate) state: RollingState<T, S>,
+}
+
+impl<I, T: PrimInt + Hash, S: BuildHasher> BasesMinimizerIterator<I, T, S> {
+    pub fn new(
+        bases: I,
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            bases,
+            state: RollingState::new(minimizer_size, width, hasher, encoding),
+        }
+    }
+
+    /// Returns the iterator of bases, dropping the rolling state.
+    pub fn into_inner(self) -> I {
+        self.bases
+    }
+}
+
+impl<I: Iterator<Item = u8>, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for BasesMinimizerIterator<I, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for b in self.bases.by_ref() {
+            if let Some(min_pos) = self.state.push(b) {
+                return Some(min_pos);
+            }
+        }
+        None
+    }
+}
+
+impl<I: FusedIterator<Item = u8>, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for BasesMinimizerIterator<I, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
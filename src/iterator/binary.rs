@@ -0,0 +1,120 @@
+use super::{check_hasher, check_sizes};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// Returns the mask of the `size` lowest bits, checking that they fit in `T`.
+#[inline]
+fn mask_bits<T: PrimInt>(size: usize) -> T {
+    let bits = T::zero().count_zeros() as usize;
+    assert!(
+        size <= bits,
+        "With this integer type, minimizer_size must be ≤ {bits} for a binary alphabet, got {size}. Please select a smaller size or a larger type."
+    );
+    if size == bits {
+        T::max_value()
+    } else {
+        (T::one() << size) - T::one()
+    }
+}
+
+/// An iterator over the minimizers of a sequence over a binary alphabet and their positions.
+///
+/// Each base is encoded on a single bit, given by the encoding of the base (which must be 0 or 1),
+/// for instance with purines (A, G) mapped to 0 and pyrimidines (C, T) mapped to 1.
+/// Minimizers thus fit in twice fewer bits than with the usual 2-bit encoding, up to 64 bases in a `u64`.
+pub struct BinaryMinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder>
+{
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> BinaryMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask_bits(minimizer_size),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+        }
+    }
+
+    /// Returns the 1-bit code of the base at position `i`.
+    #[inline]
+    fn base(&self, i: usize) -> T
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let code = unsafe { *self.encoding.get_unchecked(self.seq[i] as usize) };
+        debug_assert!(code < 2, "invalid binary code {code} at position {i}");
+        code.as_()
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for BinaryMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.mmer = (self.mmer << 1) | self.base(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.mmer = ((self.mmer << 1) & self.mmer_mask) | self.base(i);
+                self.queue.insert(self.mmer);
+            }
+            self.min_pos = self.queue.get_min_pos();
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+                self.mmer = ((self.mmer << 1) & self.mmer_mask) | self.base(self.end);
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for BinaryMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
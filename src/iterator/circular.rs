@@ -0,0 +1,280 @@
+use super::{check_hasher, check_sizes, mask, rc_encoding};
+use crate::Strand;
+use core::cmp::min;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+use std::collections::VecDeque;
+
+/// An iterator over the minimizers of a circular sequence and their positions.
+///
+/// After the last base, the sequence continues with its first `base_width - 1` bases,
+/// so that every window straddling the origin is considered, and positions are given modulo the length of the sequence.
+/// A minimizer selected by both the first and the last windows is only reported once.
+pub struct CircularMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) first_pos: usize,
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> CircularMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+            first_pos: 0,
+        }
+    }
+
+    /// Returns the encoding of the base at position `i` of the circular sequence.
+    #[inline]
+    fn base(&self, i: usize) -> T
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let b = self.seq[i % self.seq.len()];
+        unsafe { self.encoding.get_unchecked(b as usize) }.as_()
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for CircularMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.seq.len();
+        if self.queue.is_empty() {
+            if len == 0 {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.mmer = (self.mmer << 2) | self.base(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask) | self.base(i);
+                self.queue.insert(self.mmer);
+            }
+            self.min_pos = self.queue.get_min_pos();
+            self.first_pos = self.min_pos.1 % len;
+            return Some((self.min_pos.0, self.first_pos));
+        }
+        loop {
+            let mut min_pos = self.min_pos;
+            while self.end < len + self.base_width - 1 && min_pos.1 == self.min_pos.1 {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask) | self.base(self.end);
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+            if self.min_pos.1 < len || self.min_pos.1 % len != self.first_pos {
+                return Some((self.min_pos.0, self.min_pos.1 % len));
+            }
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for CircularMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the canonical minimizers of a circular sequence and their positions with their [`Strand`].
+/// It requires an odd width to break ties between multiple minimizers.
+///
+/// After the last base, the sequence continues with its first `base_width - 1` bases,
+/// so that every window straddling the origin is considered, and positions are given modulo the length of the sequence.
+/// A minimizer selected by both the first and the last windows is only reported once.
+pub struct CanonicalCircularMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) rc_mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) rc_mmer_shift: usize,
+    pub(crate) is_rc: VecDeque<bool>,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) rc_encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize, Strand),
+    pub(crate) first_pos: usize,
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalCircularMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        assert_eq!(
+            width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        let rc_encoding = rc_encoding(&encoding, &complement);
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            rc_mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            rc_mmer_shift: 2 * (minimizer_size - 1),
+            is_rc: VecDeque::with_capacity(width),
+            encoding,
+            rc_encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0, Strand::Forward),
+            first_pos: 0,
+        }
+    }
+
+    #[inline]
+    fn window_not_canonical(&self) -> bool {
+        self.is_rc[self.width / 2]
+    }
+
+    /// Rolls the m-mers of both strands with the base at position `i` of the circular sequence.
+    #[inline]
+    fn roll(&mut self, i: usize)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let b = self.seq[i % self.seq.len()] as usize;
+        self.mmer =
+            ((self.mmer << 2) & self.mmer_mask) | (unsafe { self.encoding.get_unchecked(b) }.as_());
+        self.rc_mmer = (self.rc_mmer >> 2)
+            | (unsafe { self.rc_encoding.get_unchecked(b) }.as_() << self.rc_mmer_shift);
+    }
+
+    #[inline]
+    fn window_min_pos(&self) -> (T, usize) {
+        if self.queue.multiple_mins() {
+            let (x, pos, tie) = self.queue.get_inner_min_pos();
+            tie.map_or((x, pos), |alt| {
+                if self.window_not_canonical() {
+                    alt
+                } else {
+                    (x, pos)
+                }
+            })
+        } else {
+            self.queue.get_min_pos()
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for CanonicalCircularMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, Strand);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.seq.len();
+        if self.queue.is_empty() {
+            if len == 0 {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.roll(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.roll(i);
+                let canonical_mmer = min(self.mmer, self.rc_mmer);
+                self.queue.insert(canonical_mmer);
+                self.is_rc.push_back(canonical_mmer == self.rc_mmer);
+            }
+            let _min_pos = self.window_min_pos();
+            self.min_pos = (_min_pos.0, _min_pos.1, Strand::from(self.is_rc[_min_pos.1]));
+            self.first_pos = self.min_pos.1 % len;
+            return Some((self.min_pos.0, self.first_pos, self.min_pos.2));
+        }
+        loop {
+            let mut min_pos = self.min_pos;
+            while self.end < len + self.base_width - 1 && min_pos.1 == self.min_pos.1 {
+                self.roll(self.end);
+                let canonical_mmer = min(self.mmer, self.rc_mmer);
+                self.queue.insert(canonical_mmer);
+                self.is_rc.pop_front();
+                self.is_rc.push_back(canonical_mmer == self.rc_mmer);
+                self.end += 1;
+                let _min_pos = self.window_min_pos();
+                min_pos = (
+                    _min_pos.0,
+                    self.end - self.base_width + _min_pos.1,
+                    Strand::from(self.is_rc[_min_pos.1]),
+                );
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+            if self.min_pos.1 < len || self.min_pos.1 % len != self.first_pos {
+                return Some((self.min_pos.0, self.min_pos.1 % len, self.min_pos.2));
+            }
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for CanonicalCircularMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,147 @@
+use crate::Strand;
+use core::iter::FusedIterator;
+use num_traits::PrimInt;
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Computes a stable 64-bit fingerprint of a packed minimizer, independent of the hasher used to order minimizers.
+///
+/// The fingerprint is the 64-bit FNV-1a hash of the 16 little-endian bytes of the minimizer widened to `u128`,
+/// so it does not depend on the integer type either.
+/// Since it is computed from the packed value, it does depend on the encoding of the bases.
+/// Negative values of signed types are reinterpreted as the unsigned value with the same bits.
+pub fn fingerprint<T: PrimInt>(mmer: T) -> u64 {
+    let bytes = to_unsigned_u128(mmer).to_le_bytes();
+    bytes.iter().fold(FNV_OFFSET, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Computes a stable 128-bit key of a packed minimizer, independent of the hasher used to order minimizers.
+///
+/// The key is obtained by mixing the minimizer widened to `u128` with xor-shifts and multiplications by odd constants,
+/// which is a bijection of `u128`: distinct minimizers never share a key, whatever their number.
+/// As with [`fingerprint`], it does not depend on the integer type but does depend on the encoding of the bases,
+/// and negative values of signed types are reinterpreted as the unsigned value with the same bits.
+pub fn fingerprint128<T: PrimInt>(mmer: T) -> u128 {
+    let mut key = to_unsigned_u128(mmer);
+    key ^= key >> 64;
+    key = key.wrapping_mul(KEY128_MUL_1);
+    key ^= key >> 64;
+    key = key.wrapping_mul(KEY128_MUL_2);
+    key ^ (key >> 64)
+}
+
+/// Widens a value to `u128`, reinterpreting the bits of negative values of signed types as unsigned.
+#[inline]
+fn to_unsigned_u128<T: PrimInt>(mmer: T) -> u128 {
+    match mmer.to_u128() {
+        Some(value) => value,
+        None => {
+            let bits = T::zero().count_zeros();
+            let value = mmer.to_i128().unwrap() as u128;
+            if bits == u128::BITS {
+                value
+            } else {
+                value & ((1 << bits) - 1)
+            }
+        }
+    }
+}
+
+const KEY128_MUL_1: u128 = 0x9e3779b97f4a7c15f39cc0605cedc835;
+const KEY128_MUL_2: u128 = 0xbf58476d1ce4e5b94d2a6b7e3c1f0d9b;
+
+/// An iterator over minimizers and their positions, with the [`fingerprint`] of each minimizer
+/// inserted between the minimizer and its position.
+pub struct FingerprintIterator<I> {
+    pub(crate) inner: I,
+}
+
+impl<I> FingerprintIterator<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: PrimInt, I: Iterator<Item = (T, usize)>> Iterator for FingerprintIterator<I> {
+    type Item = (T, u64, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        Some((min, fingerprint(min), pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: PrimInt, I: FusedIterator<Item = (T, usize)>> FusedIterator for FingerprintIterator<I> {}
+
+/// An iterator over minimizers and their positions, with the [`fingerprint128`] of each minimizer inserted first.
+///
+/// The key is only computed for the yielded minimizers, not for every window.
+pub struct Key128Iterator<I> {
+    pub(crate) inner: I,
+}
+
+impl<I> Key128Iterator<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: PrimInt, I: Iterator<Item = (T, usize)>> Iterator for Key128Iterator<I> {
+    type Item = (u128, T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        Some((fingerprint128(min), min, pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: PrimInt, I: FusedIterator<Item = (T, usize)>> FusedIterator for Key128Iterator<I> {}
+
+/// An iterator over canonical minimizers, their positions and their strands,
+/// with the [`fingerprint128`] of each canonical minimizer inserted first.
+///
+/// The key is computed from the canonical minimizer, so both strands of an m-mer share the same key.
+pub struct CanonicalKey128Iterator<I> {
+    pub(crate) inner: I,
+}
+
+impl<I> CanonicalKey128Iterator<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: PrimInt, I: Iterator<Item = (T, usize, Strand)>> Iterator for CanonicalKey128Iterator<I> {
+    type Item = (u128, T, usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos, strand) = self.inner.next()?;
+        Some((fingerprint128(min), min, pos, strand))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: PrimInt, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator
+    for CanonicalKey128Iterator<I>
+{
+}
@@ -0,0 +1,100 @@
+use crate::Strand;
+use core::iter::FusedIterator;
+
+/// An iterator over minimizers and their positions counted from the end of the sequence,
+/// i.e. `seq.len() - pos - minimizer_size` for a minimizer starting at `pos`.
+///
+/// This is the position of the minimizer in the reverse complement of the sequence.
+/// The minimizers are yielded in the same order as the underlying iterator, so these positions are decreasing.
+pub struct FromEndIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) last_mmer: usize,
+}
+
+impl<I> FromEndIterator<I> {
+    /// Wraps an iterator over minimizers, given the position of the last m-mer of the sequence, i.e. `seq.len() - minimizer_size`.
+    pub fn new(inner: I, last_mmer: usize) -> Self {
+        Self { inner, last_mmer }
+    }
+}
+
+impl<T, I: Iterator<Item = (T, usize)>> Iterator for FromEndIterator<I> {
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        Some((min, self.last_mmer - pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, I: FusedIterator<Item = (T, usize)>> FusedIterator for FromEndIterator<I> {}
+
+/// An iterator over the positions of minimizers counted from the end of the sequence,
+/// i.e. `seq.len() - pos - minimizer_size` for a minimizer starting at `pos`.
+pub struct PosFromEndIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) last_mmer: usize,
+}
+
+impl<I> PosFromEndIterator<I> {
+    /// Wraps an iterator over positions of minimizers, given the position of the last m-mer of the sequence, i.e. `seq.len() - minimizer_size`.
+    pub fn new(inner: I, last_mmer: usize) -> Self {
+        Self { inner, last_mmer }
+    }
+}
+
+impl<I: Iterator<Item = usize>> Iterator for PosFromEndIterator<I> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.inner.next()?;
+        Some(self.last_mmer - pos)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: FusedIterator<Item = usize>> FusedIterator for PosFromEndIterator<I> {}
+
+/// An iterator over canonical minimizers and their positions counted from the end of the sequence with their strand,
+/// i.e. `seq.len() - pos - minimizer_size` for a minimizer starting at `pos`.
+///
+/// The strand still refers to the forward sequence, so it is reversed compared to the canonical minimizers of the reverse complement.
+pub struct CanonicalFromEndIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) last_mmer: usize,
+}
+
+impl<I> CanonicalFromEndIterator<I> {
+    /// Wraps an iterator over canonical minimizers, given the position of the last m-mer of the sequence, i.e. `seq.len() - minimizer_size`.
+    pub fn new(inner: I, last_mmer: usize) -> Self {
+        Self { inner, last_mmer }
+    }
+}
+
+impl<T, I: Iterator<Item = (T, usize, Strand)>> Iterator for CanonicalFromEndIterator<I> {
+    type Item = (T, usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos, strand) = self.inner.next()?;
+        Some((min, self.last_mmer - pos, strand))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator for CanonicalFromEndIterator<I> {}
@@ -0,0 +1,70 @@
+use core::iter::FusedIterator;
+use num_traits::PrimInt;
+
+/// An iterator over minimizers and their positions, with the number of G/C bases in each minimizer.
+///
+/// The G/C bases are counted directly on the encoded minimizer, by comparing each 2-bit symbol with the codes of C and G.
+pub struct GcCountIterator<I, T: PrimInt> {
+    pub(crate) inner: I,
+    pub(crate) c_pattern: T,
+    pub(crate) g_pattern: T,
+    pub(crate) low_bits: T,
+    pub(crate) same_codes: bool,
+}
+
+impl<I, T: PrimInt> GcCountIterator<I, T> {
+    /// Wraps an iterator over minimizers of size `minimizer_size`, encoded with the given encoding.
+    pub fn new(inner: I, minimizer_size: usize, encoding: &[u8; 256]) -> Self {
+        let c = encoding[b'C' as usize] & 0b11;
+        let g = encoding[b'G' as usize] & 0b11;
+        let mut c_pattern = T::zero();
+        let mut g_pattern = T::zero();
+        let mut low_bits = T::zero();
+        for _ in 0..minimizer_size {
+            c_pattern = (c_pattern << 2) | T::from(c).unwrap();
+            g_pattern = (g_pattern << 2) | T::from(g).unwrap();
+            low_bits = (low_bits << 2) | T::one();
+        }
+        Self {
+            inner,
+            c_pattern,
+            g_pattern,
+            low_bits,
+            same_codes: c == g,
+        }
+    }
+
+    /// Counts the 2-bit symbols of `x` equal to the ones of `pattern`.
+    #[inline]
+    fn count_symbols(&self, x: T, pattern: T) -> usize {
+        let diff = x ^ pattern;
+        (!(diff | (diff >> 1)) & self.low_bits).count_ones() as usize
+    }
+
+    #[inline]
+    fn gc_count(&self, x: T) -> usize {
+        let c_count = self.count_symbols(x, self.c_pattern);
+        if self.same_codes {
+            c_count
+        } else {
+            c_count + self.count_symbols(x, self.g_pattern)
+        }
+    }
+}
+
+impl<T: PrimInt, I: Iterator<Item = (T, usize)>> Iterator for GcCountIterator<I, T> {
+    type Item = (T, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        Some((min, pos, self.gc_count(min)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: PrimInt, I: FusedIterator<Item = (T, usize)>> FusedIterator for GcCountIterator<I, T> {}
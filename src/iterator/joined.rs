@@ -0,0 +1,63 @@
+use super::bases::RollingState;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::DefaultHashBuilder;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the minimizers and their positions in a sequence given as two halves, e.g. the two parts of a ring buffer.
+///
+/// The second half is considered to follow the first one, so the m-mers and the windows straddling the junction are rolled across it,
+/// and the positions are given in the joined sequence, i.e. in `0..(first.len() + second.len())`.
+/// It yields exactly the same minimizers as [`MinimizerIterator`](super::MinimizerIterator) on the concatenation of the halves,
+/// without copying them.
+pub struct JoinedMinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder>
+{
+    pub(crate) first: &'a [u8],
+    pub(crate) second: &'a [u8],
+    pub(crate) state: RollingState<T, S>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> JoinedMinimizerIterator<'a, T, S> {
+    pub fn new(
+        first: &'a [u8],
+        second: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            first,
+            second,
+            state: RollingState::new(minimizer_size, width, hasher, encoding),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for JoinedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.state.end < self.first.len() {
+            if let Some(min_pos) = self.state.push(self.first[self.state.end]) {
+                return Some(min_pos);
+            }
+        }
+        while let Some(&b) = self.second.get(self.state.end - self.first.len()) {
+            if let Some(min_pos) = self.state.push(b) {
+                return Some(min_pos);
+            }
+        }
+        None
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for JoinedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
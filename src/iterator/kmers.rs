@@ -0,0 +1,79 @@
+use crate::Strand;
+use core::iter::FusedIterator;
+
+/// An iterator over the minimizers of a sequence as slices of the sequence and their positions.
+///
+/// The slices borrow directly from the sequence, so no decoding is needed.
+/// This relies on minimizers being contiguous substrings of the sequence, which would not hold
+/// with transformations such as homopolymer compression or spaced seeds.
+pub struct KmerIterator<'a, I> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) minimizer_size: usize,
+    pub(crate) inner: I,
+}
+
+impl<'a, I> KmerIterator<'a, I> {
+    pub fn new(seq: &'a [u8], minimizer_size: usize, inner: I) -> Self {
+        Self {
+            seq,
+            minimizer_size,
+            inner,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = usize>> Iterator for KmerIterator<'a, I> {
+    type Item = (&'a [u8], usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.inner.next()?;
+        Some((&self.seq[pos..(pos + self.minimizer_size)], pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, I: FusedIterator<Item = usize>> FusedIterator for KmerIterator<'a, I> {}
+
+/// An iterator over the canonical minimizers of a sequence as slices of the sequence, their positions,
+/// and their strand, i.e. [`Strand::Reverse`] if the canonical minimizer is the reverse complement of the slice.
+///
+/// The slices borrow directly from the sequence, so no decoding is needed.
+/// This relies on minimizers being contiguous substrings of the sequence, which would not hold
+/// with transformations such as homopolymer compression or spaced seeds.
+pub struct CanonicalKmerIterator<'a, I> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) minimizer_size: usize,
+    pub(crate) inner: I,
+}
+
+impl<'a, I> CanonicalKmerIterator<'a, I> {
+    pub fn new(seq: &'a [u8], minimizer_size: usize, inner: I) -> Self {
+        Self {
+            seq,
+            minimizer_size,
+            inner,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = (usize, Strand)>> Iterator for CanonicalKmerIterator<'a, I> {
+    type Item = (&'a [u8], usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, strand) = self.inner.next()?;
+        Some((&self.seq[pos..(pos + self.minimizer_size)], pos, strand))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, I: FusedIterator<Item = (usize, Strand)>> FusedIterator for CanonicalKmerIterator<'a, I> {}
@@ -0,0 +1,118 @@
+use super::nthash::BuildIdentityHasher;
+use super::{check_sizes, mask};
+use core::fmt::{self, Debug, Formatter};
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use minimizer_queue::MinimizerQueue;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the lexicographic minimizers of a sequence and their positions.
+///
+/// The packed m-mers are directly used as their own hash, so no hasher is called and
+/// the smallest m-mer of each window (following the order of the encoding) is selected.
+/// It requires the m-mers to fit in 64 bits, i.e. `minimizer_size ≤ 32`.
+pub struct LexMinimizerIterator<'a, T: PrimInt + Hash = u64> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, BuildIdentityHasher>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash + Debug> Debug for LexMinimizerIterator<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LexMinimizerIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash> LexMinimizerIterator<'a, T> {
+    pub fn new(seq: &'a [u8], minimizer_size: usize, width: u16, encoding: [u8; 256]) -> Self {
+        check_sizes(minimizer_size, width);
+        assert!(
+            minimizer_size <= 32,
+            "lexicographic minimizers require minimizer_size ≤ 32, got {minimizer_size}"
+        );
+        let queue = MinimizerQueue::with_hasher(width, BuildIdentityHasher::default());
+        let width = width as usize;
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+        }
+    }
+
+    /// Inserts the current m-mer in the queue, using its value as its hash.
+    #[inline]
+    fn insert(&mut self) {
+        // m-mers fit in 64 bits since minimizer_size ≤ 32, and sign-extending the bits of negative values
+        // of signed types keeps them above the non-negative ones, in the order of their unsigned bits
+        let hash = match self.mmer.to_u64() {
+            Some(hash) => hash,
+            None => self.mmer.to_i64().unwrap() as u64,
+        };
+        self.queue.insert_with_hash(self.mmer, hash);
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static> Iterator for LexMinimizerIterator<'a, T>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.mmer = (self.mmer << 2)
+                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.insert();
+            }
+            self.min_pos = self.queue.get_min_pos();
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                    | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
+                self.insert();
+                self.end += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static> FusedIterator for LexMinimizerIterator<'a, T> where
+    u8: AsPrimitive<T>
+{
+}
@@ -0,0 +1,76 @@
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+
+/// An iterator over the minimizers and their positions whose hash is a strict local minimum of the stream of minimizers.
+///
+/// A minimizer is kept if its hash is strictly smaller than the hashes of both the previous and the next minimizers of the underlying iterator,
+/// whether or not these neighbors are kept themselves.
+/// The first and the last minimizers only have one neighbor to compare to, and a lone minimizer is always kept.
+/// Since the hashes are compared strictly, two consecutive minimizers with the same value are both discarded.
+///
+/// For random sequences, about one minimizer out of four is a local minimum, so the density is reduced roughly fourfold.
+/// This is less than the one out of three expected for independent values, because consecutive minimizers are correlated:
+/// a minimizer leaving the window is usually replaced by a larger one.
+pub struct LocalMinimaIterator<I, T, S: BuildHasher> {
+    pub(crate) inner: I,
+    pub(crate) hasher: S,
+    pub(crate) prev_hash: Option<u64>,
+    pub(crate) current: Option<(T, usize, u64)>,
+    pub(crate) started: bool,
+}
+
+impl<I, T, S: BuildHasher> LocalMinimaIterator<I, T, S> {
+    pub fn new(inner: I, hasher: S) -> Self {
+        Self {
+            inner,
+            hasher,
+            prev_hash: None,
+            current: None,
+            started: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = (T, usize)>, T: Hash, S: BuildHasher> LocalMinimaIterator<I, T, S> {
+    #[inline]
+    fn pull(&mut self) -> Option<(T, usize, u64)> {
+        let (min, pos) = self.inner.next()?;
+        let hash = self.hasher.hash_one(&min);
+        Some((min, pos, hash))
+    }
+}
+
+impl<I: Iterator<Item = (T, usize)>, T: Hash, S: BuildHasher> Iterator
+    for LocalMinimaIterator<I, T, S>
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.current = self.pull();
+            self.started = true;
+        }
+        loop {
+            let (min, pos, hash) = self.current.take()?;
+            self.current = self.pull();
+            let below_prev = !matches!(self.prev_hash, Some(prev) if prev <= hash);
+            let below_next = !matches!(self.current, Some((_, _, next)) if next <= hash);
+            self.prev_hash = Some(hash);
+            if below_prev && below_next {
+                return Some((min, pos));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.current.is_some());
+        (0, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<I: FusedIterator<Item = (T, usize)>, T: Hash, S: BuildHasher> FusedIterator
+    for LocalMinimaIterator<I, T, S>
+{
+}
@@ -0,0 +1,94 @@
+use crate::Strand;
+use core::iter::FusedIterator;
+
+/// An iterator over minimizers and their positions, skipping a minimizer if it is less than `distance` bases away from the last yielded one.
+///
+/// The first minimizer is always yielded, and the distance is measured from the last yielded minimizer,
+/// so the skipped minimizers do not push the following ones further away.
+/// Unlike a fixed stride, this adapts to where the minimizers fall, and consecutive yielded minimizers are at least `distance` bases apart.
+pub struct MinDistanceIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) distance: usize,
+    pub(crate) last_pos: Option<usize>,
+}
+
+impl<I> MinDistanceIterator<I> {
+    pub fn new(inner: I, distance: usize) -> Self {
+        Self {
+            inner,
+            distance,
+            last_pos: None,
+        }
+    }
+
+    /// Returns whether the minimizer at the given position should be yielded, recording it if so.
+    #[inline]
+    fn accept(&mut self, pos: usize) -> bool {
+        if matches!(self.last_pos, Some(last_pos) if pos.abs_diff(last_pos) < self.distance) {
+            return false;
+        }
+        self.last_pos = Some(pos);
+        true
+    }
+}
+
+impl<T, I: Iterator<Item = (T, usize)>> Iterator for MinDistanceIterator<I> {
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((min, pos)) = self.inner.next() {
+            if self.accept(pos) {
+                return Some((min, pos));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<T, I: FusedIterator<Item = (T, usize)>> FusedIterator for MinDistanceIterator<I> {}
+
+/// An iterator over canonical minimizers and their positions with their strand,
+/// skipping a minimizer if it is less than `distance` bases away from the last yielded one.
+///
+/// Breaking ties may select a position before the last yielded one, so the distance is measured in both directions.
+pub struct CanonicalMinDistanceIterator<I> {
+    pub(crate) inner: MinDistanceIterator<I>,
+}
+
+impl<I> CanonicalMinDistanceIterator<I> {
+    pub fn new(inner: I, distance: usize) -> Self {
+        Self {
+            inner: MinDistanceIterator::new(inner, distance),
+        }
+    }
+}
+
+impl<T, I: Iterator<Item = (T, usize, Strand)>> Iterator for CanonicalMinDistanceIterator<I> {
+    type Item = (T, usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((min, pos, strand)) = self.inner.inner.next() {
+            if self.inner.accept(pos) {
+                return Some((min, pos, strand));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.inner.size_hint().1)
+    }
+}
+
+impl<T, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator
+    for CanonicalMinDistanceIterator<I>
+{
+}
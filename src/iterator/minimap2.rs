@@ -0,0 +1,190 @@
+use crate::Strand;
+use core::iter::FusedIterator;
+use std::collections::VecDeque;
+
+/// Invertible integer hash used by minimap2 to order k-mers.
+#[inline]
+pub(crate) fn hash64(key: u64, mask: u64) -> u64 {
+    let mut key = (!key).wrapping_add(key << 21) & mask;
+    key ^= key >> 24;
+    key = key.wrapping_add(key << 3).wrapping_add(key << 8) & mask;
+    key ^= key >> 14;
+    key = key.wrapping_add(key << 2).wrapping_add(key << 4) & mask;
+    key ^= key >> 28;
+    key.wrapping_add(key << 31) & mask
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    hash: u64,
+    kmer: u64,
+    pos: usize,
+    is_rc: bool,
+}
+
+const EMPTY: Entry = Entry {
+    hash: u64::MAX,
+    kmer: 0,
+    pos: usize::MAX,
+    is_rc: false,
+};
+
+impl Entry {
+    #[inline]
+    fn same_origin(&self, other: &Self) -> bool {
+        self.pos == other.pos && self.is_rc == other.is_rc
+    }
+}
+
+/// An iterator over the canonical minimizers of a sequence following the conventions of minimap2's `mm_sketch`,
+/// yielding the canonical minimizers and their positions with their [`Strand`].
+///
+/// Compared to [`CanonicalMinimizerIterator`](super::CanonicalMinimizerIterator), it matches minimap2 in that:
+/// - k-mers are ordered by minimap2's invertible `hash64` of the canonical k-mer, the hasher of the builder is ignored
+/// - bases are encoded as A = `00`, C = `01`, G = `10`, T = `11` (case-insensitive), the encoding of the builder is ignored
+/// - the strand is forward iff the forward k-mer is strictly smaller than its reverse complement
+/// - k-mers equal to their reverse complement are skipped and do not occupy a slot of the window
+/// - any other base resets the window
+/// - ties are broken in favor of the rightmost k-mer, and every k-mer of the window sharing the minimal hash is reported
+///
+/// Unlike minimap2 which reports the position of the last base of a k-mer, positions are those of the first base.
+pub struct Minimap2Iterator<'a> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) minimizer_size: usize,
+    pub(crate) width: usize,
+    pub(crate) shift: usize,
+    pub(crate) mask: u64,
+    pub(crate) kmer: [u64; 2],
+    buf: Vec<Entry>,
+    min: Entry,
+    buf_pos: usize,
+    min_pos: usize,
+    len: usize,
+    i: usize,
+    out: VecDeque<(u64, usize, Strand)>,
+}
+
+impl<'a> Minimap2Iterator<'a> {
+    pub fn new(seq: &'a [u8], minimizer_size: usize, width: u16) -> Self {
+        assert!(
+            (1..=28).contains(&minimizer_size),
+            "minimap2 requires 1 ≤ minimizer_size ≤ 28"
+        );
+        assert!(
+            (1..256).contains(&width),
+            "minimap2 requires 1 ≤ width < 256"
+        );
+        let width = width as usize;
+        Self {
+            seq,
+            minimizer_size,
+            width,
+            shift: 2 * (minimizer_size - 1),
+            mask: (1 << (2 * minimizer_size)) - 1,
+            kmer: [0, 0],
+            buf: vec![EMPTY; width],
+            min: EMPTY,
+            buf_pos: 0,
+            min_pos: 0,
+            len: 0,
+            i: 0,
+            out: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, entry: Entry) {
+        self.out
+            .push_back((entry.kmer, entry.pos, Strand::from(entry.is_rc)));
+    }
+
+    /// Pushes the entries of the window sharing the hash of the minimum, in sequence order.
+    #[inline]
+    fn push_identical(&mut self, range_end: usize) {
+        for j in (self.buf_pos + 1..self.width).chain(0..range_end) {
+            let entry = self.buf[j];
+            if self.min.hash == entry.hash && !self.min.same_origin(&entry) {
+                self.push(entry);
+            }
+        }
+    }
+
+    fn step(&mut self) {
+        let i = self.i;
+        self.i += 1;
+        let (k, w) = (self.minimizer_size, self.width);
+        let mut info = EMPTY;
+        let c = match self.seq[i] {
+            b'A' | b'a' => 0,
+            b'C' | b'c' => 1,
+            b'G' | b'g' => 2,
+            b'T' | b't' => 3,
+            _ => 4,
+        };
+        if c < 4 {
+            self.kmer[0] = ((self.kmer[0] << 2) | c) & self.mask;
+            self.kmer[1] = (self.kmer[1] >> 2) | ((3 ^ c) << self.shift);
+            if self.kmer[0] == self.kmer[1] {
+                return;
+            }
+            let is_rc = self.kmer[0] >= self.kmer[1];
+            self.len += 1;
+            if self.len >= k {
+                let kmer = self.kmer[is_rc as usize];
+                info = Entry {
+                    hash: hash64(kmer, self.mask),
+                    kmer,
+                    pos: i + 1 - k,
+                    is_rc,
+                };
+            }
+        } else {
+            self.len = 0;
+        }
+        self.buf[self.buf_pos] = info;
+        if self.len == w + k - 1 && self.min.hash != u64::MAX {
+            self.push_identical(self.buf_pos);
+        }
+        if info.hash <= self.min.hash {
+            if self.len >= w + k && self.min.hash != u64::MAX {
+                self.push(self.min);
+            }
+            self.min = info;
+            self.min_pos = self.buf_pos;
+        } else if self.buf_pos == self.min_pos {
+            if self.len >= w + k - 1 && self.min.hash != u64::MAX {
+                self.push(self.min);
+            }
+            self.min.hash = u64::MAX;
+            for j in (self.buf_pos + 1..w).chain(0..=self.buf_pos) {
+                if self.min.hash >= self.buf[j].hash {
+                    self.min = self.buf[j];
+                    self.min_pos = j;
+                }
+            }
+            if self.len >= w + k - 1 && self.min.hash != u64::MAX {
+                self.push_identical(self.buf_pos + 1);
+            }
+        }
+        self.buf_pos += 1;
+        if self.buf_pos == w {
+            self.buf_pos = 0;
+        }
+    }
+}
+
+impl<'a> Iterator for Minimap2Iterator<'a> {
+    type Item = (u64, usize, Strand);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.out.is_empty() && self.i < self.seq.len() {
+            self.step();
+            if self.i == self.seq.len() && self.min.hash != u64::MAX {
+                self.push(self.min);
+            }
+        }
+        self.out.pop_front()
+    }
+}
+
+impl<'a> FusedIterator for Minimap2Iterator<'a> {}
@@ -1,30 +1,93 @@
+use super::{
+    center_not_canonical, check_hasher, check_queue, check_sizes, mask, rc_encoding, BaseOrder,
+};
+use crate::encoding::BaseEncoding;
+use crate::hash::BuildHasherRef;
+use crate::queue::{MinPosWindow, MinWindow};
+use crate::sink::MinimizerSink;
+use crate::Strand;
 use core::cmp::min;
+use core::fmt::{self, Debug, Formatter};
 use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
 use minimizer_queue::{DefaultHashBuilder, ImplicitMinimizerQueue, MinimizerQueue};
 use num_traits::{AsPrimitive, PrimInt};
 use std::collections::VecDeque;
 
 /// An iterator over the positions of the minimizers of a sequence.
-pub struct MinimizerPosIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+///
+/// The minimizers are selected by an [`ImplicitMinimizerQueue`] by default,
+/// or by any queue implementing [`MinPosWindow`], see [`with_queue`](Self::with_queue).
+pub struct MinimizerPosIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
+    E: BaseEncoding = [u8; 256],
+> {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: ImplicitMinimizerQueue<S>,
+    pub(crate) queue: Q,
     pub(crate) width: usize,
     pub(crate) mmer: T,
     pub(crate) mmer_mask: T,
-    pub(crate) encoding: [u8; 256],
+    pub(crate) base_order: BaseOrder,
+    pub(crate) top_shift: usize,
+    pub(crate) encoding: E,
     pub(crate) base_width: usize,
     pub(crate) min_pos: usize,
     pub(crate) end: usize,
+    pub(crate) _marker: PhantomData<S>,
 }
 
-impl<'a, T: PrimInt + Hash, S: BuildHasher> MinimizerPosIterator<'a, T, S> {
-    pub fn new(
-        seq: &'a [u8],
-        minimizer_size: usize,
-        width: u16,
-        hasher: S,
-        encoding: [u8; 256],
-    ) -> Self {
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding> Debug
+    for MinimizerPosIterator<'a, T, S, Q, E>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinimizerPosIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<
+        'a,
+        T: PrimInt + Hash,
+        S: BuildHasher,
+        Q: MinPosWindow<T> + Clone,
+        E: BaseEncoding + Clone,
+    > Clone for MinimizerPosIterator<'a, T, S, Q, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width: self.width,
+            mmer: self.mmer,
+            mmer_mask: self.mmer_mask,
+            base_order: self.base_order,
+            top_shift: self.top_shift,
+            encoding: self.encoding.clone(),
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            _marker: self._marker,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, E: BaseEncoding>
+    MinimizerPosIterator<'a, T, S, ImplicitMinimizerQueue<S>, E>
+{
+    pub fn new(seq: &'a [u8], minimizer_size: usize, width: u16, hasher: S, encoding: E) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
         let queue = ImplicitMinimizerQueue::with_hasher(width, hasher);
         let width = width as usize;
         Self {
@@ -32,44 +95,80 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> MinimizerPosIterator<'a, T, S> {
             queue,
             width,
             mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
+            mmer_mask: mask(minimizer_size),
+            base_order: BaseOrder::MostSignificantFirst,
+            top_shift: 2 * (minimizer_size - 1),
             encoding,
             base_width: width + minimizer_size - 1,
             end: width + minimizer_size - 1,
             min_pos: 0,
+            _marker: PhantomData,
         }
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for MinimizerPosIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding>
+    MinimizerPosIterator<'a, T, S, Q, E>
+{
+    /// Creates an iterator selecting the minimizers with the given queue, which must be empty.
+    /// The width of the window is given by the queue.
+    pub fn with_queue(seq: &'a [u8], minimizer_size: usize, queue: Q, encoding: E) -> Self {
+        let width = check_queue(minimizer_size, &queue);
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            base_order: BaseOrder::MostSignificantFirst,
+            top_shift: 2 * (minimizer_size - 1),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding>
+    MinimizerPosIterator<'a, T, S, Q, E>
 where
     u8: AsPrimitive<T>,
 {
-    type Item = usize;
+    /// Appends a base to the rolling m-mer, the first base being the least significant one if `LSF` is set.
+    /// The order is a constant so that the rolling loops do not branch on it for each base.
+    #[inline]
+    fn push<const LSF: bool>(&mut self, base: u8) {
+        let code: T = self.encoding.code(base).as_();
+        self.mmer = if LSF {
+            (self.mmer >> 2) | (code << self.top_shift)
+        } else {
+            ((self.mmer << 2) & self.mmer_mask) | code
+        };
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances to the next position, with the order of the bases fixed by `LSF`.
+    fn next_in_order<const LSF: bool>(&mut self) -> Option<usize> {
         if self.queue.is_empty() {
             if self.base_width > self.seq.len() {
                 return None;
             }
             for i in 0..(self.base_width - self.width) {
-                self.mmer = (self.mmer << 2)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.push::<LSF>(self.seq[i]);
             }
             for i in (self.base_width - self.width)..self.base_width {
-                self.mmer = ((self.mmer << 2) & self.mmer_mask)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
-                self.queue.insert(&self.mmer);
+                self.push::<LSF>(self.seq[i]);
+                self.queue.insert(self.mmer);
             }
-            self.min_pos = self.queue.get_min_pos();
+            self.min_pos = self.queue.min_pos();
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos == self.min_pos {
-                self.mmer = ((self.mmer << 2) & self.mmer_mask)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
-                self.queue.insert(&self.mmer);
+                self.push::<LSF>(self.seq[self.end]);
+                self.queue.insert(self.mmer);
                 self.end += 1;
-                min_pos = self.end - self.base_width + self.queue.get_min_pos();
+                min_pos = self.end - self.base_width + self.queue.min_pos();
             }
             if min_pos == self.min_pos {
                 return None;
@@ -80,48 +179,580 @@ where
     }
 }
 
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding> Iterator
+    for MinimizerPosIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.base_order {
+            BaseOrder::MostSignificantFirst => self.next_in_order::<false>(),
+            BaseOrder::LeastSignificantFirst => self.next_in_order::<true>(),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding>
+    FusedIterator for MinimizerPosIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the positions of the minimizers of a sequence relative to the center of a window,
+/// i.e. `pos - (window_start + width / 2)` where `window_start` is the start of the first window selecting the minimizer.
+pub struct CenteredMinimizerPosIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) inner: MinimizerPosIterator<'a, T, S>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> CenteredMinimizerPosIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            inner: MinimizerPosIterator::new(seq, minimizer_size, width, hasher, encoding),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for CenteredMinimizerPosIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = i64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.inner.next()?;
+        // the window ending at `end` is the first one selecting this minimizer
+        let window_start = self.inner.end - self.inner.base_width;
+        Some(pos as i64 - (window_start + self.inner.width / 2) as i64)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for CenteredMinimizerPosIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
 /// An iterator over the minimizers of a sequence and their positions.
-pub struct MinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+///
+/// The minimizers are selected by a [`MinimizerQueue`] by default,
+/// or by any queue implementing [`MinWindow`], see [`with_queue`](Self::with_queue).
+pub struct MinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+    Q: MinWindow<T> = MinimizerQueue<T, S>,
+    E: BaseEncoding = [u8; 256],
+> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: Q,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) base_order: BaseOrder,
+    pub(crate) top_shift: usize,
+    pub(crate) encoding: E,
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+    pub(crate) pending: bool,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding> Debug
+    for MinimizerIterator<'a, T, S, Q, E>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinimizerIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinWindow<T> + Clone, E: BaseEncoding + Clone> Clone
+    for MinimizerIterator<'a, T, S, Q, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width: self.width,
+            mmer: self.mmer,
+            mmer_mask: self.mmer_mask,
+            base_order: self.base_order,
+            top_shift: self.top_shift,
+            encoding: self.encoding.clone(),
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            pending: self.pending,
+            _marker: self._marker,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, E: BaseEncoding>
+    MinimizerIterator<'a, T, S, MinimizerQueue<T, S>, E>
+{
+    pub fn new(seq: &'a [u8], minimizer_size: usize, width: u16, hasher: S, encoding: E) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            base_order: BaseOrder::MostSignificantFirst,
+            top_shift: 2 * (minimizer_size - 1),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+            pending: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Rebuilds an iterator from a state saved with [`save_state`](Self::save_state).
+    /// The sequence and the parameters must be identical to those of the saved iterator,
+    /// since the window is recomputed from the sequence instead of being stored in the state.
+    pub fn resume(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: E,
+        state: IterState<T>,
+    ) -> Self
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let mut iter = Self::new(seq, minimizer_size, width, hasher, encoding);
+        iter.restore(state);
+        iter
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding>
+    MinimizerIterator<'a, T, S, Q, E>
+{
+    /// Creates an iterator selecting the minimizers with the given queue, which must be empty.
+    /// The width of the window is given by the queue.
+    pub fn with_queue(seq: &'a [u8], minimizer_size: usize, queue: Q, encoding: E) -> Self {
+        let width = check_queue(minimizer_size, &queue);
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            base_order: BaseOrder::MostSignificantFirst,
+            top_shift: 2 * (minimizer_size - 1),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+            pending: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Saves the rolling state of the iterator, so that it can be resumed later with [`resume`](MinimizerIterator::resume).
+    #[inline]
+    pub fn save_state(&self) -> IterState<T> {
+        IterState {
+            end: self.end,
+            min_pos: (!self.queue.is_empty()).then_some(self.min_pos),
+            pending: self.pending,
+        }
+    }
+}
+
+/// The rolling state of a [`MinimizerIterator`], used to resume iteration from a checkpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IterState<T> {
+    /// End of the current window in the sequence.
+    pub end: usize,
+    /// Last minimizer and its position, or `None` if iteration has not started.
+    pub min_pos: Option<(T, usize)>,
+    /// Whether the minimizer in `min_pos` was selected by [`skip_to`](MinimizerIterator::skip_to) and is left to be yielded.
+    pub pending: bool,
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding>
+    MinimizerIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+    /// Appends a base to the rolling m-mer, the first base being the least significant one if `LSF` is set.
+    /// The order is a constant so that the rolling loops do not branch on it for each base.
+    #[inline]
+    fn push<const LSF: bool>(&mut self, base: u8) {
+        let code: T = self.encoding.code(base).as_();
+        self.mmer = if LSF {
+            (self.mmer >> 2) | (code << self.top_shift)
+        } else {
+            ((self.mmer << 2) & self.mmer_mask) | code
+        };
+    }
+
+    /// Advances to the next minimizer and returns it by reference with its position, without copying it.
+    /// This is a lending counterpart of [`next`](Iterator::next), useful for wide minimizer types such as `u128`.
+    pub fn advance(&mut self) -> Option<(&T, usize)> {
+        match self.base_order {
+            BaseOrder::MostSignificantFirst => self.advance_in_order::<false>(),
+            BaseOrder::LeastSignificantFirst => self.advance_in_order::<true>(),
+        }
+    }
+
+    /// Returns up to 8 next minimizers and their positions at once with the number of valid lanes, or `None` if no minimizer remains.
+    /// The lanes beyond this number are unspecified.
+    /// Repeated calls yield the same minimizers as [`next`](Iterator::next), which is convenient to feed SIMD lanes.
+    pub fn next_batch(&mut self) -> Option<([T; 8], [usize; 8], usize)> {
+        let mut mins = [T::zero(); 8];
+        let mut positions = [0; 8];
+        let mut count = 0;
+        while count < 8 {
+            let Some((&min, pos)) = self.advance() else {
+                break;
+            };
+            mins[count] = min;
+            positions[count] = pos;
+            count += 1;
+        }
+        (count > 0).then_some((mins, positions, count))
+    }
+
+    /// Feeds the remaining minimizers and their positions to `sink`.
+    /// The sequence is rolled in a single loop which pushes each new minimizer to the sink as soon as it is selected,
+    /// instead of returning it from [`next`](Iterator::next).
+    pub fn feed<K: MinimizerSink<T>>(mut self, sink: &mut K) {
+        match self.base_order {
+            BaseOrder::MostSignificantFirst => self.feed_in_order::<false, K>(sink),
+            BaseOrder::LeastSignificantFirst => self.feed_in_order::<true, K>(sink),
+        }
+    }
+
+    /// Advances the iterator without yielding the minimizers before `offset`, so that it then yields the same minimizers
+    /// as a full scan of the sequence restricted to the positions `≥ offset`.
+    ///
+    /// The windows ending before `offset + minimizer_size - 1` cannot select a position `≥ offset`, so they are rolled without looking for their minimizer.
+    /// The next windows are then rolled until one of them selects a position `≥ offset`, whose minimizer is left to be yielded next.
+    /// Skipping to an offset that was already passed has no effect.
+    pub fn skip_to(&mut self, offset: usize) {
+        match self.base_order {
+            BaseOrder::MostSignificantFirst => self.skip_to_in_order::<false>(offset),
+            BaseOrder::LeastSignificantFirst => self.skip_to_in_order::<true>(offset),
+        }
+    }
+
+    /// Advances to the next minimizer, with the order of the bases fixed by `LSF`.
+    fn advance_in_order<const LSF: bool>(&mut self) -> Option<(&T, usize)> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.push::<LSF>(self.seq[i]);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.push::<LSF>(self.seq[i]);
+                self.queue.insert(self.mmer);
+            }
+            self.min_pos = self.queue.get_min_pos();
+        } else if self.pending {
+            self.pending = false;
+        } else {
+            loop {
+                if self.end >= self.seq.len() {
+                    return None;
+                }
+                self.push::<LSF>(self.seq[self.end]);
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                let (x, pos) = self.queue.get_min_pos();
+                let pos = self.end - self.base_width + pos;
+                if pos != self.min_pos.1 {
+                    self.min_pos = (x, pos);
+                    break;
+                }
+            }
+        }
+        Some((&self.min_pos.0, self.min_pos.1))
+    }
+
+    /// Feeds the remaining minimizers to `sink`, with the order of the bases fixed by `LSF`.
+    fn feed_in_order<const LSF: bool, K: MinimizerSink<T>>(&mut self, sink: &mut K) {
+        let Some((&min, pos)) = self.advance_in_order::<LSF>() else {
+            return;
+        };
+        sink.accept(min, pos);
+        for end in self.end..self.seq.len() {
+            self.push::<LSF>(self.seq[end]);
+            self.queue.insert(self.mmer);
+            let (x, pos) = self.queue.get_min_pos();
+            let pos = end + 1 - self.base_width + pos;
+            if pos != self.min_pos.1 {
+                self.min_pos = (x, pos);
+                sink.accept(x, pos);
+            }
+        }
+        self.end = self.seq.len();
+    }
+
+    /// Skips to `offset`, with the order of the bases fixed by `LSF`.
+    fn skip_to_in_order<const LSF: bool>(&mut self, offset: usize) {
+        if !self.queue.is_empty() && self.min_pos.1 >= offset {
+            return;
+        }
+        let target_end = min(
+            (offset + self.base_width - self.width).max(self.base_width),
+            self.seq.len(),
+        );
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return;
+            }
+            let start = target_end - self.base_width;
+            for i in start..(start + self.base_width - self.width) {
+                self.push::<LSF>(self.seq[i]);
+            }
+            for i in (start + self.base_width - self.width)..target_end {
+                self.push::<LSF>(self.seq[i]);
+                self.queue.insert(self.mmer);
+            }
+            self.end = target_end;
+        } else if target_end > self.end {
+            for i in self.end..target_end {
+                self.push::<LSF>(self.seq[i]);
+                self.queue.insert(self.mmer);
+            }
+            self.end = target_end;
+        }
+        let (x, pos) = self.queue.get_min_pos();
+        let mut min_pos = (x, self.end - self.base_width + pos);
+        while min_pos.1 < offset && self.end < self.seq.len() {
+            self.push::<LSF>(self.seq[self.end]);
+            self.queue.insert(self.mmer);
+            self.end += 1;
+            let (x, pos) = self.queue.get_min_pos();
+            min_pos = (x, self.end - self.base_width + pos);
+        }
+        // no minimizer `≥ offset` was yielded yet, so this one must be yielded by the next call to `advance`
+        self.pending = min_pos.1 >= offset;
+        self.min_pos = min_pos;
+    }
+
+    /// Restores a state saved with [`save_state`](Self::save_state) in a new iterator over the same sequence.
+    pub(crate) fn restore(&mut self, state: IterState<T>) {
+        match self.base_order {
+            BaseOrder::MostSignificantFirst => self.restore_in_order::<false>(state),
+            BaseOrder::LeastSignificantFirst => self.restore_in_order::<true>(state),
+        }
+    }
+
+    /// Restores a saved state, with the order of the bases fixed by `LSF`.
+    fn restore_in_order<const LSF: bool>(&mut self, state: IterState<T>) {
+        if let Some(min_pos) = state.min_pos {
+            assert!(
+                self.base_width <= state.end && state.end <= self.seq.len(),
+                "the state does not match the sequence"
+            );
+            let (prefix, window) = self.seq[(state.end - self.base_width)..state.end]
+                .split_at(self.base_width - self.width);
+            for &base in prefix {
+                self.push::<LSF>(base);
+            }
+            for &base in window {
+                self.push::<LSF>(base);
+                self.queue.insert(self.mmer);
+            }
+            self.end = state.end;
+            self.min_pos = min_pos;
+            self.pending = state.pending;
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding> Iterator
+    for MinimizerIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance().map(|(&x, pos)| (x, pos))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding>
+    FusedIterator for MinimizerIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the minimizers of a sequence and their positions with a boolean indicating a tie,
+/// i.e. whether the window selecting the minimizer contained several m-mers with the minimal hash.
+pub struct MinimizerTieIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+    pub(crate) inner: MinimizerIterator<'a, T, S>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> MinimizerTieIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            inner: MinimizerIterator::new(seq, minimizer_size, width, hasher, encoding),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for MinimizerTieIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, bool);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        Some((min, pos, self.inner.queue.multiple_mins()))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for MinimizerTieIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the minimizers of both strands of a sequence, yielding the minimizer of the forward strand and its position
+/// followed by the minimizer of the reverse strand and its position, each time one of them changes.
+/// Positions are given in forward coordinates, so that the minimizers of the reverse strand match those of the reverse complement of the sequence.
+pub struct BothStrandsMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
     pub(crate) seq: &'a [u8],
     pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) rc_queue: MinimizerQueue<T, S>,
     pub(crate) width: usize,
     pub(crate) mmer: T,
+    pub(crate) rc_mmer: T,
     pub(crate) mmer_mask: T,
+    pub(crate) rc_mmer_shift: usize,
     pub(crate) encoding: [u8; 256],
+    pub(crate) rc_encoding: [u8; 256],
     pub(crate) base_width: usize,
-    pub(crate) min_pos: (T, usize),
+    pub(crate) min_pos: (T, usize, T, usize),
     pub(crate) end: usize,
 }
 
-impl<'a, T: PrimInt + Hash, S: BuildHasher> MinimizerIterator<'a, T, S> {
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher> Debug
+    for BothStrandsMinimizerIterator<'a, T, S>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BothStrandsMinimizerIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("rc_mmer", &self.rc_mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher + Clone> BothStrandsMinimizerIterator<'a, T, S> {
     pub fn new(
         seq: &'a [u8],
         minimizer_size: usize,
         width: u16,
         hasher: S,
         encoding: [u8; 256],
+        complement: [u8; 256],
     ) -> Self {
-        let queue = MinimizerQueue::with_hasher(width, hasher);
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher.clone());
+        let rc_queue = MinimizerQueue::with_hasher(width, hasher);
         let width = width as usize;
+        let rc_encoding = rc_encoding(&encoding, &complement);
         Self {
             seq,
             queue,
+            rc_queue,
             width,
             mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
+            rc_mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            rc_mmer_shift: 2 * (minimizer_size - 1),
             encoding,
+            rc_encoding,
             base_width: width + minimizer_size - 1,
             end: width + minimizer_size - 1,
-            min_pos: (T::zero(), 0),
+            min_pos: (T::zero(), 0, T::zero(), 0),
         }
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for MinimizerIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash, S: BuildHasher> BothStrandsMinimizerIterator<'a, T, S> {
+    /// Returns the minimizer of the reverse strand, breaking ties in favor of the rightmost position
+    /// since the reverse strand is read from right to left.
+    #[inline]
+    fn rc_min_pos(&self) -> (T, usize) {
+        if self.rc_queue.multiple_mins() {
+            let (x, pos, tie) = self.rc_queue.get_inner_min_pos();
+            tie.unwrap_or((x, pos))
+        } else {
+            self.rc_queue.get_min_pos()
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for BothStrandsMinimizerIterator<'a, T, S>
 where
     u8: AsPrimitive<T>,
 {
-    type Item = (T, usize);
+    type Item = (T, usize, T, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.queue.is_empty() {
@@ -131,24 +762,43 @@ where
             for i in 0..(self.base_width - self.width) {
                 self.mmer = (self.mmer << 2)
                     | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.rc_mmer = (self.rc_mmer >> 2)
+                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[i] as usize) }.as_()
+                        << self.rc_mmer_shift);
             }
             for i in (self.base_width - self.width)..self.base_width {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
                     | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.rc_mmer = (self.rc_mmer >> 2)
+                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[i] as usize) }.as_()
+                        << self.rc_mmer_shift);
                 self.queue.insert(self.mmer);
+                self.rc_queue.insert(self.rc_mmer);
             }
-            self.min_pos = self.queue.get_min_pos();
+            let (x, pos) = self.queue.get_min_pos();
+            let (rc_x, rc_pos) = self.rc_min_pos();
+            self.min_pos = (x, pos, rc_x, rc_pos);
         } else {
             let mut min_pos = self.min_pos;
-            while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+            while self.end < self.seq.len()
+                && min_pos.1 == self.min_pos.1
+                && min_pos.3 == self.min_pos.3
+            {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
                     | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
+                self.rc_mmer = (self.rc_mmer >> 2)
+                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[self.end] as usize) }
+                        .as_()
+                        << self.rc_mmer_shift);
                 self.queue.insert(self.mmer);
+                self.rc_queue.insert(self.rc_mmer);
                 self.end += 1;
-                let _min_pos = self.queue.get_min_pos();
-                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+                let (x, pos) = self.queue.get_min_pos();
+                let (rc_x, rc_pos) = self.rc_min_pos();
+                let start = self.end - self.base_width;
+                min_pos = (x, start + pos, rc_x, start + rc_pos);
             }
-            if min_pos.1 == self.min_pos.1 {
+            if min_pos.1 == self.min_pos.1 && min_pos.3 == self.min_pos.3 {
                 return None;
             }
             self.min_pos = min_pos;
@@ -157,26 +807,87 @@ where
     }
 }
 
-/// An iterator over the positions of the canonical minimizers of a sequence with a boolean indicating a reverse complement.
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for BothStrandsMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the positions of the canonical minimizers of a sequence with their [`Strand`].
 /// It requires an odd width to break ties between multiple minimizers.
+///
+/// The minimizers are selected by an [`ImplicitMinimizerQueue`] by default,
+/// or by any queue implementing [`MinPosWindow`], see [`with_queue`](Self::with_queue).
 pub struct CanonicalMinimizerPosIterator<
     'a,
     T: PrimInt + Hash = u64,
     S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
+    E: BaseEncoding = [u8; 256],
 > {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: ImplicitMinimizerQueue<S>,
+    pub(crate) queue: Q,
     pub(crate) width: usize,
     pub(crate) mmer: T,
     pub(crate) rc_mmer: T,
     pub(crate) mmer_mask: T,
     pub(crate) rc_mmer_shift: usize,
     pub(crate) is_rc: VecDeque<bool>,
-    pub(crate) encoding: [u8; 256],
-    pub(crate) rc_encoding: [u8; 256],
+    pub(crate) encoding: E,
+    pub(crate) rc_encoding: E,
     pub(crate) base_width: usize,
-    pub(crate) min_pos: (usize, bool),
+    pub(crate) min_pos: (usize, Strand),
     pub(crate) end: usize,
+    pub(crate) tie_breaks: usize,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding> Debug
+    for CanonicalMinimizerPosIterator<'a, T, S, Q, E>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalMinimizerPosIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("rc_mmer", &self.rc_mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("tie_breaks", &self.tie_breaks)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<
+        'a,
+        T: PrimInt + Hash,
+        S: BuildHasher,
+        Q: MinPosWindow<T> + Clone,
+        E: BaseEncoding + Clone,
+    > Clone for CanonicalMinimizerPosIterator<'a, T, S, Q, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width: self.width,
+            mmer: self.mmer,
+            rc_mmer: self.rc_mmer,
+            mmer_mask: self.mmer_mask,
+            rc_mmer_shift: self.rc_mmer_shift,
+            is_rc: self.is_rc.clone(),
+            encoding: self.encoding.clone(),
+            rc_encoding: self.rc_encoding.clone(),
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            tie_breaks: self.tie_breaks,
+            _marker: self._marker,
+        }
+    }
 }
 
 impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalMinimizerPosIterator<'a, T, S> {
@@ -186,48 +897,116 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalMinimizerPosIterator<'a, T,
         width: u16,
         hasher: S,
         encoding: [u8; 256],
+        complement: [u8; 256],
     ) -> Self {
-        let queue = ImplicitMinimizerQueue::with_hasher(width, hasher);
-        let width = width as usize;
         assert_eq!(
             width % 2,
             1,
             "width must be odd to break ties between multiple minimizers"
         );
-        let mut rc_encoding = encoding;
-        rc_encoding.swap(b'A' as usize, b'T' as usize);
-        rc_encoding.swap(b'a' as usize, b't' as usize);
-        rc_encoding.swap(b'C' as usize, b'G' as usize);
-        rc_encoding.swap(b'c' as usize, b'g' as usize);
+        Self::new_even_width(seq, minimizer_size, width, hasher, encoding, complement)
+    }
+
+    /// Same as [`new`](Self::new), but also accepts even widths.
+    /// In a window of even width, ties are broken with the orientation of the central (minimizer_size + 1)-mer,
+    /// which is reversed when the window is reverse complemented, so that the selection stays strand-symmetric.
+    /// The only exception is a central (minimizer_size + 1)-mer that is its own reverse complement, e.g. `GGCC`,
+    /// which requires an odd minimizer size: its orientation is undefined, so ties keep the leftmost minimizer on both strands.
+    pub fn new_even_width(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        let rc_encoding = rc_encoding(&encoding, &complement);
+        Self::with_rc_encoding(seq, minimizer_size, width, hasher, encoding, rc_encoding)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, E: BaseEncoding>
+    CanonicalMinimizerPosIterator<'a, T, S, ImplicitMinimizerQueue<S>, E>
+{
+    /// Same as [`new_even_width`](CanonicalMinimizerPosIterator::new_even_width), but takes the code of the complement of each byte,
+    /// i.e. `encoding[complement[b]]`, instead of the complement table, so that both tables can be borrowed.
+    pub fn with_rc_encoding(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: E,
+        rc_encoding: E,
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = ImplicitMinimizerQueue::with_hasher(width, hasher);
+        Self::with_queue(seq, minimizer_size, queue, encoding, rc_encoding)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding>
+    CanonicalMinimizerPosIterator<'a, T, S, Q, E>
+{
+    /// Same as [`with_rc_encoding`](CanonicalMinimizerPosIterator::with_rc_encoding), but selects the minimizers with the given queue,
+    /// which must be empty. The width of the window is given by the queue.
+    /// The queue must report its ties with [`inner_min_pos`](MinPosWindow::inner_min_pos) to keep the selection strand-symmetric.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        queue: Q,
+        encoding: E,
+        rc_encoding: E,
+    ) -> Self {
+        let width = check_queue(minimizer_size, &queue);
         Self {
             seq,
             queue,
             width,
             mmer: T::zero(),
             rc_mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
+            mmer_mask: mask(minimizer_size),
             rc_mmer_shift: 2 * (minimizer_size - 1),
             is_rc: VecDeque::with_capacity(width),
             encoding,
             rc_encoding,
             base_width: width + minimizer_size - 1,
             end: width + minimizer_size - 1,
-            min_pos: (0, false),
+            min_pos: (0, Strand::Forward),
+            tie_breaks: 0,
+            _marker: PhantomData,
         }
     }
 
+    /// Returns the number of windows so far in which a tie between multiple minimizers
+    /// was broken by selecting the alternate (rightmost) minimizer, because the window was not canonical.
+    #[inline]
+    pub fn tie_break_count(&self) -> usize {
+        self.tie_breaks
+    }
+
     #[inline]
     fn window_not_canonical(&self) -> bool {
-        self.is_rc[self.width / 2]
+        if self.width % 2 == 1 {
+            self.is_rc[self.width / 2]
+        } else {
+            center_not_canonical(
+                self.seq,
+                self.end - self.base_width + self.width / 2 - 1,
+                self.base_width - self.width + 1,
+                &self.encoding,
+                &self.rc_encoding,
+            )
+        }
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
-    for CanonicalMinimizerPosIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding> Iterator
+    for CanonicalMinimizerPosIterator<'a, T, S, Q, E>
 where
     u8: AsPrimitive<T>,
 {
-    type Item = (usize, bool);
+    type Item = (usize, Strand);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.queue.is_empty() {
@@ -235,62 +1014,60 @@ where
                 return None;
             }
             for i in 0..(self.base_width - self.width) {
-                self.mmer = (self.mmer << 2)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.mmer = (self.mmer << 2) | self.encoding.code(self.seq[i]).as_();
                 self.rc_mmer = (self.rc_mmer >> 2)
-                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[i] as usize) }.as_()
-                        << self.rc_mmer_shift);
+                    | (self.rc_encoding.code(self.seq[i]).as_() << self.rc_mmer_shift);
             }
             for i in (self.base_width - self.width)..self.base_width {
-                self.mmer = ((self.mmer << 2) & self.mmer_mask)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.mmer =
+                    ((self.mmer << 2) & self.mmer_mask) | self.encoding.code(self.seq[i]).as_();
                 self.rc_mmer = (self.rc_mmer >> 2)
-                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[i] as usize) }.as_()
-                        << self.rc_mmer_shift);
+                    | (self.rc_encoding.code(self.seq[i]).as_() << self.rc_mmer_shift);
                 let canonical_mmer = min(self.mmer, self.rc_mmer);
-                self.queue.insert(&canonical_mmer);
+                self.queue.insert(canonical_mmer);
                 self.is_rc.push_back(canonical_mmer == self.rc_mmer);
             }
             let pos = if self.queue.multiple_mins() {
-                let (pos, tie) = self.queue.get_inner_min_pos();
-                tie.map_or(pos, |alt| {
-                    if self.window_not_canonical() {
+                let (pos, tie) = self.queue.inner_min_pos();
+                match tie {
+                    Some(alt) if self.window_not_canonical() => {
+                        self.tie_breaks += 1;
                         alt
-                    } else {
-                        pos
                     }
-                })
+                    _ => pos,
+                }
             } else {
-                self.queue.get_min_pos()
+                self.queue.min_pos()
             };
-            self.min_pos = (pos, self.is_rc[pos])
+            self.min_pos = (pos, Strand::from(self.is_rc[pos]))
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos == self.min_pos {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
+                    | self.encoding.code(self.seq[self.end]).as_();
                 self.rc_mmer = (self.rc_mmer >> 2)
-                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[self.end] as usize) }
-                        .as_()
-                        << self.rc_mmer_shift);
+                    | (self.rc_encoding.code(self.seq[self.end]).as_() << self.rc_mmer_shift);
                 let canonical_mmer = min(self.mmer, self.rc_mmer);
-                self.queue.insert(&canonical_mmer);
+                self.queue.insert(canonical_mmer);
                 self.is_rc.pop_front();
                 self.is_rc.push_back(canonical_mmer == self.rc_mmer);
                 self.end += 1;
                 let pos = if self.queue.multiple_mins() {
-                    let (pos, tie) = self.queue.get_inner_min_pos();
-                    tie.map_or(pos, |alt| {
-                        if self.window_not_canonical() {
+                    let (pos, tie) = self.queue.inner_min_pos();
+                    match tie {
+                        Some(alt) if self.window_not_canonical() => {
+                            self.tie_breaks += 1;
                             alt
-                        } else {
-                            pos
                         }
-                    })
+                        _ => pos,
+                    }
                 } else {
-                    self.queue.get_min_pos()
+                    self.queue.min_pos()
                 };
-                min_pos = (self.end - self.base_width + pos, self.is_rc[pos]);
+                min_pos = (
+                    self.end - self.base_width + pos,
+                    Strand::from(self.is_rc[pos]),
+                );
             }
             if min_pos == self.min_pos {
                 return None;
@@ -301,26 +1078,82 @@ where
     }
 }
 
-/// An iterator over the canonical minimizers of a sequence and their positions with a boolean indicating a reverse complement.
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>, E: BaseEncoding>
+    FusedIterator for CanonicalMinimizerPosIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the canonical minimizers of a sequence and their positions with their [`Strand`].
 /// It requires an odd width to break ties between multiple minimizers.
+///
+/// The minimizers are selected by a [`MinimizerQueue`] by default,
+/// or by any queue implementing [`MinWindow`], see [`with_queue`](Self::with_queue).
 pub struct CanonicalMinimizerIterator<
     'a,
     T: PrimInt + Hash = u64,
     S: BuildHasher = DefaultHashBuilder,
+    Q: MinWindow<T> = MinimizerQueue<T, S>,
+    E: BaseEncoding = [u8; 256],
 > {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) queue: Q,
     pub(crate) width: usize,
     pub(crate) mmer: T,
     pub(crate) rc_mmer: T,
     pub(crate) mmer_mask: T,
     pub(crate) rc_mmer_shift: usize,
     pub(crate) is_rc: VecDeque<bool>,
-    pub(crate) encoding: [u8; 256],
-    pub(crate) rc_encoding: [u8; 256],
+    pub(crate) encoding: E,
+    pub(crate) rc_encoding: E,
     pub(crate) base_width: usize,
-    pub(crate) min_pos: (T, usize, bool),
+    pub(crate) min_pos: (T, usize, Strand),
     pub(crate) end: usize,
+    pub(crate) tie_breaks: usize,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding> Debug
+    for CanonicalMinimizerIterator<'a, T, S, Q, E>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalMinimizerIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("rc_mmer", &self.rc_mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("tie_breaks", &self.tie_breaks)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinWindow<T> + Clone, E: BaseEncoding + Clone> Clone
+    for CanonicalMinimizerIterator<'a, T, S, Q, E>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width: self.width,
+            mmer: self.mmer,
+            rc_mmer: self.rc_mmer,
+            mmer_mask: self.mmer_mask,
+            rc_mmer_shift: self.rc_mmer_shift,
+            is_rc: self.is_rc.clone(),
+            encoding: self.encoding.clone(),
+            rc_encoding: self.rc_encoding.clone(),
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            tie_breaks: self.tie_breaks,
+            _marker: self._marker,
+        }
+    }
 }
 
 impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalMinimizerIterator<'a, T, S> {
@@ -330,48 +1163,180 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalMinimizerIterator<'a, T, S>
         width: u16,
         hasher: S,
         encoding: [u8; 256],
+        complement: [u8; 256],
     ) -> Self {
-        let queue = MinimizerQueue::with_hasher(width, hasher);
-        let width = width as usize;
         assert_eq!(
             width % 2,
             1,
             "width must be odd to break ties between multiple minimizers"
         );
-        let mut rc_encoding = encoding;
-        rc_encoding.swap(b'A' as usize, b'T' as usize);
-        rc_encoding.swap(b'a' as usize, b't' as usize);
-        rc_encoding.swap(b'C' as usize, b'G' as usize);
-        rc_encoding.swap(b'c' as usize, b'g' as usize);
+        Self::new_even_width(seq, minimizer_size, width, hasher, encoding, complement)
+    }
+
+    /// Same as [`new`](Self::new), but also accepts even widths.
+    /// In a window of even width, ties are broken with the orientation of the central (minimizer_size + 1)-mer,
+    /// which is reversed when the window is reverse complemented, so that the selection stays strand-symmetric.
+    /// The only exception is a central (minimizer_size + 1)-mer that is its own reverse complement, e.g. `GGCC`,
+    /// which requires an odd minimizer size: its orientation is undefined, so ties keep the leftmost minimizer on both strands.
+    pub fn new_even_width(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        let rc_encoding = rc_encoding(&encoding, &complement);
+        Self::with_rc_encoding(seq, minimizer_size, width, hasher, encoding, rc_encoding)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, E: BaseEncoding>
+    CanonicalMinimizerIterator<'a, T, S, MinimizerQueue<T, S>, E>
+{
+    /// Same as [`new_even_width`](CanonicalMinimizerIterator::new_even_width), but takes the code of the complement of each byte,
+    /// i.e. `encoding[complement[b]]`, instead of the complement table, so that both tables can be borrowed.
+    pub fn with_rc_encoding(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: E,
+        rc_encoding: E,
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        Self::with_queue(seq, minimizer_size, queue, encoding, rc_encoding)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding>
+    CanonicalMinimizerIterator<'a, T, S, Q, E>
+{
+    /// Same as [`with_rc_encoding`](CanonicalMinimizerIterator::with_rc_encoding), but selects the minimizers with the given queue,
+    /// which must be empty. The width of the window is given by the queue.
+    /// The queue must report its ties with [`get_inner_min_pos`](MinWindow::get_inner_min_pos) to keep the selection strand-symmetric.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        queue: Q,
+        encoding: E,
+        rc_encoding: E,
+    ) -> Self {
+        let width = check_queue(minimizer_size, &queue);
         Self {
             seq,
             queue,
             width,
             mmer: T::zero(),
             rc_mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
+            mmer_mask: mask(minimizer_size),
             rc_mmer_shift: 2 * (minimizer_size - 1),
             is_rc: VecDeque::with_capacity(width),
             encoding,
             rc_encoding,
             base_width: width + minimizer_size - 1,
             end: width + minimizer_size - 1,
-            min_pos: (T::zero(), 0, false),
+            min_pos: (T::zero(), 0, Strand::Forward),
+            tie_breaks: 0,
+            _marker: PhantomData,
         }
     }
 
+    /// Returns the number of windows so far in which a tie between multiple minimizers
+    /// was broken by selecting the alternate (rightmost) minimizer, because the window was not canonical.
+    #[inline]
+    pub fn tie_break_count(&self) -> usize {
+        self.tie_breaks
+    }
+
     #[inline]
     fn window_not_canonical(&self) -> bool {
-        self.is_rc[self.width / 2]
+        if self.width % 2 == 1 {
+            self.is_rc[self.width / 2]
+        } else {
+            center_not_canonical(
+                self.seq,
+                self.end - self.base_width + self.width / 2 - 1,
+                self.base_width - self.width + 1,
+                &self.encoding,
+                &self.rc_encoding,
+            )
+        }
+    }
+
+    /// Explains how the minimizer of the current window, i.e. the last window rolled by the iterator, was selected,
+    /// or returns `None` if iteration has not started.
+    ///
+    /// When several m-mers of the window share the minimal hash, the leftmost one is selected if the window is canonical,
+    /// and the rightmost one otherwise, so that the selection is the same on the reverse complement of the window.
+    /// The window is canonical if the m-mer at its center is canonical, or for even widths,
+    /// if the central (minimizer_size + 1)-mer is not greater than its reverse complement.
+    /// After a call to [`next`](Iterator::next) returning a minimizer, the chosen minimizer is the one that was returned.
+    pub fn explain_selection(&self) -> Option<SelectionInfo<T>> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        let window_start = self.end - self.base_width;
+        let (x, pos, tie) = if self.queue.multiple_mins() {
+            self.queue.get_inner_min_pos()
+        } else {
+            let (x, pos) = self.queue.get_min_pos();
+            (x, pos, None)
+        };
+        let (reason, (chosen, chosen_pos)) = match tie {
+            None => (SelectionReason::Unique, (x, pos)),
+            Some(alt) if self.window_not_canonical() => (SelectionReason::TieNotCanonical, alt),
+            Some(_) => (SelectionReason::TieCanonical, (x, pos)),
+        };
+        Some(SelectionInfo {
+            window_start,
+            leftmost: window_start + pos,
+            rightmost: tie.map(|(_, alt_pos)| window_start + alt_pos),
+            chosen: (
+                chosen,
+                window_start + chosen_pos,
+                Strand::from(self.is_rc[chosen_pos]),
+            ),
+            reason,
+        })
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
-    for CanonicalMinimizerIterator<'a, T, S>
+/// The reason why a canonical minimizer was selected in its window, see [`SelectionInfo`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SelectionReason {
+    /// A single m-mer of the window has the minimal hash.
+    Unique,
+    /// Several m-mers share the minimal hash and the window is canonical, so the leftmost one was selected.
+    TieCanonical,
+    /// Several m-mers share the minimal hash and the window is not canonical, so the rightmost one was selected.
+    TieNotCanonical,
+}
+
+/// The candidates considered when selecting the canonical minimizer of a window, as returned by
+/// [`CanonicalMinimizerIterator::explain_selection`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SelectionInfo<T> {
+    /// Position of the first base of the window in the sequence.
+    pub window_start: usize,
+    /// Position of the leftmost m-mer with the minimal hash.
+    pub leftmost: usize,
+    /// Position of the rightmost m-mer with the minimal hash, or `None` if it is unique.
+    pub rightmost: Option<usize>,
+    /// The selected canonical minimizer, its position and its strand.
+    pub chosen: (T, usize, Strand),
+    /// Why this minimizer was selected.
+    pub reason: SelectionReason,
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding> Iterator
+    for CanonicalMinimizerIterator<'a, T, S, Q, E>
 where
     u8: AsPrimitive<T>,
 {
-    type Item = (T, usize, bool);
+    type Item = (T, usize, Strand);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.queue.is_empty() {
@@ -379,44 +1344,39 @@ where
                 return None;
             }
             for i in 0..(self.base_width - self.width) {
-                self.mmer = (self.mmer << 2)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.mmer = (self.mmer << 2) | self.encoding.code(self.seq[i]).as_();
                 self.rc_mmer = (self.rc_mmer >> 2)
-                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[i] as usize) }.as_()
-                        << self.rc_mmer_shift);
+                    | (self.rc_encoding.code(self.seq[i]).as_() << self.rc_mmer_shift);
             }
             for i in (self.base_width - self.width)..self.base_width {
-                self.mmer = ((self.mmer << 2) & self.mmer_mask)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.mmer =
+                    ((self.mmer << 2) & self.mmer_mask) | self.encoding.code(self.seq[i]).as_();
                 self.rc_mmer = (self.rc_mmer >> 2)
-                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[i] as usize) }.as_()
-                        << self.rc_mmer_shift);
+                    | (self.rc_encoding.code(self.seq[i]).as_() << self.rc_mmer_shift);
                 let canonical_mmer = min(self.mmer, self.rc_mmer);
                 self.queue.insert(canonical_mmer);
                 self.is_rc.push_back(canonical_mmer == self.rc_mmer);
             }
             let _min_pos = if self.queue.multiple_mins() {
                 let (x, pos, tie) = self.queue.get_inner_min_pos();
-                tie.map_or((x, pos), |alt| {
-                    if self.window_not_canonical() {
+                match tie {
+                    Some(alt) if self.window_not_canonical() => {
+                        self.tie_breaks += 1;
                         alt
-                    } else {
-                        (x, pos)
                     }
-                })
+                    _ => (x, pos),
+                }
             } else {
                 self.queue.get_min_pos()
             };
-            self.min_pos = (_min_pos.0, _min_pos.1, self.is_rc[_min_pos.1]);
+            self.min_pos = (_min_pos.0, _min_pos.1, Strand::from(self.is_rc[_min_pos.1]));
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
-                    | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
+                    | self.encoding.code(self.seq[self.end]).as_();
                 self.rc_mmer = (self.rc_mmer >> 2)
-                    | (unsafe { self.rc_encoding.get_unchecked(self.seq[self.end] as usize) }
-                        .as_()
-                        << self.rc_mmer_shift);
+                    | (self.rc_encoding.code(self.seq[self.end]).as_() << self.rc_mmer_shift);
                 let canonical_mmer = min(self.mmer, self.rc_mmer);
                 self.queue.insert(canonical_mmer);
                 self.is_rc.pop_front();
@@ -424,20 +1384,20 @@ where
                 self.end += 1;
                 let _min_pos = if self.queue.multiple_mins() {
                     let (x, pos, tie) = self.queue.get_inner_min_pos();
-                    tie.map_or((x, pos), |alt| {
-                        if self.window_not_canonical() {
+                    match tie {
+                        Some(alt) if self.window_not_canonical() => {
+                            self.tie_breaks += 1;
                             alt
-                        } else {
-                            (x, pos)
                         }
-                    })
+                        _ => (x, pos),
+                    }
                 } else {
                     self.queue.get_min_pos()
                 };
                 min_pos = (
                     _min_pos.0,
                     self.end - self.base_width + _min_pos.1,
-                    self.is_rc[_min_pos.1],
+                    Strand::from(self.is_rc[_min_pos.1]),
                 );
             }
             if min_pos.1 == self.min_pos.1 {
@@ -448,3 +1408,55 @@ where
         Some(self.min_pos)
     }
 }
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinWindow<T>, E: BaseEncoding>
+    FusedIterator for CanonicalMinimizerIterator<'a, T, S, Q, E>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the minimizers of an ASCII sequence of A, C, G and T and their positions, encoding the bases without a lookup table,
+/// see [`AsciiEncoding`](crate::encoding::AsciiEncoding).
+pub type AsciiMinimizerIterator<'a, T = u64, S = DefaultHashBuilder> =
+    MinimizerIterator<'a, T, S, MinimizerQueue<T, S>, crate::encoding::AsciiEncoding>;
+
+/// A [`MinimizerIterator`] borrowing the hasher and the encoding of a builder, see [`MinimizerBuilder::iter_ref`](crate::MinimizerBuilder::iter_ref).
+pub type MinimizerRefIterator<'a, T = u64, S = DefaultHashBuilder> = MinimizerIterator<
+    'a,
+    T,
+    BuildHasherRef<'a, S>,
+    MinimizerQueue<T, BuildHasherRef<'a, S>>,
+    &'a [u8; 256],
+>;
+
+/// A [`MinimizerPosIterator`] borrowing the hasher and the encoding of a builder, see [`MinimizerBuilder::iter_pos_ref`](crate::MinimizerBuilder::iter_pos_ref).
+pub type MinimizerPosRefIterator<'a, T = u64, S = DefaultHashBuilder> = MinimizerPosIterator<
+    'a,
+    T,
+    BuildHasherRef<'a, S>,
+    ImplicitMinimizerQueue<BuildHasherRef<'a, S>>,
+    &'a [u8; 256],
+>;
+
+/// A [`CanonicalMinimizerIterator`] borrowing the hasher and the encoding tables of a builder,
+/// see [`MinimizerBuilder::iter_ref`](crate::MinimizerBuilder::iter_ref).
+pub type CanonicalMinimizerRefIterator<'a, T = u64, S = DefaultHashBuilder> =
+    CanonicalMinimizerIterator<
+        'a,
+        T,
+        BuildHasherRef<'a, S>,
+        MinimizerQueue<T, BuildHasherRef<'a, S>>,
+        &'a [u8; 256],
+    >;
+
+/// A [`CanonicalMinimizerPosIterator`] borrowing the hasher and the encoding tables of a builder,
+/// see [`MinimizerBuilder::iter_pos_ref`](crate::MinimizerBuilder::iter_pos_ref).
+pub type CanonicalMinimizerPosRefIterator<'a, T = u64, S = DefaultHashBuilder> =
+    CanonicalMinimizerPosIterator<
+        'a,
+        T,
+        BuildHasherRef<'a, S>,
+        ImplicitMinimizerQueue<BuildHasherRef<'a, S>>,
+        &'a [u8; 256],
+    >;
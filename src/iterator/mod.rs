@@ -1,7 +1,281 @@
 //! Iterators over minimizers.
+//!
+//! Positions within an in-memory sequence are bounded by its length, which never exceeds `isize::MAX`,
+//! so they cannot overflow. Iterators adding a user-provided offset to these positions check that the shifted positions fit in `usize`.
 
+mod bases;
+mod binary;
+mod circular;
+mod fingerprint;
+mod from_end;
+mod gc;
+mod joined;
+mod kmers;
+mod lex;
+mod local_minima;
+mod min_distance;
+pub(crate) mod minimap2;
 mod minimizer;
 mod mod_sampling;
+mod nthash;
+mod ownership;
+mod packed;
+mod paired;
+mod palindrome;
+mod penalized;
+mod positions;
+mod quality;
+mod range;
+mod raw;
+mod retain;
+mod running_count;
+mod secondary;
+mod six_frame;
+mod spliced;
+mod stranded;
+#[cfg(feature = "async")]
+mod stream;
+mod suppress;
+mod transitions;
+mod windows;
 
+pub use bases::BasesMinimizerIterator;
+pub use binary::*;
+pub use circular::*;
+pub use fingerprint::*;
+pub use from_end::*;
+pub use gc::*;
+pub use joined::*;
+pub use kmers::*;
+pub use lex::*;
+pub use local_minima::*;
+pub use min_distance::*;
+pub use minimap2::Minimap2Iterator;
 pub use minimizer::*;
 pub use mod_sampling::*;
+pub use nthash::{
+    nthash, nthash_forward, CanonicalNtHashMinimizerIterator, NtHashMinimizerIterator,
+};
+pub use ownership::*;
+pub use packed::*;
+pub use paired::*;
+pub use palindrome::*;
+pub use penalized::*;
+pub use positions::*;
+pub use quality::*;
+pub use range::*;
+pub use raw::*;
+pub use retain::*;
+pub use running_count::*;
+pub use secondary::*;
+pub use six_frame::*;
+pub use spliced::*;
+pub use stranded::StrandedIterator;
+#[cfg(feature = "async")]
+pub use stream::MinimizerStream;
+pub use suppress::{CanonicalSuppressIterator, SuppressIterator};
+pub use transitions::*;
+pub use windows::*;
+
+use crate::encoding::BaseEncoding;
+use crate::queue::MinPosWindow;
+use core::hash::{BuildHasher, Hash};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// The order in which the bases of an m-mer are packed into an integer.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum BaseOrder {
+    /// The first base is stored in the most significant bits, e.g. `ACG` is packed as `0b000110`. This is the default.
+    #[default]
+    MostSignificantFirst,
+    /// The first base is stored in the least significant bits, e.g. `ACG` is packed as `0b100100`.
+    LeastSignificantFirst,
+}
+
+/// Checks that the minimizer size and the width are positive.
+#[inline]
+pub(crate) fn check_sizes(minimizer_size: usize, width: u16) {
+    assert!(minimizer_size > 0, "minimizer_size must be ≥ 1");
+    assert!(width > 0, "width must be ≥ 1");
+}
+
+/// Checks that the minimizer size and the width of a user-provided queue are positive,
+/// that the width fits in a `u16` and that the queue is empty, and returns the width.
+#[inline]
+pub(crate) fn check_queue<T, Q: MinPosWindow<T>>(minimizer_size: usize, queue: &Q) -> usize {
+    let width = queue.width();
+    assert!(
+        width <= u16::MAX as usize,
+        "the width of the queue must be ≤ {}, got {width}",
+        u16::MAX
+    );
+    check_sizes(minimizer_size, width as u16);
+    assert!(queue.is_empty(), "the queue must be empty");
+    width
+}
+
+/// Checks in debug builds that the hasher takes the bits of the m-mers beyond the 64 lowest ones into account,
+/// given the number of bits of an m-mer, since a hasher truncating the m-mers would silently select wrong minimizers.
+#[inline]
+pub(crate) fn check_hasher<T: PrimInt + Hash, S: BuildHasher>(mmer_bits: usize, hasher: &S) {
+    debug_assert!(
+        mmer_bits <= 64 || hasher.hash_one(T::one() << 64) != hasher.hash_one(T::zero()),
+        "the hasher ignores the bits of the m-mers beyond the 64 lowest ones"
+    );
+}
+
+/// Returns the number of t-mers in a window of mod-minimizers, i.e. `width + minimizer_size - t`, checking that it fits in a `u16`.
+#[inline]
+pub(crate) fn width_t(minimizer_size: usize, width: u16, t: usize) -> u16 {
+    assert!(
+        t <= minimizer_size,
+        "t must be ≤ minimizer_size={minimizer_size}"
+    );
+    let width_t = width as usize + minimizer_size - t;
+    assert!(
+        width_t <= u16::MAX as usize,
+        "width + minimizer_size - t must be ≤ {}, got {width_t}",
+        u16::MAX
+    );
+    width_t as u16
+}
+
+/// Returns the mask of the `2 * size` lowest bits, checking that they fit in `T`.
+#[inline]
+pub(crate) fn mask<T: PrimInt>(size: usize) -> T {
+    let bits = T::zero().count_zeros() as usize;
+    assert!(
+        2 * size <= bits,
+        "With this integer type, minimizer_size must be ≤ {}, got {size}. Please select a smaller size or a larger type.",
+        bits / 2
+    );
+    if 2 * size == bits {
+        T::max_value()
+    } else {
+        (T::one() << (2 * size)) - T::one()
+    }
+}
+
+/// The complement of each byte, following the IUPAC nucleotide codes in both cases.
+/// Bytes that are not nucleotide codes, as well as self-complementary codes (S, W, N), are left unchanged.
+pub const COMPLEMENT: [u8; 256] = {
+    let pairs = [
+        (b'A', b'T'),
+        (b'C', b'G'),
+        (b'R', b'Y'),
+        (b'K', b'M'),
+        (b'B', b'V'),
+        (b'D', b'H'),
+    ];
+    let mut complement = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        complement[i] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < pairs.len() {
+        let (a, b) = pairs[i];
+        complement[a as usize] = b;
+        complement[b as usize] = a;
+        complement[a.to_ascii_lowercase() as usize] = b.to_ascii_lowercase();
+        complement[b.to_ascii_lowercase() as usize] = a.to_ascii_lowercase();
+        i += 1;
+    }
+    complement
+};
+
+/// Returns the concrete bases represented by an IUPAC degenerate base in either case, or `None` for any other byte.
+pub(crate) fn degenerate_bases(base: u8) -> Option<&'static [u8]> {
+    match base.to_ascii_uppercase() {
+        b'R' => Some(b"AG"),
+        b'Y' => Some(b"CT"),
+        b'S' => Some(b"CG"),
+        b'W' => Some(b"AT"),
+        b'K' => Some(b"GT"),
+        b'M' => Some(b"AC"),
+        b'B' => Some(b"CGT"),
+        b'D' => Some(b"AGT"),
+        b'H' => Some(b"ACT"),
+        b'V' => Some(b"ACG"),
+        b'N' => Some(b"ACGT"),
+        _ => None,
+    }
+}
+
+/// Returns the encoding of the complement of each base, used to compute reverse complements.
+#[inline]
+pub(crate) fn rc_encoding(encoding: &[u8; 256], complement: &[u8; 256]) -> [u8; 256] {
+    let mut rc_encoding = [0u8; 256];
+    for (rc, &c) in rc_encoding.iter_mut().zip(complement) {
+        *rc = encoding[c as usize];
+    }
+    rc_encoding
+}
+
+/// Returns whether the (minimizer_size + 1)-mer starting at `start` is greater than its reverse complement, comparing their first `minimizer_size` bases.
+/// Reverse complementing the sequence reverses this comparison, which makes it suitable to break ties in canonical windows of even width,
+/// except for a (minimizer_size + 1)-mer that is its own reverse complement, for which it returns `false` on both strands.
+#[inline]
+pub(crate) fn center_not_canonical<E: BaseEncoding>(
+    seq: &[u8],
+    start: usize,
+    minimizer_size: usize,
+    encoding: &E,
+    rc_encoding: &E,
+) -> bool {
+    for i in 0..minimizer_size {
+        let code = encoding.code(seq[start + i]);
+        let rc_code = rc_encoding.code(seq[start + minimizer_size - i]);
+        if code != rc_code {
+            return code > rc_code;
+        }
+    }
+    false
+}
+
+/// Returns the code of the complement of each 2-bit code, following the encoding and the complement table.
+pub(crate) fn rc_codes(encoding: &[u8; 256], complement: &[u8; 256]) -> [u8; 4] {
+    let mut rc_codes = [0u8; 4];
+    for b in [b'A', b'C', b'G', b'T'] {
+        rc_codes[(encoding[b as usize] & 0b11) as usize] =
+            encoding[complement[b as usize] as usize];
+    }
+    rc_codes
+}
+
+/// Computes the reverse complement of a packed m-mer, given the code of the complement of each 2-bit code.
+pub(crate) fn reverse_complement_kmer<T: PrimInt + 'static>(
+    mmer: T,
+    minimizer_size: usize,
+    rc_codes: &[u8; 4],
+) -> T
+where
+    u8: AsPrimitive<T>,
+{
+    let mut rc_mmer = T::zero();
+    for i in 0..minimizer_size {
+        let code = ((mmer >> (2 * i)) & 0b11u8.as_()).to_usize().unwrap();
+        rc_mmer = (rc_mmer << 2) | rc_codes[code].as_();
+    }
+    rc_mmer
+}
+
+/// Computes the canonical form of a packed m-mer, i.e. the minimum of the m-mer and its reverse complement,
+/// with a boolean indicating whether the canonical form is the reverse complement.
+///
+/// The reverse complement is computed with the same encoding and complement table as the canonical iterators,
+/// so a query normalized with this function matches the canonical minimizers they yield.
+pub fn canonical_kmer<T: PrimInt + 'static>(
+    mmer: T,
+    minimizer_size: usize,
+    encoding: &[u8; 256],
+    complement: &[u8; 256],
+) -> (T, bool)
+where
+    u8: AsPrimitive<T>,
+{
+    let rc_mmer = reverse_complement_kmer(mmer, minimizer_size, &rc_codes(encoding, complement));
+    let canonical_mmer = mmer.min(rc_mmer);
+    (canonical_mmer, canonical_mmer == rc_mmer)
+}
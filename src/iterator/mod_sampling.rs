@@ -1,15 +1,26 @@
+use super::{check_hasher, check_queue, check_sizes, mask, rc_encoding, width_t};
+use crate::queue::MinPosWindow;
+use crate::sink::MinimizerSink;
+use crate::Strand;
 use core::cmp::min;
+use core::fmt::{self, Debug, Formatter};
 use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
 use minimizer_queue::{DefaultHashBuilder, ImplicitMinimizerQueue};
 use num_traits::{AsPrimitive, PrimInt};
 use std::collections::VecDeque;
 use strength_reduce::StrengthReducedU16;
 
 /// An iterator over the positions of the mod-sampling minimizers of a sequence.
-pub struct ModSamplingPosIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder>
-{
+pub struct ModSamplingPosIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
+> {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: ImplicitMinimizerQueue<S>,
+    pub(crate) queue: Q,
     pub(crate) width_m: StrengthReducedU16,
     pub(crate) width_t: usize,
     pub(crate) tmer: T,
@@ -18,6 +29,43 @@ pub struct ModSamplingPosIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher =
     pub(crate) base_width: usize,
     pub(crate) min_pos: usize,
     pub(crate) end: usize,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>> Debug
+    for ModSamplingPosIterator<'a, T, S, Q>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModSamplingPosIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width_t", &self.width_t)
+            .field("tmer", &self.tmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T> + Clone> Clone
+    for ModSamplingPosIterator<'a, T, S, Q>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width_m: self.width_m,
+            width_t: self.width_t,
+            tmer: self.tmer,
+            tmer_mask: self.tmer_mask,
+            encoding: self.encoding,
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            _marker: self._marker,
+        }
+    }
 }
 
 impl<'a, T: PrimInt + Hash, S: BuildHasher> ModSamplingPosIterator<'a, T, S> {
@@ -29,26 +77,53 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> ModSamplingPosIterator<'a, T, S> {
         hasher: S,
         encoding: [u8; 256],
     ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * t, &hasher);
+        let queue = ImplicitMinimizerQueue::with_hasher(width_t(minimizer_size, width, t), hasher);
+        Self::with_queue(seq, minimizer_size, width, t, queue, encoding)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>>
+    ModSamplingPosIterator<'a, T, S, Q>
+{
+    /// Same as [`new`](ModSamplingPosIterator::new), but selects the minimizers with the given queue over the t-mers, which must be empty.
+    /// The width of the queue must be `width + minimizer_size - t`.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        t: usize,
+        queue: Q,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
         let width_m = StrengthReducedU16::new(width);
-        let width_t = width + (minimizer_size - t) as u16;
-        let queue = ImplicitMinimizerQueue::with_hasher(width_t, hasher);
+        let width_t = width_t(minimizer_size, width, t);
         let width_t = width_t as usize;
+        assert_eq!(
+            check_queue(minimizer_size, &queue),
+            width_t,
+            "the width of the queue must be width + minimizer_size - t"
+        );
         Self {
             seq,
             queue,
             width_m,
             width_t,
             tmer: T::zero(),
-            tmer_mask: (T::one() << (2 * t)) - T::one(),
+            tmer_mask: mask(t),
             encoding,
             base_width: width_t + t - 1,
             end: width_t + t - 1,
             min_pos: 0,
+            _marker: PhantomData,
         }
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for ModSamplingPosIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> Iterator
+    for ModSamplingPosIterator<'a, T, S, Q>
 where
     u8: AsPrimitive<T>,
 {
@@ -66,18 +141,18 @@ where
             for i in (self.base_width - self.width_t)..self.base_width {
                 self.tmer = ((self.tmer << 2) & self.tmer_mask)
                     | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
-                self.queue.insert(&self.tmer);
+                self.queue.insert(self.tmer);
             }
-            self.min_pos = (self.queue.get_min_pos() as u16 % self.width_m) as usize;
+            self.min_pos = (self.queue.min_pos() as u16 % self.width_m) as usize;
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos == self.min_pos {
                 self.tmer = ((self.tmer << 2) & self.tmer_mask)
                     | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
-                self.queue.insert(&self.tmer);
+                self.queue.insert(self.tmer);
                 self.end += 1;
                 min_pos = self.end - self.base_width
-                    + (self.queue.get_min_pos() as u16 % self.width_m) as usize;
+                    + (self.queue.min_pos() as u16 % self.width_m) as usize;
             }
             if min_pos == self.min_pos {
                 return None;
@@ -88,10 +163,22 @@ where
     }
 }
 
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> FusedIterator
+    for ModSamplingPosIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
 /// An iterator over the mod-sampling minimizers of a sequence and their positions.
-pub struct ModSamplingIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+pub struct ModSamplingIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
+> {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: ImplicitMinimizerQueue<S>,
+    pub(crate) queue: Q,
     pub(crate) width_m: StrengthReducedU16,
     pub(crate) width_t: usize,
     pub(crate) mmer: T,
@@ -102,6 +189,45 @@ pub struct ModSamplingIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = Def
     pub(crate) base_width: usize,
     pub(crate) min_pos: (T, usize),
     pub(crate) end: usize,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>> Debug
+    for ModSamplingIterator<'a, T, S, Q>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModSamplingIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width_t", &self.width_t)
+            .field("mmer", &self.mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T> + Clone> Clone
+    for ModSamplingIterator<'a, T, S, Q>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width_m: self.width_m,
+            width_t: self.width_t,
+            mmer: self.mmer,
+            mmer_mask: self.mmer_mask,
+            tmer_mask: self.tmer_mask,
+            canon_mmers: self.canon_mmers.clone(),
+            encoding: self.encoding,
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            _marker: self._marker,
+        }
+    }
 }
 
 impl<'a, T: PrimInt + Hash, S: BuildHasher> ModSamplingIterator<'a, T, S> {
@@ -113,28 +239,53 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> ModSamplingIterator<'a, T, S> {
         hasher: S,
         encoding: [u8; 256],
     ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * t, &hasher);
+        let queue = ImplicitMinimizerQueue::with_hasher(width_t(minimizer_size, width, t), hasher);
+        Self::with_queue(seq, minimizer_size, width, t, queue, encoding)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>> ModSamplingIterator<'a, T, S, Q> {
+    /// Same as [`new`](ModSamplingIterator::new), but selects the minimizers with the given queue over the t-mers, which must be empty.
+    /// The width of the queue must be `width + minimizer_size - t`.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        t: usize,
+        queue: Q,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
         let width_m = StrengthReducedU16::new(width);
-        let width_t = width + (minimizer_size - t) as u16;
-        let queue = ImplicitMinimizerQueue::with_hasher(width_t, hasher);
+        let width_t = width_t(minimizer_size, width, t);
         let width_t = width_t as usize;
+        assert_eq!(
+            check_queue(minimizer_size, &queue),
+            width_t,
+            "the width of the queue must be width + minimizer_size - t"
+        );
         Self {
             seq,
             queue,
             width_m,
             width_t,
             mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
-            tmer_mask: (T::one() << (2 * t)) - T::one(),
+            mmer_mask: mask(minimizer_size),
+            tmer_mask: mask(t),
             canon_mmers: VecDeque::with_capacity(width as usize),
             encoding,
             base_width: width_t + t - 1,
             end: width_t + t - 1,
             min_pos: (T::zero(), 0),
+            _marker: PhantomData,
         }
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for ModSamplingIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> Iterator
+    for ModSamplingIterator<'a, T, S, Q>
 where
     u8: AsPrimitive<T>,
 {
@@ -153,26 +304,26 @@ where
             for i in (self.base_width - self.width_t)..(self.base_width - width_m) {
                 self.mmer = (self.mmer << 2)
                     | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
-                self.queue.insert(&(self.mmer & self.tmer_mask));
+                self.queue.insert(self.mmer & self.tmer_mask);
             }
             for i in (self.base_width - width_m)..self.base_width {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
                     | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
-                self.queue.insert(&(self.mmer & self.tmer_mask));
+                self.queue.insert(self.mmer & self.tmer_mask);
                 self.canon_mmers.push_back(self.mmer);
             }
-            let _min_pos = (self.queue.get_min_pos() as u16 % self.width_m) as usize;
+            let _min_pos = (self.queue.min_pos() as u16 % self.width_m) as usize;
             self.min_pos = (self.canon_mmers[_min_pos], _min_pos);
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
                     | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
-                self.queue.insert(&(self.mmer & self.tmer_mask));
+                self.queue.insert(self.mmer & self.tmer_mask);
                 self.canon_mmers.pop_front();
                 self.canon_mmers.push_back(self.mmer);
                 self.end += 1;
-                let _min_pos = (self.queue.get_min_pos() as u16 % self.width_m) as usize;
+                let _min_pos = (self.queue.min_pos() as u16 % self.width_m) as usize;
                 min_pos = (
                     self.canon_mmers[_min_pos],
                     self.end - self.base_width + _min_pos,
@@ -187,15 +338,159 @@ where
     }
 }
 
-/// An iterator over the positions of the canonical mod-sampling minimizers of a sequence with a boolean indicating a reverse complement.
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>>
+    ModSamplingIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+    /// Feeds the remaining mod-minimizers and their positions to `sink`.
+    /// The sequence is rolled in a single loop which pushes each new mod-minimizer to the sink as soon as it is selected,
+    /// instead of returning it from [`next`](Iterator::next).
+    pub fn feed<K: MinimizerSink<T>>(mut self, sink: &mut K) {
+        if self.queue.is_empty() {
+            let Some((min, pos)) = self.next() else {
+                return;
+            };
+            sink.accept(min, pos);
+        }
+        for end in self.end..self.seq.len() {
+            self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                | (unsafe { self.encoding.get_unchecked(self.seq[end] as usize) }.as_());
+            self.queue.insert(self.mmer & self.tmer_mask);
+            self.canon_mmers.pop_front();
+            self.canon_mmers.push_back(self.mmer);
+            let _min_pos = (self.queue.min_pos() as u16 % self.width_m) as usize;
+            let pos = end + 1 - self.base_width + _min_pos;
+            if pos != self.min_pos.1 {
+                self.min_pos = (self.canon_mmers[_min_pos], pos);
+                sink.accept(self.min_pos.0, pos);
+            }
+        }
+        self.end = self.seq.len();
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> FusedIterator
+    for ModSamplingIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the mod-sampling minimizers of a sequence and their positions,
+/// with the packed t-mer driving their selection and its offset from the start of the minimizer.
+///
+/// The offset is `x - x % width` where `x` is the position of the smallest t-mer in the window,
+/// so the t-mer lies within the minimizer only when the offset is at most `minimizer_size - t`.
+pub struct ModSamplingTmerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
+> {
+    pub(crate) inner: ModSamplingIterator<'a, T, S, Q>,
+    pub(crate) t: usize,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>> Debug
+    for ModSamplingTmerIterator<'a, T, S, Q>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModSamplingTmerIterator")
+            .field("inner", &self.inner)
+            .field("t", &self.t)
+            .finish()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>> Clone
+    for ModSamplingTmerIterator<'a, T, S, Q>
+where
+    Q: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            t: self.t,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> ModSamplingTmerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        t: usize,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            inner: ModSamplingIterator::new(seq, minimizer_size, width, t, hasher, encoding),
+            t,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>>
+    ModSamplingTmerIterator<'a, T, S, Q>
+{
+    /// Same as [`new`](ModSamplingTmerIterator::new), but selects the minimizers with the given queue over the t-mers, which must be empty.
+    /// The width of the queue must be `width + minimizer_size - t`.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        t: usize,
+        queue: Q,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            inner: ModSamplingIterator::with_queue(seq, minimizer_size, width, t, queue, encoding),
+            t,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> Iterator
+    for ModSamplingTmerIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mmer, pos) = self.inner.next()?;
+        // the queue still holds the window in which the minimizer was selected
+        let tmer_pos = self.inner.queue.min_pos() as u16;
+        let tmer_offset = (tmer_pos - tmer_pos % self.inner.width_m) as usize;
+        let start = pos + tmer_offset;
+        let tmer = self.inner.seq[start..(start + self.t)]
+            .iter()
+            .fold(T::zero(), |tmer, &base| {
+                (tmer << 2) | (unsafe { self.inner.encoding.get_unchecked(base as usize) }.as_())
+            });
+        Some((mmer, pos, tmer, tmer_offset))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> FusedIterator
+    for ModSamplingTmerIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the positions of the canonical mod-sampling minimizers of a sequence with their [`Strand`].
 /// It requires an odd width to break ties between multiple minimizers.
 pub struct CanonicalModSamplingPosIterator<
     'a,
     T: PrimInt + Hash = u64,
     S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
 > {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: ImplicitMinimizerQueue<S>,
+    pub(crate) queue: Q,
     pub(crate) width_m: StrengthReducedU16,
     pub(crate) width_t: usize,
     pub(crate) mmer: T,
@@ -208,8 +503,55 @@ pub struct CanonicalModSamplingPosIterator<
     pub(crate) encoding: [u8; 256],
     pub(crate) rc_encoding: [u8; 256],
     pub(crate) base_width: usize,
-    pub(crate) min_pos: (usize, bool),
+    pub(crate) min_pos: (usize, Strand),
     pub(crate) end: usize,
+    pub(crate) tie_breaks: usize,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>> Debug
+    for CanonicalModSamplingPosIterator<'a, T, S, Q>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalModSamplingPosIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width_t", &self.width_t)
+            .field("mmer", &self.mmer)
+            .field("rc_mmer", &self.rc_mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("tie_breaks", &self.tie_breaks)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T> + Clone> Clone
+    for CanonicalModSamplingPosIterator<'a, T, S, Q>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width_m: self.width_m,
+            width_t: self.width_t,
+            mmer: self.mmer,
+            rc_mmer: self.rc_mmer,
+            mmer_mask: self.mmer_mask,
+            tmer_mask: self.tmer_mask,
+            rc_mmer_shift: self.rc_mmer_shift,
+            rc_tmer_shift: self.rc_tmer_shift,
+            is_rc_m: self.is_rc_m.clone(),
+            encoding: self.encoding,
+            rc_encoding: self.rc_encoding,
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            tie_breaks: self.tie_breaks,
+            _marker: self._marker,
+        }
+    }
 }
 
 impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingPosIterator<'a, T, S> {
@@ -220,9 +562,32 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingPosIterator<'a,
         t: usize,
         hasher: S,
         encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * t, &hasher);
+        let queue = ImplicitMinimizerQueue::with_hasher(width_t(minimizer_size, width, t), hasher);
+        Self::with_queue(seq, minimizer_size, width, t, queue, encoding, complement)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>>
+    CanonicalModSamplingPosIterator<'a, T, S, Q>
+{
+    /// Same as [`new`](CanonicalModSamplingPosIterator::new), but selects the minimizers with the given queue over the t-mers, which must be empty.
+    /// The width of the queue must be `width + minimizer_size - t`.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        t: usize,
+        queue: Q,
+        encoding: [u8; 256],
+        complement: [u8; 256],
     ) -> Self {
+        check_sizes(minimizer_size, width);
         let width_m = StrengthReducedU16::new(width);
-        let width_t = width + (minimizer_size - t) as u16;
+        let width_t = width_t(minimizer_size, width, t);
         assert_eq!(
             width_t % width_m,
             0,
@@ -233,13 +598,13 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingPosIterator<'a,
             1,
             "width must be odd to break ties between multiple minimizers"
         );
-        let queue = ImplicitMinimizerQueue::with_hasher(width_t, hasher);
         let width_t = width_t as usize;
-        let mut rc_encoding = encoding;
-        rc_encoding.swap(b'A' as usize, b'T' as usize);
-        rc_encoding.swap(b'a' as usize, b't' as usize);
-        rc_encoding.swap(b'C' as usize, b'G' as usize);
-        rc_encoding.swap(b'c' as usize, b'g' as usize);
+        assert_eq!(
+            check_queue(minimizer_size, &queue),
+            width_t,
+            "the width of the queue must be width + minimizer_size - t"
+        );
+        let rc_encoding = rc_encoding(&encoding, &complement);
         Self {
             seq,
             queue,
@@ -247,8 +612,8 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingPosIterator<'a,
             width_t,
             mmer: T::zero(),
             rc_mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
-            tmer_mask: (T::one() << (2 * t)) - T::one(),
+            mmer_mask: mask(minimizer_size),
+            tmer_mask: mask(t),
             rc_mmer_shift: 2 * (minimizer_size - 1),
             rc_tmer_shift: 2 * (minimizer_size - t),
             is_rc_m: VecDeque::with_capacity(width as usize),
@@ -256,10 +621,19 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingPosIterator<'a,
             rc_encoding,
             base_width: width_t + t - 1,
             end: width_t + t - 1,
-            min_pos: (0, false),
+            min_pos: (0, Strand::Forward),
+            tie_breaks: 0,
+            _marker: PhantomData,
         }
     }
 
+    /// Returns the number of windows so far in which a tie between multiple minimizers
+    /// was broken by selecting the alternate (rightmost) minimizer, because the window was not canonical.
+    #[inline]
+    pub fn tie_break_count(&self) -> usize {
+        self.tie_breaks
+    }
+
     #[inline]
     fn window_not_canonical(&self) -> bool {
         let mid = self.is_rc_m.len() / 2;
@@ -267,12 +641,12 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingPosIterator<'a,
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
-    for CanonicalModSamplingPosIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> Iterator
+    for CanonicalModSamplingPosIterator<'a, T, S, Q>
 where
     u8: AsPrimitive<T>,
 {
-    type Item = (usize, bool);
+    type Item = (usize, Strand);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.queue.is_empty() {
@@ -296,7 +670,7 @@ where
                 let tmer = self.mmer & self.tmer_mask;
                 let rc_tmer = self.rc_mmer >> self.rc_tmer_shift;
                 let canonical_tmer = min(tmer, rc_tmer);
-                self.queue.insert(&canonical_tmer);
+                self.queue.insert(canonical_tmer);
             }
             for i in (self.base_width - width_m)..self.base_width {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
@@ -307,23 +681,23 @@ where
                 let tmer = self.mmer & self.tmer_mask;
                 let rc_tmer = self.rc_mmer >> self.rc_tmer_shift;
                 let canonical_tmer = min(tmer, rc_tmer);
-                self.queue.insert(&canonical_tmer);
+                self.queue.insert(canonical_tmer);
                 self.is_rc_m.push_back(self.rc_mmer <= self.mmer);
             }
             let pos = if self.queue.multiple_mins() {
-                let (pos, tie) = self.queue.get_inner_min_pos();
-                tie.map_or(pos, |alt| {
-                    if self.window_not_canonical() {
+                let (pos, tie) = self.queue.inner_min_pos();
+                match tie {
+                    Some(alt) if self.window_not_canonical() => {
+                        self.tie_breaks += 1;
                         alt
-                    } else {
-                        pos
                     }
-                })
+                    _ => pos,
+                }
             } else {
-                self.queue.get_min_pos()
+                self.queue.min_pos()
             };
             let pos = (pos as u16 % self.width_m) as usize;
-            self.min_pos = (pos, self.is_rc_m[pos]);
+            self.min_pos = (pos, Strand::from(self.is_rc_m[pos]));
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos.0 == self.min_pos.0 {
@@ -336,24 +710,27 @@ where
                 let tmer = self.mmer & self.tmer_mask;
                 let rc_tmer = self.rc_mmer >> self.rc_tmer_shift;
                 let canonical_tmer = min(tmer, rc_tmer);
-                self.queue.insert(&canonical_tmer);
+                self.queue.insert(canonical_tmer);
                 self.is_rc_m.pop_front();
                 self.is_rc_m.push_back(self.rc_mmer <= self.mmer);
                 self.end += 1;
                 let pos = if self.queue.multiple_mins() {
-                    let (pos, tie) = self.queue.get_inner_min_pos();
-                    tie.map_or(pos, |alt| {
-                        if self.window_not_canonical() {
+                    let (pos, tie) = self.queue.inner_min_pos();
+                    match tie {
+                        Some(alt) if self.window_not_canonical() => {
+                            self.tie_breaks += 1;
                             alt
-                        } else {
-                            pos
                         }
-                    })
+                        _ => pos,
+                    }
                 } else {
-                    self.queue.get_min_pos()
+                    self.queue.min_pos()
                 };
                 let pos = (pos as u16 % self.width_m) as usize;
-                min_pos = (self.end - self.base_width + pos, self.is_rc_m[pos]);
+                min_pos = (
+                    self.end - self.base_width + pos,
+                    Strand::from(self.is_rc_m[pos]),
+                );
             }
             if min_pos.0 == self.min_pos.0 {
                 return None;
@@ -364,15 +741,23 @@ where
     }
 }
 
-/// An iterator over the canonical mod-sampling minimizers of a sequence and their positions with a boolean indicating a reverse complement.
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> FusedIterator
+    for CanonicalModSamplingPosIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the canonical mod-sampling minimizers of a sequence and their positions with their [`Strand`].
 /// It requires an odd width to break ties between multiple minimizers.
 pub struct CanonicalModSamplingIterator<
     'a,
     T: PrimInt + Hash = u64,
     S: BuildHasher = DefaultHashBuilder,
+    Q: MinPosWindow<T> = ImplicitMinimizerQueue<S>,
 > {
     pub(crate) seq: &'a [u8],
-    pub(crate) queue: ImplicitMinimizerQueue<S>,
+    pub(crate) queue: Q,
     pub(crate) width_m: StrengthReducedU16,
     pub(crate) width_t: usize,
     pub(crate) mmer: T,
@@ -385,8 +770,55 @@ pub struct CanonicalModSamplingIterator<
     pub(crate) encoding: [u8; 256],
     pub(crate) rc_encoding: [u8; 256],
     pub(crate) base_width: usize,
-    pub(crate) min_pos: (T, usize, bool),
+    pub(crate) min_pos: (T, usize, Strand),
     pub(crate) end: usize,
+    pub(crate) tie_breaks: usize,
+    pub(crate) _marker: PhantomData<S>,
+}
+
+impl<'a, T: PrimInt + Hash + Debug, S: BuildHasher, Q: MinPosWindow<T>> Debug
+    for CanonicalModSamplingIterator<'a, T, S, Q>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalModSamplingIterator")
+            .field("seq_len", &self.seq.len())
+            .field("width_t", &self.width_t)
+            .field("mmer", &self.mmer)
+            .field("rc_mmer", &self.rc_mmer)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("tie_breaks", &self.tie_breaks)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T> + Clone> Clone
+    for CanonicalModSamplingIterator<'a, T, S, Q>
+{
+    fn clone(&self) -> Self {
+        Self {
+            seq: self.seq,
+            queue: self.queue.clone(),
+            width_m: self.width_m,
+            width_t: self.width_t,
+            mmer: self.mmer,
+            rc_mmer: self.rc_mmer,
+            mmer_mask: self.mmer_mask,
+            tmer_mask: self.tmer_mask,
+            rc_mmer_shift: self.rc_mmer_shift,
+            rc_tmer_shift: self.rc_tmer_shift,
+            canon_mmers: self.canon_mmers.clone(),
+            encoding: self.encoding,
+            rc_encoding: self.rc_encoding,
+            base_width: self.base_width,
+            min_pos: self.min_pos,
+            end: self.end,
+            tie_breaks: self.tie_breaks,
+            _marker: self._marker,
+        }
+    }
 }
 
 impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingIterator<'a, T, S> {
@@ -397,9 +829,32 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingIterator<'a, T,
         t: usize,
         hasher: S,
         encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * t, &hasher);
+        let queue = ImplicitMinimizerQueue::with_hasher(width_t(minimizer_size, width, t), hasher);
+        Self::with_queue(seq, minimizer_size, width, t, queue, encoding, complement)
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, Q: MinPosWindow<T>>
+    CanonicalModSamplingIterator<'a, T, S, Q>
+{
+    /// Same as [`new`](CanonicalModSamplingIterator::new), but selects the minimizers with the given queue over the t-mers, which must be empty.
+    /// The width of the queue must be `width + minimizer_size - t`.
+    pub fn with_queue(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        t: usize,
+        queue: Q,
+        encoding: [u8; 256],
+        complement: [u8; 256],
     ) -> Self {
+        check_sizes(minimizer_size, width);
         let width_m = StrengthReducedU16::new(width);
-        let width_t = width + (minimizer_size - t) as u16;
+        let width_t = width_t(minimizer_size, width, t);
         assert_eq!(
             width_t % width_m,
             0,
@@ -410,13 +865,13 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingIterator<'a, T,
             1,
             "width must be odd to break ties between multiple minimizers"
         );
-        let queue = ImplicitMinimizerQueue::with_hasher(width_t, hasher);
         let width_t = width_t as usize;
-        let mut rc_encoding = encoding;
-        rc_encoding.swap(b'A' as usize, b'T' as usize);
-        rc_encoding.swap(b'a' as usize, b't' as usize);
-        rc_encoding.swap(b'C' as usize, b'G' as usize);
-        rc_encoding.swap(b'c' as usize, b'g' as usize);
+        assert_eq!(
+            check_queue(minimizer_size, &queue),
+            width_t,
+            "the width of the queue must be width + minimizer_size - t"
+        );
+        let rc_encoding = rc_encoding(&encoding, &complement);
         Self {
             seq,
             queue,
@@ -424,8 +879,8 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingIterator<'a, T,
             width_t,
             mmer: T::zero(),
             rc_mmer: T::zero(),
-            mmer_mask: (T::one() << (2 * minimizer_size)) - T::one(),
-            tmer_mask: (T::one() << (2 * t)) - T::one(),
+            mmer_mask: mask(minimizer_size),
+            tmer_mask: mask(t),
             rc_mmer_shift: 2 * (minimizer_size - 1),
             rc_tmer_shift: 2 * (minimizer_size - t),
             canon_mmers: VecDeque::with_capacity(width as usize),
@@ -433,10 +888,19 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingIterator<'a, T,
             rc_encoding,
             base_width: width_t + t - 1,
             end: width_t + t - 1,
-            min_pos: (T::zero(), 0, false),
+            min_pos: (T::zero(), 0, Strand::Forward),
+            tie_breaks: 0,
+            _marker: PhantomData,
         }
     }
 
+    /// Returns the number of windows so far in which a tie between multiple minimizers
+    /// was broken by selecting the alternate (rightmost) minimizer, because the window was not canonical.
+    #[inline]
+    pub fn tie_break_count(&self) -> usize {
+        self.tie_breaks
+    }
+
     #[inline]
     fn window_not_canonical(&self) -> bool {
         let mid = self.canon_mmers.len() / 2;
@@ -444,12 +908,12 @@ impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalModSamplingIterator<'a, T,
     }
 }
 
-impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
-    for CanonicalModSamplingIterator<'a, T, S>
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> Iterator
+    for CanonicalModSamplingIterator<'a, T, S, Q>
 where
     u8: AsPrimitive<T>,
 {
-    type Item = (T, usize, bool);
+    type Item = (T, usize, Strand);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.queue.is_empty() {
@@ -473,7 +937,7 @@ where
                 let tmer = self.mmer & self.tmer_mask;
                 let rc_tmer = self.rc_mmer >> self.rc_tmer_shift;
                 let canonical_tmer = min(tmer, rc_tmer);
-                self.queue.insert(&canonical_tmer);
+                self.queue.insert(canonical_tmer);
             }
             for i in (self.base_width - width_m)..self.base_width {
                 self.mmer = ((self.mmer << 2) & self.mmer_mask)
@@ -484,26 +948,26 @@ where
                 let tmer = self.mmer & self.tmer_mask;
                 let rc_tmer = self.rc_mmer >> self.rc_tmer_shift;
                 let canonical_tmer = min(tmer, rc_tmer);
-                self.queue.insert(&canonical_tmer);
+                self.queue.insert(canonical_tmer);
                 let canonical_mmer = min(self.mmer, self.rc_mmer);
                 self.canon_mmers
                     .push_back((canonical_mmer, canonical_mmer == self.rc_mmer));
             }
             let pos = if self.queue.multiple_mins() {
-                let (pos, tie) = self.queue.get_inner_min_pos();
-                tie.map_or(pos, |alt| {
-                    if self.window_not_canonical() {
+                let (pos, tie) = self.queue.inner_min_pos();
+                match tie {
+                    Some(alt) if self.window_not_canonical() => {
+                        self.tie_breaks += 1;
                         alt
-                    } else {
-                        pos
                     }
-                })
+                    _ => pos,
+                }
             } else {
-                self.queue.get_min_pos()
+                self.queue.min_pos()
             };
             let pos = (pos as u16 % self.width_m) as usize;
             let (mmer, rc) = self.canon_mmers[pos];
-            self.min_pos = (mmer, pos, rc);
+            self.min_pos = (mmer, pos, Strand::from(rc));
         } else {
             let mut min_pos = self.min_pos;
             while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
@@ -516,27 +980,27 @@ where
                 let tmer = self.mmer & self.tmer_mask;
                 let rc_tmer = self.rc_mmer >> self.rc_tmer_shift;
                 let canonical_tmer = min(tmer, rc_tmer);
-                self.queue.insert(&canonical_tmer);
+                self.queue.insert(canonical_tmer);
                 let canonical_mmer = min(self.mmer, self.rc_mmer);
                 self.canon_mmers.pop_front();
                 self.canon_mmers
                     .push_back((canonical_mmer, canonical_mmer == self.rc_mmer));
                 self.end += 1;
                 let pos = if self.queue.multiple_mins() {
-                    let (pos, tie) = self.queue.get_inner_min_pos();
-                    tie.map_or(pos, |alt| {
-                        if self.window_not_canonical() {
+                    let (pos, tie) = self.queue.inner_min_pos();
+                    match tie {
+                        Some(alt) if self.window_not_canonical() => {
+                            self.tie_breaks += 1;
                             alt
-                        } else {
-                            pos
                         }
-                    })
+                        _ => pos,
+                    }
                 } else {
-                    self.queue.get_min_pos()
+                    self.queue.min_pos()
                 };
                 let pos = (pos as u16 % self.width_m) as usize;
                 let (mmer, rc) = self.canon_mmers[pos];
-                min_pos = (mmer, self.end - self.base_width + pos, rc);
+                min_pos = (mmer, self.end - self.base_width + pos, Strand::from(rc));
             }
             if min_pos.1 == self.min_pos.1 {
                 return None;
@@ -546,3 +1010,10 @@ where
         Some(self.min_pos)
     }
 }
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, Q: MinPosWindow<T>> FusedIterator
+    for CanonicalModSamplingIterator<'a, T, S, Q>
+where
+    u8: AsPrimitive<T>,
+{
+}
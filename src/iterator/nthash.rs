@@ -0,0 +1,378 @@
+use super::{check_sizes, mask, rc_encoding};
+use crate::Strand;
+use core::cmp::min;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{BuildHasherDefault, Hash, Hasher};
+use core::iter::FusedIterator;
+use minimizer_queue::ImplicitMinimizerQueue;
+use num_traits::{AsPrimitive, PrimInt};
+use std::collections::VecDeque;
+
+const fn seeds(a: u64, c: u64, g: u64, t: u64) -> [u64; 256] {
+    let mut seeds = [0; 256];
+    seeds[b'A' as usize] = a;
+    seeds[b'a' as usize] = a;
+    seeds[b'C' as usize] = c;
+    seeds[b'c' as usize] = c;
+    seeds[b'G' as usize] = g;
+    seeds[b'g' as usize] = g;
+    seeds[b'T' as usize] = t;
+    seeds[b't' as usize] = t;
+    seeds
+}
+
+const A: u64 = 0x3c8bfbb395c60474;
+const C: u64 = 0x3193c18562a02b4c;
+const G: u64 = 0x20323ed082572324;
+const T: u64 = 0x295549f54be24456;
+
+/// Seeds of ntHash for each base, any base other than A, C, G, T has a null seed.
+const SEEDS: [u64; 256] = seeds(A, C, G, T);
+/// Seeds of ntHash for the complement of each base.
+const RC_SEEDS: [u64; 256] = seeds(T, G, C, A);
+
+/// Computes the canonical [ntHash](https://doi.org/10.1093/bioinformatics/btw397) of a k-mer,
+/// i.e. the minimum of the hashes of the k-mer and its reverse complement.
+pub fn nthash(kmer: &[u8]) -> u64 {
+    let k = kmer.len();
+    let (fh, rh) = kmer.iter().enumerate().fold((0, 0), |(fh, rh), (i, &b)| {
+        (
+            fh ^ SEEDS[b as usize].rotate_left((k - 1 - i) as u32),
+            rh ^ RC_SEEDS[b as usize].rotate_left(i as u32),
+        )
+    });
+    min(fh, rh)
+}
+
+/// Computes the forward [ntHash](https://doi.org/10.1093/bioinformatics/btw397) of a k-mer.
+pub fn nthash_forward(kmer: &[u8]) -> u64 {
+    let k = kmer.len();
+    kmer.iter().enumerate().fold(0, |fh, (i, &b)| {
+        fh ^ SEEDS[b as usize].rotate_left((k - 1 - i) as u32)
+    })
+}
+
+/// A [`Hasher`] using the value it is given as a hash, so that the queue orders the ntHash values themselves.
+#[derive(Default)]
+pub(crate) struct IdentityHasher(u64);
+
+impl Hasher for IdentityHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 << 8) | b as u64;
+        }
+    }
+
+    #[inline]
+    fn write_u64(&mut self, x: u64) {
+        self.0 = x;
+    }
+}
+
+pub(crate) type BuildIdentityHasher = BuildHasherDefault<IdentityHasher>;
+
+/// An iterator over the minimizers of a sequence and their positions,
+/// ordered by their forward [ntHash](https://doi.org/10.1093/bioinformatics/btw397) instead of the hasher of the builder.
+///
+/// ntHash is rolled in constant time per base, and any base other than A, C, G, T (case-insensitive) has a null seed.
+/// The minimizers themselves are still packed with the encoding of the builder.
+pub struct NtHashMinimizerIterator<'a, T: PrimInt + Hash = u64> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: ImplicitMinimizerQueue<BuildIdentityHasher>,
+    pub(crate) minimizer_size: usize,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) fh: u64,
+    pub(crate) mmers: VecDeque<T>,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash + Debug> Debug for NtHashMinimizerIterator<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NtHashMinimizerIterator")
+            .field("seq_len", &self.seq.len())
+            .field("minimizer_size", &self.minimizer_size)
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("fh", &self.fh)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash> NtHashMinimizerIterator<'a, T> {
+    pub fn new(seq: &'a [u8], minimizer_size: usize, width: u16, encoding: [u8; 256]) -> Self {
+        check_sizes(minimizer_size, width);
+        let queue = ImplicitMinimizerQueue::with_hasher(width, BuildIdentityHasher::default());
+        let width = width as usize;
+        Self {
+            seq,
+            queue,
+            minimizer_size,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            fh: 0,
+            mmers: VecDeque::with_capacity(width),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+        }
+    }
+
+    /// Rolls the m-mer and its hash with the base at position `i`.
+    #[inline]
+    fn roll(&mut self, i: usize)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let b = self.seq[i] as usize;
+        self.mmer =
+            ((self.mmer << 2) & self.mmer_mask) | (unsafe { self.encoding.get_unchecked(b) }.as_());
+        self.fh = self.fh.rotate_left(1) ^ SEEDS[b];
+        if i >= self.minimizer_size {
+            let out = self.seq[i - self.minimizer_size] as usize;
+            self.fh ^= SEEDS[out].rotate_left(self.minimizer_size as u32);
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static> Iterator for NtHashMinimizerIterator<'a, T>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.roll(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.roll(i);
+                self.queue.insert(&self.fh);
+                self.mmers.push_back(self.mmer);
+            }
+            let pos = self.queue.get_min_pos();
+            self.min_pos = (self.mmers[pos], pos);
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+                self.roll(self.end);
+                self.queue.insert(&self.fh);
+                self.mmers.pop_front();
+                self.mmers.push_back(self.mmer);
+                self.end += 1;
+                let pos = self.queue.get_min_pos();
+                min_pos = (self.mmers[pos], self.end - self.base_width + pos);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static> FusedIterator for NtHashMinimizerIterator<'a, T> where
+    u8: AsPrimitive<T>
+{
+}
+
+/// An iterator over the canonical minimizers of a sequence and their positions with their [`Strand`],
+/// ordered by their canonical [ntHash](https://doi.org/10.1093/bioinformatics/btw397) instead of the hasher of the builder.
+/// It requires an odd width to break ties between multiple minimizers.
+///
+/// ntHash is rolled over both strands in constant time per base, and any base other than A, C, G, T (case-insensitive) has a null seed.
+/// The minimizers themselves are still packed with the encoding of the builder.
+pub struct CanonicalNtHashMinimizerIterator<'a, T: PrimInt + Hash = u64> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: ImplicitMinimizerQueue<BuildIdentityHasher>,
+    pub(crate) minimizer_size: usize,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) rc_mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) rc_mmer_shift: usize,
+    pub(crate) fh: u64,
+    pub(crate) rh: u64,
+    pub(crate) mmers: VecDeque<(T, bool)>,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) rc_encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize, Strand),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash + Debug> Debug for CanonicalNtHashMinimizerIterator<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalNtHashMinimizerIterator")
+            .field("seq_len", &self.seq.len())
+            .field("minimizer_size", &self.minimizer_size)
+            .field("width", &self.width)
+            .field("mmer", &self.mmer)
+            .field("rc_mmer", &self.rc_mmer)
+            .field("fh", &self.fh)
+            .field("rh", &self.rh)
+            .field("base_width", &self.base_width)
+            .field("min_pos", &self.min_pos)
+            .field("end", &self.end)
+            .field("queue_is_empty", &self.queue.is_empty())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: PrimInt + Hash> CanonicalNtHashMinimizerIterator<'a, T> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        let queue = ImplicitMinimizerQueue::with_hasher(width, BuildIdentityHasher::default());
+        let width = width as usize;
+        assert_eq!(
+            width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        let rc_encoding = rc_encoding(&encoding, &complement);
+        Self {
+            seq,
+            queue,
+            minimizer_size,
+            width,
+            mmer: T::zero(),
+            rc_mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            rc_mmer_shift: 2 * (minimizer_size - 1),
+            fh: 0,
+            rh: 0,
+            mmers: VecDeque::with_capacity(width),
+            encoding,
+            rc_encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0, Strand::Forward),
+        }
+    }
+
+    #[inline]
+    fn window_not_canonical(&self) -> bool {
+        self.mmers[self.width / 2].1
+    }
+
+    /// Rolls the m-mers and the hashes of both strands with the base at position `i`.
+    #[inline]
+    fn roll(&mut self, i: usize)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let b = self.seq[i] as usize;
+        self.mmer =
+            ((self.mmer << 2) & self.mmer_mask) | (unsafe { self.encoding.get_unchecked(b) }.as_());
+        self.rc_mmer = (self.rc_mmer >> 2)
+            | (unsafe { self.rc_encoding.get_unchecked(b) }.as_() << self.rc_mmer_shift);
+        let k = self.minimizer_size as u32;
+        self.fh = self.fh.rotate_left(1) ^ SEEDS[b];
+        self.rh = self.rh.rotate_right(1) ^ RC_SEEDS[b].rotate_left(k - 1);
+        if i >= self.minimizer_size {
+            let out = self.seq[i - self.minimizer_size] as usize;
+            self.fh ^= SEEDS[out].rotate_left(k);
+            self.rh ^= RC_SEEDS[out].rotate_right(1);
+        }
+    }
+
+    /// Inserts the canonical m-mer ending at the last rolled base in the window.
+    #[inline]
+    fn insert(&mut self) {
+        let canonical_mmer = min(self.mmer, self.rc_mmer);
+        self.queue.insert(&min(self.fh, self.rh));
+        self.mmers
+            .push_back((canonical_mmer, canonical_mmer == self.rc_mmer));
+    }
+
+    #[inline]
+    fn window_min_pos(&self) -> usize {
+        if self.queue.multiple_mins() {
+            let (pos, tie) = self.queue.get_inner_min_pos();
+            tie.map_or(pos, |alt| {
+                if self.window_not_canonical() {
+                    alt
+                } else {
+                    pos
+                }
+            })
+        } else {
+            self.queue.get_min_pos()
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static> Iterator for CanonicalNtHashMinimizerIterator<'a, T>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, Strand);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.roll(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.roll(i);
+                self.insert();
+            }
+            let pos = self.window_min_pos();
+            let (x, is_rc) = self.mmers[pos];
+            self.min_pos = (x, pos, Strand::from(is_rc));
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+                self.roll(self.end);
+                self.mmers.pop_front();
+                self.insert();
+                self.end += 1;
+                let pos = self.window_min_pos();
+                let (x, is_rc) = self.mmers[pos];
+                min_pos = (x, self.end - self.base_width + pos, Strand::from(is_rc));
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static> FusedIterator for CanonicalNtHashMinimizerIterator<'a, T> where
+    u8: AsPrimitive<T>
+{
+}
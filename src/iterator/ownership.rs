@@ -0,0 +1,125 @@
+use super::WindowMinimizerIterator;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the minimizers of a sequence with the interval of bases they own,
+/// i.e. the union of the spans of the windows selecting them, yielding `(minimizer, start, end)` with `end` excluded.
+///
+/// Each selected occurrence is yielded once, in the same order as [`MinimizerIterator`](super::MinimizerIterator).
+/// Since consecutive windows overlap, the interval of a minimizer overlaps the interval of the next one
+/// by `base_width - 1` bases: the bases of the window where a new minimizer takes over belong to both.
+/// The first interval starts at 0 and the last one ends at the end of the sequence.
+pub struct OwnershipIterator<'a, T: PrimInt + Hash, S: BuildHasher> {
+    pub(crate) inner: WindowMinimizerIterator<'a, T, S>,
+    pub(crate) current: Option<(T, usize, usize, usize)>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> OwnershipIterator<'a, T, S> {
+    pub fn new(inner: WindowMinimizerIterator<'a, T, S>) -> Self {
+        Self {
+            inner,
+            current: None,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> OwnershipIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    /// Returns the next minimizer with its position and the indices of the first and the last windows selecting it.
+    pub(crate) fn next_run(&mut self) -> Option<(T, usize, usize, usize)> {
+        loop {
+            match self.inner.next() {
+                Some((window, min, offset)) => match self.current {
+                    Some((_, pos, _, ref mut last)) if pos == window + offset => *last = window,
+                    Some(run) => {
+                        self.current = Some((min, window + offset, window, window));
+                        return Some(run);
+                    }
+                    None => self.current = Some((min, window + offset, window, window)),
+                },
+                None => return self.current.take(),
+            }
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for OwnershipIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, _, first, last) = self.next_run()?;
+        Some((x, first, last + self.inner.base_width))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        let pending = usize::from(self.current.is_some());
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator for OwnershipIterator<'a, T, S> where
+    u8: AsPrimitive<T>
+{
+}
+
+/// An iterator over the minimizers of a sequence and their positions, excluding those only selected by windows close to the ends of the sequence.
+///
+/// A window is close to an end if fewer than `width` windows separate it from the first or the last window of the sequence.
+/// The m-mers near the ends are covered by fewer windows than those in the interior, so excluding the minimizers only selected there
+/// removes the bias they introduce in density estimates.
+/// A minimizer selected by at least one interior window is kept, even if some of its windows are close to an end.
+/// Sequences with at most `2 * width` windows have no interior window, so nothing is yielded.
+pub struct TrimEndsIterator<'a, T: PrimInt + Hash, S: BuildHasher> {
+    pub(crate) inner: OwnershipIterator<'a, T, S>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> TrimEndsIterator<'a, T, S> {
+    pub fn new(inner: OwnershipIterator<'a, T, S>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for TrimEndsIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let windows = &self.inner.inner;
+        let width = windows.width;
+        let num_windows = (windows.seq.len() + 1).saturating_sub(windows.base_width);
+        if num_windows <= 2 * width {
+            return None;
+        }
+        let last_interior = num_windows - 1 - width;
+        while let Some((x, pos, first, last)) = self.inner.next_run() {
+            if first > last_interior {
+                return None;
+            }
+            if last >= width {
+                return Some((x, pos));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator for TrimEndsIterator<'a, T, S> where
+    u8: AsPrimitive<T>
+{
+}
@@ -0,0 +1,103 @@
+use super::{check_hasher, check_sizes, mask};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the minimizers of a 2-bit packed sequence and their positions.
+///
+/// Each byte holds 4 bases, the first one in the 2 most significant bits, and each base is directly given by its 2-bit code,
+/// so the encoding of the builder is ignored.
+/// Since the packed bytes are only borrowed, they can come from a memory-mapped file without being copied.
+pub struct PackedMinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder>
+{
+    pub(crate) packed: &'a [u8],
+    pub(crate) len: usize,
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> PackedMinimizerIterator<'a, T, S> {
+    pub fn new(packed: &'a [u8], len: usize, minimizer_size: usize, width: u16, hasher: S) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        assert!(
+            len <= packed.len().saturating_mul(4),
+            "{len} bases do not fit in {} packed bytes",
+            packed.len()
+        );
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            packed,
+            len,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+        }
+    }
+
+    /// Returns the 2-bit code of the base at position `i`.
+    #[inline]
+    fn base(&self, i: usize) -> T
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let byte = unsafe { self.packed.get_unchecked(i / 4) };
+        ((byte >> (6 - 2 * (i % 4))) & 0b11).as_()
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for PackedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.len {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.mmer = (self.mmer << 2) | self.base(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask) | self.base(i);
+                self.queue.insert(self.mmer);
+            }
+            self.min_pos = self.queue.get_min_pos();
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.len && min_pos.1 == self.min_pos.1 {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask) | self.base(self.end);
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for PackedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,72 @@
+use super::MinimizerIterator;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::DefaultHashBuilder;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// Returns the reverse complement of a sequence using the given complement table.
+fn reverse_complement(seq: &[u8], complement: &[u8; 256]) -> Vec<u8> {
+    seq.iter().rev().map(|&b| complement[b as usize]).collect()
+}
+
+/// An iterator over the minimizers of a pair of reads, yielding the mate (1 or 2), the minimizer and its position in the read.
+///
+/// The minimizers of the first mate are yielded first, followed by those of the second mate,
+/// which can be computed on its reverse complement, as aligners do to seed paired reads.
+/// In that case, positions are given on the reverse complement of the second mate.
+pub struct PairedMinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder>
+{
+    pub(crate) mate1: MinimizerIterator<'a, T, S>,
+    pub(crate) mate2: std::vec::IntoIter<(T, usize)>,
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher + Clone> PairedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    pub fn new(
+        (read1, read2): (&'a [u8], &[u8]),
+        rc_mate2: bool,
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        // the second mate is processed eagerly since its reverse complement is not borrowed from the input
+        let mate2 = if rc_mate2 {
+            let read2 = reverse_complement(read2, &complement);
+            MinimizerIterator::new(&read2, minimizer_size, width, hasher.clone(), encoding)
+                .collect::<Vec<_>>()
+        } else {
+            MinimizerIterator::new(read2, minimizer_size, width, hasher.clone(), encoding).collect()
+        };
+        Self {
+            mate1: MinimizerIterator::new(read1, minimizer_size, width, hasher, encoding),
+            mate2: mate2.into_iter(),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for PairedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (u8, T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((min, pos)) = self.mate1.next() {
+            return Some((1, min, pos));
+        }
+        let (min, pos) = self.mate2.next()?;
+        Some((2, min, pos))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for PairedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,55 @@
+use super::reverse_complement_kmer;
+use crate::Strand;
+use core::iter::FusedIterator;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over canonical minimizers and their positions with their strand,
+/// and a boolean indicating whether the minimizer is a palindrome, i.e. its own reverse complement.
+///
+/// A palindromic m-mer reads the same on both strands, so its strand is ambiguous: the canonical iterators report it on [`Strand::Reverse`].
+/// With the usual complement, only m-mers of even size can be palindromes.
+pub struct PalindromeIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) minimizer_size: usize,
+    pub(crate) rc_codes: [u8; 4],
+}
+
+impl<I> PalindromeIterator<I> {
+    /// Wraps an iterator over canonical minimizers of size `minimizer_size`,
+    /// given the code of the complement of each 2-bit code.
+    pub fn new(inner: I, minimizer_size: usize, rc_codes: [u8; 4]) -> Self {
+        Self {
+            inner,
+            minimizer_size,
+            rc_codes,
+        }
+    }
+}
+
+impl<T: PrimInt + 'static, I: Iterator<Item = (T, usize, Strand)>> Iterator
+    for PalindromeIterator<I>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, Strand, bool);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos, strand) = self.inner.next()?;
+        let is_palindrome =
+            reverse_complement_kmer(min, self.minimizer_size, &self.rc_codes) == min;
+        Some((min, pos, strand, is_palindrome))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: PrimInt + 'static, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator
+    for PalindromeIterator<I>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,153 @@
+use super::{check_hasher, check_sizes, mask};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::MinimizerQueue;
+use num_traits::{AsPrimitive, PrimInt};
+use std::collections::VecDeque;
+
+/// An iterator over the minimizers of a sequence and their positions, never selecting the m-mers rejected by a predicate.
+///
+/// The predicate is called with each m-mer and its position, and the rejected m-mers are inserted in the window with the largest hash.
+/// Unlike filtering the minimizers afterwards, each window selects its smallest m-mer that is not rejected,
+/// so a rejected m-mer is replaced by the next candidate of the window instead of leaving the window without minimizer.
+/// Only the windows containing rejected m-mers exclusively do not select any minimizer.
+///
+/// Rejecting some m-mers moves the selection to other m-mers, so the density can slightly increase around the rejected m-mers,
+/// and it decreases in the regions where every m-mer is rejected.
+pub struct PenalizedMinimizerIterator<
+    'a,
+    T: PrimInt + Hash,
+    S: BuildHasher,
+    F: Fn(T, usize) -> bool,
+> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) penalized: F,
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) hasher: S,
+    pub(crate) minimizer_size: usize,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) is_penalized: VecDeque<bool>,
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher + Clone, F: Fn(T, usize) -> bool>
+    PenalizedMinimizerIterator<'a, T, S, F>
+{
+    pub fn new(
+        seq: &'a [u8],
+        penalized: F,
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher.clone());
+        let width = width as usize;
+        Self {
+            seq,
+            penalized,
+            queue,
+            hasher,
+            minimizer_size,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            encoding,
+            is_penalized: VecDeque::with_capacity(width),
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher, F: Fn(T, usize) -> bool>
+    PenalizedMinimizerIterator<'a, T, S, F>
+{
+    /// Rolls the m-mer with the base at position `i`.
+    #[inline]
+    fn roll(&mut self, i: usize)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.mmer = ((self.mmer << 2) & self.mmer_mask)
+            | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+    }
+
+    /// Inserts the m-mer ending at position `i` in the queue, with the largest hash if it is rejected.
+    #[inline]
+    fn insert(&mut self, i: usize) {
+        let penalized = (self.penalized)(self.mmer, i + 1 - self.minimizer_size);
+        let hash = if penalized {
+            u64::MAX
+        } else {
+            self.hasher.hash_one(self.mmer)
+        };
+        self.queue.insert_with_hash(self.mmer, hash);
+        if self.is_penalized.len() == self.width {
+            self.is_penalized.pop_front();
+        }
+        self.is_penalized.push_back(penalized);
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, F: Fn(T, usize) -> bool> Iterator
+    for PenalizedMinimizerIterator<'a, T, S, F>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let offset = if self.queue.is_empty() {
+                if self.base_width > self.seq.len() {
+                    return None;
+                }
+                for i in 0..(self.base_width - self.width) {
+                    self.roll(i);
+                }
+                for i in (self.base_width - self.width)..self.base_width {
+                    self.roll(i);
+                    self.insert(i);
+                }
+                self.min_pos = self.queue.get_min_pos();
+                self.min_pos.1
+            } else {
+                let mut min_pos = self.min_pos;
+                let mut offset = 0;
+                while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+                    self.roll(self.end);
+                    self.insert(self.end);
+                    self.end += 1;
+                    let _min_pos = self.queue.get_min_pos();
+                    offset = _min_pos.1;
+                    min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+                }
+                if min_pos.1 == self.min_pos.1 {
+                    return None;
+                }
+                self.min_pos = min_pos;
+                offset
+            };
+            if !self.is_penalized[offset] {
+                return Some(self.min_pos);
+            }
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher, F: Fn(T, usize) -> bool> FusedIterator
+    for PenalizedMinimizerIterator<'a, T, S, F>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,134 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// An iterator over the positions of minimizers narrowed to `u32`, halving the memory footprint of collected positions.
+///
+/// Positions are checked to fit in a `u32` in debug builds only.
+pub struct U32PosIterator<I> {
+    pub(crate) inner: I,
+}
+
+impl<I> U32PosIterator<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner }
+    }
+}
+
+impl<I: Iterator<Item = usize>> Iterator for U32PosIterator<I> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.inner.next()?;
+        debug_assert!(
+            pos <= u32::MAX as usize,
+            "position {pos} does not fit in a u32"
+        );
+        Some(pos as u32)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: FusedIterator<Item = usize>> FusedIterator for U32PosIterator<I> {}
+
+/// An iterator over the gaps between the positions of consecutive minimizers.
+///
+/// The first item is the position of the first minimizer, i.e. its gap from the start of the sequence,
+/// so the prefix sums of the gaps give back the positions.
+pub struct GapIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) last_pos: usize,
+}
+
+impl<I> GapIterator<I> {
+    pub fn new(inner: I) -> Self {
+        Self { inner, last_pos: 0 }
+    }
+}
+
+impl<I: Iterator<Item = usize>> Iterator for GapIterator<I> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.inner.next()?;
+        let gap = pos - self.last_pos;
+        self.last_pos = pos;
+        Some(gap)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: FusedIterator<Item = usize>> FusedIterator for GapIterator<I> {}
+
+/// An iterator over the gaps between the positions of consecutive minimizers that are longer than a threshold, as ranges of positions.
+///
+/// Each range starts at the position of a minimizer and ends at the position of the next one,
+/// so its length is the gap between them and no minimizer starts strictly inside it.
+/// As with `max_gap`, the start of the sequence and the last m-mer are also considered as endpoints,
+/// so the first range may start at 0 and the last one may end at the last m-mer.
+pub struct LongGapIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) threshold: usize,
+    pub(crate) last_pos: usize,
+    pub(crate) last_mmer: usize,
+    pub(crate) started: bool,
+}
+
+impl<I> LongGapIterator<I> {
+    pub fn new(inner: I, threshold: usize, last_mmer: usize) -> Self {
+        Self {
+            inner,
+            threshold,
+            last_pos: 0,
+            last_mmer,
+            started: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = usize>> Iterator for LongGapIterator<I> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next() {
+                Some(pos) => {
+                    self.started = true;
+                    let gap = self.last_pos..pos;
+                    self.last_pos = pos;
+                    if gap.len() > self.threshold {
+                        return Some(gap);
+                    }
+                }
+                None => {
+                    if !self.started {
+                        return None;
+                    }
+                    self.started = false;
+                    let gap = self.last_pos..self.last_mmer;
+                    if gap.len() > self.threshold {
+                        return Some(gap);
+                    }
+                    return None;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.inner.size_hint();
+        (0, upper.and_then(|upper| upper.checked_add(1)))
+    }
+}
+
+impl<I: FusedIterator<Item = usize>> FusedIterator for LongGapIterator<I> {}
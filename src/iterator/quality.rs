@@ -0,0 +1,152 @@
+use super::{check_hasher, check_sizes, mask};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the minimizers of a sequence and their positions, ignoring the low-quality bases.
+///
+/// Bases whose quality score is below `min_q` and bases other than A, C, G and T (e.g. N) are treated as invalid:
+/// the window is restarted after each of them, so that no selected minimizer overlaps an invalid base.
+/// Quality scores are compared as raw bytes, so `min_q` must use the same offset as `qual` (e.g. `b'!' + 20` for Phred+33).
+pub struct QualityMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) qual: &'a [u8],
+    pub(crate) min_q: u8,
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+    pub(crate) valid: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> QualityMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        qual: &'a [u8],
+        min_q: u8,
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        assert_eq!(
+            seq.len(),
+            qual.len(),
+            "the quality scores must have the same length as the sequence"
+        );
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            seq,
+            qual,
+            min_q,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+            end: 0,
+            valid: 0,
+        }
+    }
+
+    /// Returns the number of valid bases read so far, i.e. the A, C, G and T whose quality score is at least `min_q`.
+    /// Once the iterator is exhausted, this is the number of valid bases in the whole sequence,
+    /// which tells apart a sequence with no valid base (e.g. only N) from a valid sequence too short to contain a window.
+    #[inline]
+    pub fn valid_base_count(&self) -> usize {
+        self.valid
+    }
+
+    #[inline]
+    fn is_valid(&self, i: usize) -> bool {
+        self.qual[i] >= self.min_q
+            && matches!(
+                self.seq[i],
+                b'A' | b'C' | b'G' | b'T' | b'a' | b'c' | b'g' | b't'
+            )
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for QualityMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.queue.is_empty() {
+            let mut min_pos = self.min_pos;
+            while self.end < self.seq.len() && min_pos.1 == self.min_pos.1 {
+                if !self.is_valid(self.end) {
+                    self.queue.clear();
+                    self.mmer = T::zero();
+                    self.end += 1;
+                    break;
+                }
+                self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                    | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                self.valid += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if !self.queue.is_empty() {
+                if min_pos.1 == self.min_pos.1 {
+                    return None;
+                }
+                self.min_pos = min_pos;
+                return Some(self.min_pos);
+            }
+        }
+        // looks for the next run of base_width valid bases to restart the window
+        let mut run = 0;
+        while run < self.base_width {
+            if self.end >= self.seq.len() {
+                return None;
+            }
+            if self.is_valid(self.end) {
+                run += 1;
+                self.valid += 1;
+            } else {
+                run = 0;
+            }
+            self.end += 1;
+        }
+        let start = self.end - self.base_width;
+        for i in start..(self.end - self.width) {
+            self.mmer = (self.mmer << 2)
+                | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+        }
+        for i in (self.end - self.width)..self.end {
+            self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+            self.queue.insert(self.mmer);
+        }
+        let (x, pos) = self.queue.get_min_pos();
+        self.min_pos = (x, start + pos);
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for QualityMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,50 @@
+use core::iter::FusedIterator;
+
+/// An iterator over the minimizers and their positions within a range of a sequence.
+///
+/// The underlying iterator only processes the part of the sequence covered by the windows overlapping the range,
+/// and its positions are shifted by `offset` to be given in the coordinates of the whole sequence.
+/// Only the minimizers positioned in `start..end` are yielded.
+pub struct RangeIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) offset: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl<I> RangeIterator<I> {
+    pub fn new(inner: I, offset: usize, start: usize, end: usize) -> Self {
+        Self {
+            inner,
+            offset,
+            start,
+            end,
+        }
+    }
+}
+
+impl<T, I: Iterator<Item = (T, usize)>> Iterator for RangeIterator<I> {
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (min, pos) = self.inner.next()?;
+            let pos = pos + self.offset;
+            // positions are increasing, so no minimizer remains in the range
+            if pos >= self.end {
+                return None;
+            }
+            if pos >= self.start {
+                return Some((min, pos));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<T, I: FusedIterator<Item = (T, usize)>> FusedIterator for RangeIterator<I> {}
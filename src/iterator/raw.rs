@@ -0,0 +1,100 @@
+use super::{check_hasher, check_sizes, mask};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the minimizers of a sequence of 2-bit codes and their positions.
+///
+/// Each byte holds a single base given directly by its code (0, 1, 2 or 3) instead of an ASCII letter,
+/// so the encoding of the builder is ignored and no lookup is performed.
+pub struct RawCodesMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) codes: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> RawCodesMinimizerIterator<'a, T, S> {
+    pub fn new(codes: &'a [u8], minimizer_size: usize, width: u16, hasher: S) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            codes,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+        }
+    }
+
+    /// Returns the code of the base at position `i`.
+    #[inline]
+    fn base(&self, i: usize) -> T
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        let code = self.codes[i];
+        debug_assert!(code < 4, "invalid code {code} at position {i}");
+        code.as_()
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for RawCodesMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.codes.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.mmer = (self.mmer << 2) | self.base(i);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask) | self.base(i);
+                self.queue.insert(self.mmer);
+            }
+            self.min_pos = self.queue.get_min_pos();
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.codes.len() && min_pos.1 == self.min_pos.1 {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask) | self.base(self.end);
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some(self.min_pos)
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for RawCodesMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,36 @@
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// An iterator over the minimizers and their positions that belong to a shared set of values.
+///
+/// The underlying iterator still processes the whole sequence, only the minimizers outside of the set are skipped.
+/// Since the set is shared through an [`Arc`], the same allow-list can be used by several threads without copying it.
+pub struct RetainIterator<I, T> {
+    pub(crate) inner: I,
+    pub(crate) set: Arc<HashSet<T>>,
+}
+
+impl<I, T> RetainIterator<I, T> {
+    pub fn new(inner: I, set: Arc<HashSet<T>>) -> Self {
+        Self { inner, set }
+    }
+}
+
+impl<T: Eq + Hash, I: Iterator<Item = (T, usize)>> Iterator for RetainIterator<I, T> {
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let set = &self.set;
+        self.inner.find(|(min, _)| set.contains(min))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<T: Eq + Hash, I: FusedIterator<Item = (T, usize)>> FusedIterator for RetainIterator<I, T> {}
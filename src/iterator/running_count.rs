@@ -0,0 +1,43 @@
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use std::collections::HashMap;
+
+/// An iterator over minimizers and their positions, with the number of occurrences of each minimizer so far.
+///
+/// The count is the 1-based index of the occurrence of the minimizer among the ones yielded so far,
+/// which makes it possible to filter frequent minimizers on the fly.
+pub struct RunningCountIterator<I, T> {
+    pub(crate) inner: I,
+    pub(crate) counts: HashMap<T, usize>,
+}
+
+impl<I, T> RunningCountIterator<I, T> {
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash, I: Iterator<Item = (T, usize)>> Iterator for RunningCountIterator<I, T> {
+    type Item = (T, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        let count = self.counts.entry(min).or_default();
+        *count += 1;
+        Some((min, pos, *count))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Copy + Eq + Hash, I: FusedIterator<Item = (T, usize)>> FusedIterator
+    for RunningCountIterator<I, T>
+{
+}
@@ -0,0 +1,40 @@
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+
+/// An iterator over minimizers and their positions, with a second hash of each minimizer computed by an independent hasher.
+///
+/// The secondary hash does not affect the selection of minimizers: two distinct minimizers with equal primary hashes
+/// but different secondary hashes reveal a collision of the hasher used for the ordering.
+pub struct SecondaryHashIterator<I, H: BuildHasher> {
+    pub(crate) inner: I,
+    pub(crate) hasher: H,
+}
+
+impl<I, H: BuildHasher> SecondaryHashIterator<I, H> {
+    pub fn new(inner: I, hasher: H) -> Self {
+        Self { inner, hasher }
+    }
+}
+
+impl<T: Hash, I: Iterator<Item = (T, usize)>, H: BuildHasher> Iterator
+    for SecondaryHashIterator<I, H>
+{
+    type Item = (T, usize, u64);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        let hash = self.hasher.hash_one(&min);
+        Some((min, pos, hash))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Hash, I: FusedIterator<Item = (T, usize)>, H: BuildHasher> FusedIterator
+    for SecondaryHashIterator<I, H>
+{
+}
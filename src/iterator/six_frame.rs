@@ -0,0 +1,143 @@
+use super::bases::RollingState;
+use super::COMPLEMENT;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::DefaultHashBuilder;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// The amino acids, the stop codon `*` and the unknown residue `X`, in the order of their 5-bit codes.
+pub const AMINO_ACIDS: &[u8; 22] = b"ACDEFGHIKLMNPQRSTVWY*X";
+
+/// The standard genetic code, indexed by the codons encoded with A = `00`, C = `01`, G = `10`, T = `11`.
+const CODON_TABLE: &[u8; 64] = b"KNKNTTTTRSRSIIMIQHQHPPPPRRRRLLLLEDEDAAAAGGGGVVVV*Y*YSSSS*CWCLFLF";
+
+/// The frames in the order in which they are processed.
+const FRAMES: [i8; 6] = [1, 2, 3, -1, -2, -3];
+
+/// Returns the 2-bit code of a nucleotide, or `None` if it is not one of A, C, G, T.
+#[inline]
+fn nucleotide_code(b: u8) -> Option<usize> {
+    match b {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+/// Translates a nucleotide sequence in its first frame with the standard genetic code.
+/// Codons containing a base other than A, C, G, T are translated to `X`, and stop codons to `*`.
+pub fn translate(dna: &[u8]) -> Vec<u8> {
+    dna.chunks_exact(3)
+        .map(|codon| {
+            match (
+                nucleotide_code(codon[0]),
+                nucleotide_code(codon[1]),
+                nucleotide_code(codon[2]),
+            ) {
+                (Some(a), Some(b), Some(c)) => CODON_TABLE[(a << 4) | (b << 2) | c],
+                _ => b'X',
+            }
+        })
+        .collect()
+}
+
+/// Returns the mask of the `5 * size` lowest bits, checking that they fit in `T`.
+#[inline]
+fn mask_residues<T: PrimInt>(size: usize) -> T {
+    let bits = T::zero().count_zeros() as usize;
+    assert!(
+        5 * size <= bits,
+        "With this integer type, minimizer_size must be ≤ {} for amino acids, got {size}. Please select a smaller size or a larger type.",
+        bits / 5
+    );
+    if 5 * size == bits {
+        T::max_value()
+    } else {
+        (T::one() << (5 * size)) - T::one()
+    }
+}
+
+/// An iterator over the minimizers of a nucleotide sequence translated in its six frames,
+/// yielding the frame, the minimizer and its position in the translated frame.
+///
+/// The frames are processed in the order 1, 2, 3, -1, -2, -3, where the negative frames are read on the reverse complement.
+/// Each amino acid is encoded on 5 bits following [`AMINO_ACIDS`], so the minimizer size and the width are counted in amino acids.
+/// Use [`nucleotide_pos`](Self::nucleotide_pos) to map a position in a frame back to the nucleotide sequence.
+pub struct SixFrameMinimizerIterator<T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+    pub(crate) frames: Vec<Vec<u8>>,
+    pub(crate) frame: usize,
+    pub(crate) len: usize,
+    pub(crate) minimizer_size: usize,
+    pub(crate) state: RollingState<T, S, 5>,
+}
+
+impl<T: PrimInt + Hash, S: BuildHasher> SixFrameMinimizerIterator<T, S> {
+    pub fn new(dna: &[u8], minimizer_size: usize, width: u16, hasher: S) -> Self {
+        let mut aa_codes = [0u8; 256];
+        for (code, &aa) in AMINO_ACIDS.iter().enumerate() {
+            aa_codes[aa as usize] = code as u8;
+        }
+        let state = RollingState::with_mask(
+            minimizer_size,
+            width,
+            hasher,
+            aa_codes,
+            mask_residues(minimizer_size),
+        );
+        let rc: Vec<u8> = dna.iter().rev().map(|&b| COMPLEMENT[b as usize]).collect();
+        let frames = FRAMES
+            .iter()
+            .map(|&frame| {
+                let strand = if frame > 0 { dna } else { &rc[..] };
+                let offset = (frame.unsigned_abs() - 1) as usize;
+                translate(strand.get(offset..).unwrap_or_default())
+            })
+            .collect();
+        Self {
+            frames,
+            frame: 0,
+            len: dna.len(),
+            minimizer_size,
+            state,
+        }
+    }
+
+    /// Returns the position in the nucleotide sequence of the first base covered by a minimizer of the given frame,
+    /// i.e. the leftmost base of its codons on the forward strand.
+    pub fn nucleotide_pos(&self, frame: i8, pos: usize) -> usize {
+        let offset = (frame.unsigned_abs() - 1) as usize + 3 * pos;
+        if frame > 0 {
+            offset
+        } else {
+            self.len - offset - 3 * self.minimizer_size
+        }
+    }
+}
+
+impl<T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for SixFrameMinimizerIterator<T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (i8, T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.frame < FRAMES.len() {
+            let residues = &self.frames[self.frame];
+            while self.state.end < residues.len() {
+                if let Some((min, pos)) = self.state.push(residues[self.state.end]) {
+                    return Some((FRAMES[self.frame], min, pos));
+                }
+            }
+            self.state.reset();
+            self.frame += 1;
+        }
+        None
+    }
+}
+
+impl<T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator for SixFrameMinimizerIterator<T, S> where
+    u8: AsPrimitive<T>
+{
+}
@@ -0,0 +1,145 @@
+use super::{check_hasher, check_sizes, mask};
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over the minimizers of a spliced sequence and their genomic positions.
+///
+/// The segments are concatenated into a single logical sequence, so that k-mers spanning a junction are considered,
+/// and each minimizer is reported at the genomic coordinate of its first base.
+/// The genomic coordinates of every segment must fit in `usize`.
+pub struct SplicedMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) segments: &'a [(&'a [u8], usize)],
+    pub(crate) starts: Vec<usize>,
+    pub(crate) len: usize,
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) min_pos: (T, usize),
+    pub(crate) end: usize,
+    pub(crate) read_seg: usize,
+    pub(crate) read_offset: usize,
+    pub(crate) map_seg: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> SplicedMinimizerIterator<'a, T, S> {
+    pub fn new(
+        segments: &'a [(&'a [u8], usize)],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        let mut starts = Vec::with_capacity(segments.len());
+        let mut len = 0;
+        for (segment, genomic_start) in segments {
+            assert!(
+                genomic_start.checked_add(segment.len()).is_some(),
+                "the genomic coordinates of a segment starting at {genomic_start} overflow usize"
+            );
+            starts.push(len);
+            len += segment.len();
+        }
+        Self {
+            segments,
+            starts,
+            len,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+            min_pos: (T::zero(), 0),
+            read_seg: 0,
+            read_offset: 0,
+            map_seg: 0,
+        }
+    }
+
+    /// Reads the next base of the logical sequence, crossing junctions as needed.
+    #[inline]
+    fn next_base(&mut self) -> u8 {
+        while self.read_offset == self.segments[self.read_seg].0.len() {
+            self.read_seg += 1;
+            self.read_offset = 0;
+        }
+        let base = self.segments[self.read_seg].0[self.read_offset];
+        self.read_offset += 1;
+        base
+    }
+
+    /// Maps a position of the logical sequence to its genomic coordinate.
+    /// Positions must be queried in non-decreasing order.
+    #[inline]
+    fn genomic_pos(&mut self, pos: usize) -> usize {
+        while self.map_seg + 1 < self.starts.len() && self.starts[self.map_seg + 1] <= pos {
+            self.map_seg += 1;
+        }
+        self.segments[self.map_seg].1 + pos - self.starts[self.map_seg]
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for SplicedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.len {
+                return None;
+            }
+            for _ in 0..(self.base_width - self.width) {
+                let base = self.next_base();
+                self.mmer = (self.mmer << 2)
+                    | (unsafe { self.encoding.get_unchecked(base as usize) }.as_());
+            }
+            for _ in (self.base_width - self.width)..self.base_width {
+                let base = self.next_base();
+                self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                    | (unsafe { self.encoding.get_unchecked(base as usize) }.as_());
+                self.queue.insert(self.mmer);
+            }
+            self.min_pos = self.queue.get_min_pos();
+        } else {
+            let mut min_pos = self.min_pos;
+            while self.end < self.len && min_pos.1 == self.min_pos.1 {
+                let base = self.next_base();
+                self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                    | (unsafe { self.encoding.get_unchecked(base as usize) }.as_());
+                self.queue.insert(self.mmer);
+                self.end += 1;
+                let _min_pos = self.queue.get_min_pos();
+                min_pos = (_min_pos.0, self.end - self.base_width + _min_pos.1);
+            }
+            if min_pos.1 == self.min_pos.1 {
+                return None;
+            }
+            self.min_pos = min_pos;
+        }
+        Some((self.min_pos.0, self.genomic_pos(self.min_pos.1)))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for SplicedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
@@ -0,0 +1,58 @@
+use super::reverse_complement_kmer;
+use crate::Strand;
+use core::iter::FusedIterator;
+use num_traits::{AsPrimitive, PrimInt};
+
+/// An iterator over canonical minimizers and their positions, reported on an annotated strand.
+///
+/// The minimizers are selected as canonical minimizers, but each one is yielded as the m-mer read on the annotated strand,
+/// i.e. the forward m-mer for [`Strand::Forward`] and its reverse complement for [`Strand::Reverse`], along with that strand.
+pub struct StrandedIterator<I> {
+    pub(crate) inner: I,
+    pub(crate) strand: Strand,
+    pub(crate) minimizer_size: usize,
+    pub(crate) rc_codes: [u8; 4],
+}
+
+impl<I> StrandedIterator<I> {
+    /// Wraps an iterator over canonical minimizers of size `minimizer_size`,
+    /// given the code of the complement of each 2-bit code.
+    pub fn new(inner: I, strand: Strand, minimizer_size: usize, rc_codes: [u8; 4]) -> Self {
+        Self {
+            inner,
+            strand,
+            minimizer_size,
+            rc_codes,
+        }
+    }
+}
+
+impl<T: PrimInt + 'static, I: Iterator<Item = (T, usize, Strand)>> Iterator for StrandedIterator<I>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos, strand) = self.inner.next()?;
+        let min = if strand == self.strand {
+            min
+        } else {
+            reverse_complement_kmer(min, self.minimizer_size, &self.rc_codes)
+        };
+        Some((min, pos, self.strand))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: PrimInt + 'static, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator
+    for StrandedIterator<I>
+where
+    u8: AsPrimitive<T>,
+{
+}
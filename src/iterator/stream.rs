@@ -0,0 +1,92 @@
+use super::bases::RollingState;
+use core::hash::{BuildHasher, Hash};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use futures_io::AsyncBufRead;
+use minimizer_queue::DefaultHashBuilder;
+use num_traits::{AsPrimitive, PrimInt};
+use std::io;
+
+/// A stream of the minimizers and their positions in a sequence read asynchronously.
+///
+/// Every byte of the reader is considered as a base, and the window is rolled across the chunks returned by the reader,
+/// so the stream yields exactly the same minimizers as [`MinimizerIterator`](super::MinimizerIterator) on the whole sequence.
+/// Each minimizer is yielded as soon as the window selecting it has been read, so the minimizer of the last window
+/// is available without waiting for the end of the reader.
+/// Reading errors are forwarded by the stream, after which it may be polled again to resume reading.
+/// If the sequence is too long for its positions to fit in `usize`, e.g. beyond 4 GiB on 32-bit targets,
+/// an [`InvalidData`](io::ErrorKind::InvalidData) error is yielded and the stream ends.
+pub struct MinimizerStream<R, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+    reader: R,
+    state: RollingState<T, S>,
+    done: bool,
+}
+
+impl<R, T: PrimInt + Hash, S: BuildHasher> MinimizerStream<R, T, S> {
+    pub fn new(
+        reader: R,
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            reader,
+            state: RollingState::new(minimizer_size, width, hasher, encoding),
+            done: false,
+        }
+    }
+
+    /// Returns the reader, dropping the rolling state.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+// the reader is the only field that is polled, and it is required to be `Unpin`
+impl<R: Unpin, T: PrimInt + Hash, S: BuildHasher> Unpin for MinimizerStream<R, T, S> {}
+
+impl<R: AsyncBufRead + Unpin, T: PrimInt + Hash + 'static, S: BuildHasher> Stream
+    for MinimizerStream<R, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = io::Result<(T, usize)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            let buf = match Pin::new(&mut this.reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buf)) => buf,
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            };
+            if buf.is_empty() {
+                this.done = true;
+                break;
+            }
+            if buf.len() > usize::MAX - this.state.end {
+                this.done = true;
+                return Poll::Ready(Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the positions of the sequence overflow usize",
+                ))));
+            }
+            let mut consumed = 0;
+            let mut min_pos = None;
+            for &b in buf {
+                consumed += 1;
+                min_pos = this.state.push(b);
+                if min_pos.is_some() {
+                    break;
+                }
+            }
+            Pin::new(&mut this.reader).consume(consumed);
+            if let Some(min_pos) = min_pos {
+                return Poll::Ready(Some(Ok(min_pos)));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::Strand;
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use std::collections::{HashMap, VecDeque};
+
+/// The values yielded within the last `distance` bases, with their last position.
+pub(crate) struct RecentValues<T> {
+    pub(crate) distance: usize,
+    pub(crate) last_pos: HashMap<T, usize>,
+    pub(crate) recent: VecDeque<(usize, T)>,
+}
+
+impl<T: Copy + Eq + Hash> RecentValues<T> {
+    pub(crate) fn new(distance: usize) -> Self {
+        Self {
+            distance,
+            last_pos: HashMap::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Returns whether the value at the given position should be yielded, recording it if so.
+    /// Positions must be given in non-decreasing order.
+    #[inline]
+    pub(crate) fn accept(&mut self, value: T, pos: usize) -> bool {
+        while let Some(&(prev_pos, prev)) = self.recent.front() {
+            if prev_pos + self.distance > pos {
+                break;
+            }
+            self.recent.pop_front();
+            self.last_pos.remove(&prev);
+        }
+        if self.last_pos.contains_key(&value) {
+            return false;
+        }
+        self.last_pos.insert(value, pos);
+        self.recent.push_back((pos, value));
+        true
+    }
+}
+
+/// An iterator over minimizers and their positions, skipping a minimizer if the same value was yielded less than `distance` bases before.
+///
+/// Only the yielded occurrences are taken into account: in a tandem repeat, a value is yielded again once it is `distance` bases away from its last yielded occurrence.
+/// The memory is bounded by the number of values yielded within the last `distance` bases.
+pub struct SuppressIterator<I, T> {
+    pub(crate) inner: I,
+    pub(crate) recent: RecentValues<T>,
+}
+
+impl<I, T: Copy + Eq + Hash> SuppressIterator<I, T> {
+    pub fn new(inner: I, distance: usize) -> Self {
+        Self {
+            inner,
+            recent: RecentValues::new(distance),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash, I: Iterator<Item = (T, usize)>> Iterator for SuppressIterator<I, T> {
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .find(|&(min, pos)| self.recent.accept(min, pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<T: Copy + Eq + Hash, I: FusedIterator<Item = (T, usize)>> FusedIterator
+    for SuppressIterator<I, T>
+{
+}
+
+/// An iterator over canonical minimizers and their positions with their strand,
+/// skipping a minimizer if the same canonical value was yielded less than `distance` bases before.
+///
+/// Since canonical values are compared, an occurrence on either strand suppresses the following ones on both strands.
+pub struct CanonicalSuppressIterator<I, T> {
+    pub(crate) inner: I,
+    pub(crate) recent: RecentValues<T>,
+}
+
+impl<I, T: Copy + Eq + Hash> CanonicalSuppressIterator<I, T> {
+    pub fn new(inner: I, distance: usize) -> Self {
+        Self {
+            inner,
+            recent: RecentValues::new(distance),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash, I: Iterator<Item = (T, usize, Strand)>> Iterator
+    for CanonicalSuppressIterator<I, T>
+{
+    type Item = (T, usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .find(|&(min, pos, _)| self.recent.accept(min, pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<T: Copy + Eq + Hash, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator
+    for CanonicalSuppressIterator<I, T>
+{
+}
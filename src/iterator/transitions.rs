@@ -0,0 +1,70 @@
+use crate::Strand;
+use core::iter::FusedIterator;
+
+/// An iterator over minimizers and their positions, with the previous minimizer of each one.
+///
+/// It yields `(prev, min, pos)` where `prev` is `None` for the first minimizer, which makes it suitable to count the transitions between minimizers.
+pub struct TransitionIterator<I, T> {
+    pub(crate) inner: I,
+    pub(crate) prev: Option<T>,
+}
+
+impl<I, T> TransitionIterator<I, T> {
+    pub fn new(inner: I) -> Self {
+        Self { inner, prev: None }
+    }
+}
+
+impl<T: Copy, I: Iterator<Item = (T, usize)>> Iterator for TransitionIterator<I, T> {
+    type Item = (Option<T>, T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos) = self.inner.next()?;
+        Some((self.prev.replace(min), min, pos))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Copy, I: FusedIterator<Item = (T, usize)>> FusedIterator for TransitionIterator<I, T> {}
+
+/// An iterator over canonical minimizers and their positions with their strand,
+/// with the previous canonical minimizer of each one.
+///
+/// It yields `(prev, min, pos, strand)` where `prev` is `None` for the first minimizer.
+pub struct CanonicalTransitionIterator<I, T> {
+    pub(crate) inner: I,
+    pub(crate) prev: Option<T>,
+}
+
+impl<I, T> CanonicalTransitionIterator<I, T> {
+    pub fn new(inner: I) -> Self {
+        Self { inner, prev: None }
+    }
+}
+
+impl<T: Copy, I: Iterator<Item = (T, usize, Strand)>> Iterator
+    for CanonicalTransitionIterator<I, T>
+{
+    type Item = (Option<T>, T, usize, Strand);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, pos, strand) = self.inner.next()?;
+        Some((self.prev.replace(min), min, pos, strand))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Copy, I: FusedIterator<Item = (T, usize, Strand)>> FusedIterator
+    for CanonicalTransitionIterator<I, T>
+{
+}
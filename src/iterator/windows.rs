@@ -0,0 +1,351 @@
+use super::{check_hasher, check_sizes, mask, rc_encoding};
+use crate::Strand;
+use core::cmp::min;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use minimizer_queue::{DefaultHashBuilder, MinimizerQueue};
+use num_traits::{AsPrimitive, PrimInt};
+use std::collections::VecDeque;
+
+/// An iterator over the minimizer of every window of a sequence, yielding the index of the window,
+/// the minimizer and its offset in the window.
+///
+/// Unlike [`MinimizerIterator`](super::MinimizerIterator), consecutive windows sharing the same minimizer are not collapsed,
+/// so it yields exactly `seq.len() - base_width + 1` items.
+pub struct WindowMinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder>
+{
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> WindowMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for WindowMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (usize, T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.mmer = (self.mmer << 2)
+                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                    | (unsafe { self.encoding.get_unchecked(self.seq[i] as usize) }.as_());
+                self.queue.insert(self.mmer);
+            }
+        } else {
+            if self.end >= self.seq.len() {
+                return None;
+            }
+            self.mmer = ((self.mmer << 2) & self.mmer_mask)
+                | (unsafe { self.encoding.get_unchecked(self.seq[self.end] as usize) }.as_());
+            self.queue.insert(self.mmer);
+            self.end += 1;
+        }
+        let (x, offset) = self.queue.get_min_pos();
+        Some((self.end - self.base_width, x, offset))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = if self.queue.is_empty() {
+            (self.seq.len() + 1).saturating_sub(self.base_width)
+        } else {
+            self.seq.len() - self.end
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for WindowMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the minimizer of every k-mer of a sequence and its position, with `k = width + minimizer_size - 1`.
+///
+/// Each k-mer spans exactly one window, so it yields exactly `seq.len() - k + 1` items,
+/// consecutive k-mers sharing the same minimizer yielding it repeatedly.
+pub struct KmerMinimizerIterator<'a, T: PrimInt + Hash = u64, S: BuildHasher = DefaultHashBuilder> {
+    pub(crate) inner: WindowMinimizerIterator<'a, T, S>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> KmerMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        Self {
+            inner: WindowMinimizerIterator::new(seq, minimizer_size, width, hasher, encoding),
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator for KmerMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, x, offset) = self.inner.next()?;
+        Some((x, i + offset))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for KmerMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the minimizers of a sequence and their positions, only considering every `stride`-th window.
+///
+/// Every base is still rolled into the window, so the minimizer of each considered window is exact,
+/// but only the windows starting at `0, stride, 2 * stride, ...` are considered.
+/// At the end of the sequence, the last window is only considered if its start is a multiple of `stride`.
+/// As with [`MinimizerIterator`](super::MinimizerIterator), a minimizer selected by consecutive considered windows is only yielded once.
+pub struct StridedMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) inner: WindowMinimizerIterator<'a, T, S>,
+    pub(crate) stride: usize,
+    pub(crate) last_pos: Option<usize>,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> StridedMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        stride: usize,
+        hasher: S,
+        encoding: [u8; 256],
+    ) -> Self {
+        assert!(stride > 0, "stride must be ≥ 1");
+        Self {
+            inner: WindowMinimizerIterator::new(seq, minimizer_size, width, hasher, encoding),
+            stride,
+            last_pos: None,
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for StridedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (i, x, offset) = if self.last_pos.is_none() {
+                self.inner.next()?
+            } else {
+                self.inner.nth(self.stride - 1)?
+            };
+            let pos = i + offset;
+            if self.last_pos != Some(pos) {
+                self.last_pos = Some(pos);
+                return Some((x, pos));
+            }
+        }
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for StridedMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
+
+/// An iterator over the canonical minimizer of every window of a sequence, yielding the index of the window,
+/// the minimizer, its offset in the window and its [`Strand`].
+/// It requires an odd width to break ties between multiple minimizers.
+///
+/// Unlike [`CanonicalMinimizerIterator`](super::CanonicalMinimizerIterator), consecutive windows sharing the same minimizer are not collapsed,
+/// so it yields exactly `seq.len() - base_width + 1` items.
+pub struct CanonicalWindowMinimizerIterator<
+    'a,
+    T: PrimInt + Hash = u64,
+    S: BuildHasher = DefaultHashBuilder,
+> {
+    pub(crate) seq: &'a [u8],
+    pub(crate) queue: MinimizerQueue<T, S>,
+    pub(crate) width: usize,
+    pub(crate) mmer: T,
+    pub(crate) rc_mmer: T,
+    pub(crate) mmer_mask: T,
+    pub(crate) rc_mmer_shift: usize,
+    pub(crate) is_rc: VecDeque<bool>,
+    pub(crate) encoding: [u8; 256],
+    pub(crate) rc_encoding: [u8; 256],
+    pub(crate) base_width: usize,
+    pub(crate) end: usize,
+}
+
+impl<'a, T: PrimInt + Hash, S: BuildHasher> CanonicalWindowMinimizerIterator<'a, T, S> {
+    pub fn new(
+        seq: &'a [u8],
+        minimizer_size: usize,
+        width: u16,
+        hasher: S,
+        encoding: [u8; 256],
+        complement: [u8; 256],
+    ) -> Self {
+        check_sizes(minimizer_size, width);
+        check_hasher::<T, _>(2 * minimizer_size, &hasher);
+        let queue = MinimizerQueue::with_hasher(width, hasher);
+        let width = width as usize;
+        assert_eq!(
+            width % 2,
+            1,
+            "width must be odd to break ties between multiple minimizers"
+        );
+        let rc_encoding = rc_encoding(&encoding, &complement);
+        Self {
+            seq,
+            queue,
+            width,
+            mmer: T::zero(),
+            rc_mmer: T::zero(),
+            mmer_mask: mask(minimizer_size),
+            rc_mmer_shift: 2 * (minimizer_size - 1),
+            is_rc: VecDeque::with_capacity(width),
+            encoding,
+            rc_encoding,
+            base_width: width + minimizer_size - 1,
+            end: width + minimizer_size - 1,
+        }
+    }
+
+    #[inline]
+    fn window_not_canonical(&self) -> bool {
+        self.is_rc[self.width / 2]
+    }
+
+    /// Rolls the m-mers of both strands with the given base.
+    #[inline]
+    fn roll(&mut self, b: u8)
+    where
+        T: 'static,
+        u8: AsPrimitive<T>,
+    {
+        self.mmer = ((self.mmer << 2) & self.mmer_mask)
+            | (unsafe { self.encoding.get_unchecked(b as usize) }.as_());
+        self.rc_mmer = (self.rc_mmer >> 2)
+            | (unsafe { self.rc_encoding.get_unchecked(b as usize) }.as_() << self.rc_mmer_shift);
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> Iterator
+    for CanonicalWindowMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+    type Item = (usize, T, usize, Strand);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if self.base_width > self.seq.len() {
+                return None;
+            }
+            for i in 0..(self.base_width - self.width) {
+                self.roll(self.seq[i]);
+            }
+            for i in (self.base_width - self.width)..self.base_width {
+                self.roll(self.seq[i]);
+                let canonical_mmer = min(self.mmer, self.rc_mmer);
+                self.queue.insert(canonical_mmer);
+                self.is_rc.push_back(canonical_mmer == self.rc_mmer);
+            }
+        } else {
+            if self.end >= self.seq.len() {
+                return None;
+            }
+            self.roll(self.seq[self.end]);
+            let canonical_mmer = min(self.mmer, self.rc_mmer);
+            self.queue.insert(canonical_mmer);
+            self.is_rc.pop_front();
+            self.is_rc.push_back(canonical_mmer == self.rc_mmer);
+            self.end += 1;
+        }
+        let (x, offset) = if self.queue.multiple_mins() {
+            let (x, pos, tie) = self.queue.get_inner_min_pos();
+            tie.map_or((x, pos), |alt| {
+                if self.window_not_canonical() {
+                    alt
+                } else {
+                    (x, pos)
+                }
+            })
+        } else {
+            self.queue.get_min_pos()
+        };
+        Some((
+            self.end - self.base_width,
+            x,
+            offset,
+            Strand::from(self.is_rc[offset]),
+        ))
+    }
+}
+
+impl<'a, T: PrimInt + Hash + 'static, S: BuildHasher> FusedIterator
+    for CanonicalWindowMinimizerIterator<'a, T, S>
+where
+    u8: AsPrimitive<T>,
+{
+}
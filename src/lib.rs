@@ -1,15 +1,34 @@
 pub mod algorithm;
 mod builder;
+mod canonical;
+pub mod encoding;
+mod error;
+pub mod hash;
+mod index;
 pub mod iterator;
+pub mod queue;
+pub mod sink;
 
 pub use builder::MinimizerBuilder;
+pub use canonical::{
+    pack_key, unpack_key, CanonicalMinimizer, Strand, KEY_POS_BITS, KEY_VALUE_BITS,
+};
+pub use error::{BuildError, ExpansionError, KeyError};
+pub use index::MinimizerIndex;
+pub use iterator::BaseOrder;
 pub use minimizer_queue::DefaultHashBuilder;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::queue::{MinPosWindow, MinWindow};
     use biotest::Format;
+    use core::hash::BuildHasher;
+    use core::ops::ControlFlow;
     use nohash_hasher::BuildNoHashHasher;
+    use std::collections::VecDeque;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
 
     #[test]
     fn test_minimizer_iter() {
@@ -49,6 +68,51 @@ mod tests {
         assert_eq!(min_iter.next(), None);
     }
 
+    #[test]
+    fn test_minimizer_iter_pos_u32() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let positions: Vec<u32> = builder.clone().iter_pos_u32(seq).collect();
+        let expected: Vec<_> = builder.iter_pos(seq).map(|pos| pos as u32).collect();
+        assert_eq!(positions, expected);
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let positions: Vec<u32> = builder.clone().iter_pos_u32(seq).collect();
+        let expected: Vec<_> = builder.iter_pos(seq).map(|pos| pos as u32).collect();
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn test_minimizer_iter_kmers() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(11);
+        for ((kmer, pos), (min, min_pos)) in builder.clone().iter_kmers(seq).zip(builder.iter(seq))
+        {
+            assert_eq!(pos, min_pos);
+            assert_eq!(pack(kmer), min);
+        }
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(11);
+        for ((kmer, pos, strand), (min, min_pos, min_strand)) in
+            builder.clone().iter_kmers(seq).zip(builder.iter(seq))
+        {
+            assert_eq!((pos, strand), (min_pos, min_strand));
+            if strand.is_rc() {
+                assert_eq!(pack(&rc(kmer)), min);
+            } else {
+                assert_eq!(pack(kmer), min);
+            }
+        }
+    }
+
     #[test]
     fn test_mod_minimizer_iter() {
         let seq = b"TGATTGCACAATC";
@@ -57,89 +121,3515 @@ mod tests {
         let hasher = BuildNoHashHasher::<u64>::default();
         let mut min_iter = MinimizerBuilder::new_mod()
             .minimizer_size(minimizer_size)
-            .width(width)
-            .hasher(hasher)
+            .width(width)
+            .hasher(hasher)
+            .iter(seq);
+
+        assert_eq!(min_iter.next(), Some((0b00111110, 2))); // ATTG
+        assert_eq!(min_iter.next(), Some((0b01000100, 6))); // CACA
+        assert_eq!(min_iter.next(), Some((0b00010000, 7))); // ACAA
+        assert_eq!(min_iter.next(), Some((0b00001101, 9))); // AATC
+        assert_eq!(min_iter.next(), None);
+    }
+
+    #[test]
+    fn test_mod_minimizer_iter_pos() {
+        let seq = b"TGATTGCACAATC";
+        let minimizer_size = 4;
+        let width = 4;
+        let hasher = BuildNoHashHasher::<u64>::default();
+        let mut min_iter = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .hasher(hasher)
+            .iter_pos(seq);
+
+        assert_eq!(min_iter.next(), Some(2)); // ATTG
+        assert_eq!(min_iter.next(), Some(6)); // CACA
+        assert_eq!(min_iter.next(), Some(7)); // ACAA
+        assert_eq!(min_iter.next(), Some(9)); // AATC
+        assert_eq!(min_iter.next(), None);
+    }
+
+    #[test]
+    fn test_minimizer_tie_iter() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 5;
+        let width = 11;
+        let hasher = BuildNoHashHasher::<u64>::default();
+
+        let mut expected = Vec::new();
+        let mmers: Vec<u64> = seq.windows(minimizer_size).map(pack).collect();
+        for (start, window) in mmers.windows(width as usize).enumerate() {
+            let min = *window.iter().min().unwrap();
+            let pos = start + window.iter().position(|&x| x == min).unwrap();
+            let tie = window.iter().filter(|&&x| x == min).count() > 1;
+            if !matches!(expected.last(), Some(&(_, p, _)) if p == pos) {
+                expected.push((min, pos, tie));
+            }
+        }
+        let mins: Vec<_> = MinimizerBuilder::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .hasher(hasher)
+            .iter_ties(seq)
+            .collect();
+
+        assert_eq!(mins, expected);
+    }
+
+    #[test]
+    fn test_minimap2_iter() {
+        fn check(seq: &[u8], minimizer_size: usize, width: u16, expected: &[(u64, usize, bool)]) {
+            let mask = (1 << (2 * minimizer_size)) - 1;
+            let mins: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .minimap2_compat()
+                .iter(seq)
+                .map(|(min, pos, strand)| {
+                    (iterator::minimap2::hash64(min, mask), pos, strand.is_rc())
+                })
+                .collect();
+            assert_eq!(mins, expected);
+        }
+
+        // expected (hash, position of the first base, is_rc) computed with minimap2's mm_sketch
+        check(
+            b"TGATTGCACAATCGGATCCATTAGGCANNACGTTGACCATGACGATTACA",
+            5,
+            4,
+            &[
+                (0xf3, 3, true),
+                (0x173, 6, false),
+                (0x65, 9, false),
+                (0x22, 13, true),
+                (0x22, 14, false),
+                (0xf7, 18, false),
+                (0x12b, 31, false),
+                (0x17f, 34, false),
+                (0x71, 36, true),
+                (0x5f, 39, true),
+                (0x65, 42, true),
+            ],
+        );
+        check(
+            b"ACGGTAGGTTACCGAATGCAGGCATTCAGCTANNNCGATGCATGCATAGCTACGACTAGGCTTACGTAGCATCAGGGTACCTTGGGCTAC",
+            15,
+            10,
+            &[
+                (0x3a7c323, 0, false),
+                (0x4937db0, 2, true),
+                (0x4ed3d62, 35, false),
+                (0x11231aa3, 40, false),
+                (0xe61953b, 46, true),
+                (0x8d9bfd7, 51, false),
+                (0x177be4e, 55, false),
+                (0x340302, 59, false),
+                (0x16f757a, 67, false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_for_each_until() {
+        let seq = &gen_seq(10_000);
+        let mut mins = Vec::new();
+        let flow = MinimizerBuilder::<u64>::new().for_each_until(seq, |(min, pos)| {
+            mins.push((min, pos));
+            if pos >= 5_000 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        let expected: Vec<_> = MinimizerBuilder::<u64>::new()
+            .iter(seq)
+            .scan(false, |done, (min, pos)| {
+                (!*done).then(|| {
+                    *done = pos >= 5_000;
+                    (min, pos)
+                })
+            })
+            .collect();
+
+        assert_eq!(flow, ControlFlow::Break(()));
+        assert_eq!(mins, expected);
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .for_each_until(seq, |_| ControlFlow::Continue(())),
+            ControlFlow::Continue(())
+        );
+    }
+
+    #[test]
+    fn test_shared_minimizers() {
+        let seq = &gen_seq(10_000);
+        let seq_rc = &rc(seq);
+        let other = &gen_seq(10_000);
+
+        let mins: HashSet<u64> = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .iter(seq)
+            .map(|(min, _, _)| min)
+            .collect();
+        let shared = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .shared_minimizers(seq, seq_rc);
+        assert_eq!(shared.len(), mins.len());
+        assert_eq!(shared.into_iter().collect::<HashSet<_>>(), mins);
+
+        let mins_sub: HashSet<u64> = MinimizerBuilder::<u64, _>::new_mod()
+            .iter(&seq[2_000..3_000])
+            .map(|(min, _)| min)
+            .collect();
+        let mins_other: HashSet<u64> = MinimizerBuilder::<u64, _>::new_mod()
+            .iter(other)
+            .map(|(min, _)| min)
+            .collect();
+        let shared: HashSet<_> = MinimizerBuilder::<u64, _>::new_mod()
+            .shared_minimizers(other, &seq[2_000..3_000])
+            .into_iter()
+            .collect();
+        assert_eq!(shared, &mins_sub & &mins_other);
+    }
+
+    #[test]
+    fn test_jaccard() {
+        let seq = &gen_seq(10_000);
+        let seq_rc = &rc(seq);
+
+        assert_eq!(MinimizerBuilder::<u64>::new().jaccard(seq, seq), 1.0);
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .canonical()
+                .jaccard(seq, seq_rc),
+            1.0
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .canonical()
+                .jaccard(seq, seq_rc),
+            1.0
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64>::new().jaccard(b"ACGT", b"ACGT"),
+            0.0
+        );
+
+        let jaccard = MinimizerBuilder::<u64, _>::new_mod().jaccard(seq, &seq[5_000..]);
+        assert!(jaccard > 0.4 && jaccard < 0.6);
+    }
+
+    #[test]
+    fn test_select_max() {
+        let seq = b"TGATTGCACAATC";
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(3)
+            .width(4)
+            .hasher(BuildNoHashHasher::<u64>::default());
+        let mut max_iter = builder.clone().select_max().iter(seq);
+
+        assert_eq!(max_iter.next(), Some((0b111110, 3))); // TTG
+        assert_eq!(max_iter.next(), Some((0b111001, 4))); // TGC
+        assert_eq!(max_iter.next(), Some((0b100100, 5))); // GCA
+        assert_eq!(max_iter.next(), Some((0b010001, 6))); // CAC
+        assert_eq!(max_iter.next(), Some((0b010000, 8))); // CAA
+        assert_eq!(max_iter.next(), None);
+
+        // selecting the maximizers of negated hashes gives back the minimizers
+        let seq = &gen_seq(10_000);
+        assert!(builder
+            .clone()
+            .select_max()
+            .select_max()
+            .iter(seq)
+            .eq(builder.iter(seq)));
+
+        let seq_rc = &rc(seq);
+        let max_values = |seq: &[u8]| -> HashSet<u64> {
+            MinimizerBuilder::<u64>::new()
+                .canonical()
+                .select_max()
+                .iter(seq)
+                .map(|(max, _, _)| max)
+                .collect()
+        };
+        assert_eq!(max_values(seq), max_values(seq_rc));
+    }
+
+    #[test]
+    fn test_iterator_debug() {
+        let seq = b"TGATTGCACAATC";
+        let mut min_iter = MinimizerBuilder::<u64>::new()
+            .minimizer_size(3)
+            .width(4)
+            .hasher(BuildNoHashHasher::<u64>::default())
+            .iter(seq);
+        assert_eq!(
+            format!("{min_iter:?}"),
+            "MinimizerIterator { seq_len: 13, width: 4, mmer: 0, base_width: 6, min_pos: (0, 0), end: 6, queue_is_empty: true, .. }"
+        );
+        min_iter.next();
+        assert_eq!(
+            format!("{min_iter:?}"),
+            "MinimizerIterator { seq_len: 13, width: 4, mmer: 62, base_width: 6, min_pos: (15, 2), end: 6, queue_is_empty: false, .. }"
+        );
+
+        let mod_iter = MinimizerBuilder::<u64, _>::new_mod()
+            .canonical()
+            .iter_pos(seq);
+        assert!(
+            format!("{mod_iter:?}").starts_with("CanonicalModSamplingPosIterator { seq_len: 13,")
+        );
+    }
+
+    #[test]
+    fn test_expected_density() {
+        let seq = &gen_seq(1_000_000);
+        for (minimizer_size, width) in [(21, 11), (31, 5), (61, 11)] {
+            let builder = MinimizerBuilder::<u128>::new()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            assert!((builder.expected_density() - builder.density(seq)).abs() < 0.005);
+
+            let builder = MinimizerBuilder::<u128, _>::new_mod()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            assert!((builder.expected_density() - builder.density(seq)).abs() < 0.005);
+        }
+        for t in [5, 15] {
+            let builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(21)
+                .width(11)
+                .t(t);
+            assert!((builder.expected_density() - builder.density(seq)).abs() < 0.005);
+        }
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(21)
+                .width(11)
+                .expected_density(),
+            3.0 / 23.0
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64>::new().width(11).expected_density(),
+            1.0 / 6.0
+        );
+        assert_eq!(MinimizerBuilder::<u64>::new().density(b"ACGT"), 0.0);
+    }
+
+    #[test]
+    fn test_mod_minimizer_t() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        let width = 11;
+
+        let mins: Vec<_> = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter(seq)
+            .collect();
+        let mins_t: Vec<_> = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .t(4 + (minimizer_size - 4) % width as usize)
+            .iter(seq)
+            .collect();
+        assert_eq!(mins, mins_t);
+
+        let mins: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter_pos(seq)
+            .collect();
+        let mins_t: Vec<_> = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .t(minimizer_size)
+            .iter_pos(seq)
+            .collect();
+        assert_eq!(mins, mins_t);
+    }
+
+    #[test]
+    #[should_panic(expected = "t must be ≤ minimizer_size")]
+    fn test_mod_minimizer_t_too_large() {
+        MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .t(22)
+            .iter_pos(b"ACGT");
+    }
+
+    #[test]
+    fn test_both_strands_minimizer_iter() {
+        let minimizer_size = 21;
+        let width = 11;
+
+        for seq in [gen_seq(10_000), b"AAG".repeat(100)] {
+            let seq_len = seq.len();
+            let seq_rc = &rc(&seq);
+            let both: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .iter_both_strands(&seq)
+                .collect();
+            let mut fwd: Vec<_> = both.iter().map(|&(min, pos, _, _)| (min, pos)).collect();
+            fwd.dedup();
+            let mut rev: Vec<_> = both.iter().map(|&(_, _, min, pos)| (min, pos)).collect();
+            rev.dedup();
+
+            let mins: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .iter(&seq)
+                .collect();
+            let mut mins_rc: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .iter(seq_rc)
+                .map(|(min, pos)| (min, seq_len - pos - minimizer_size))
+                .collect();
+            mins_rc.reverse();
+
+            assert_eq!(fwd, mins);
+            assert_eq!(rev, mins_rc);
+        }
+    }
+
+    #[test]
+    fn test_minimizer_iter_resume() {
+        let seq = &gen_seq(10_000);
+        let mins: Vec<_> = MinimizerBuilder::<u64>::new().iter(seq).collect();
+
+        for split in [0, 1, 100] {
+            let mut min_iter = MinimizerBuilder::<u64>::new().iter(seq);
+            let mut resumed: Vec<_> = min_iter.by_ref().take(split).collect();
+            let state = min_iter.save_state();
+            drop(min_iter);
+            resumed.extend(MinimizerBuilder::<u64>::new().resume(seq, state));
+            assert_eq!(resumed, mins);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(MinimizerBuilder::<u64>::new().validate(), Ok(()));
+        assert_eq!(
+            MinimizerBuilder::<u32>::new()
+                .canonical()
+                .width(10)
+                .validate(),
+            Err(vec![
+                BuildError::MinimizerSizeTooLarge {
+                    minimizer_size: 21,
+                    max_size: 16
+                },
+                BuildError::EvenWidth { width: 10 }
+            ])
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(3)
+                .validate(),
+            Err(vec![BuildError::MinimizerSizeTooSmall {
+                minimizer_size: 3,
+                r: 4
+            }])
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .canonical()
+                .minimizer_size(21)
+                .width(12)
+                .t(10)
+                .validate(),
+            Err(vec![
+                BuildError::EvenWidth { width: 12 },
+                BuildError::NonCanonicalT {
+                    t: 10,
+                    minimizer_size: 21,
+                    width: 12
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(iterator::fingerprint(0b001111u64), 0x13168ca1c068ddaa);
+        assert_eq!(iterator::fingerprint(0b001111u128), 0x13168ca1c068ddaa);
+        assert_eq!(iterator::fingerprint(0b001111i64), 0x13168ca1c068ddaa);
+        assert_eq!(
+            iterator::fingerprint(-1i64),
+            iterator::fingerprint(u64::MAX)
+        );
+        assert_eq!(
+            iterator::fingerprint(i128::MIN),
+            iterator::fingerprint(1u128 << 127)
+        );
+
+        let seq = &gen_seq(10_000);
+        let fingerprints: HashMap<u64, u64> = MinimizerBuilder::<u64>::new()
+            .iter_fingerprint(seq)
+            .map(|(min, fingerprint, _)| (min, fingerprint))
+            .collect();
+        for (min, fingerprint, _) in MinimizerBuilder::<u64>::new()
+            .hasher(BuildNoHashHasher::<u64>::default())
+            .iter_fingerprint(seq)
+        {
+            if let Some(&f) = fingerprints.get(&min) {
+                assert_eq!(f, fingerprint);
+            }
+        }
+    }
+
+    #[test]
+    fn test_retain_values() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(11).width(7);
+        let all: Vec<_> = builder.clone().iter(seq).collect();
+        let set: Arc<HashSet<u64>> = Arc::new(all.iter().step_by(3).map(|&(min, _)| min).collect());
+        let retained: Vec<_> = builder.retain_values(seq, Arc::clone(&set)).collect();
+        let expected: Vec<_> = all
+            .into_iter()
+            .filter(|(min, _)| set.contains(min))
+            .collect();
+        assert!(!retained.is_empty());
+        assert_eq!(retained, expected);
+    }
+
+    #[test]
+    fn test_positions_from_end() {
+        let seq = &gen_seq(1_000);
+        let minimizer_size = 21;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(11);
+        assert!(builder.clone().positions_from_end(seq).eq(builder
+            .clone()
+            .iter(seq)
+            .map(|(min, pos)| (min, seq.len() - pos - minimizer_size))));
+        assert!(builder.clone().iter_pos_from_end(seq).eq(builder
+            .iter_pos(seq)
+            .map(|pos| seq.len() - pos - minimizer_size)));
+
+        // the canonical minimizers of the reverse complement are found at the same positions from the end
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(11);
+        let mut mins_rc: Vec<_> = builder
+            .clone()
+            .positions_from_end(&rc(seq))
+            .map(|(min, pos, strand)| (min, pos, Strand::from(!strand.is_rc())))
+            .collect();
+        mins_rc.reverse();
+        assert_eq!(builder.iter(seq).collect::<Vec<_>>(), mins_rc);
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .positions_from_end(b"ACGT")
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_canonical_minimizer_iter_palindrome() {
+        // ACGT and GATC are palindromes
+        let seq = &[
+            gen_seq(200),
+            b"ACGT".repeat(10),
+            gen_seq(200),
+            b"GATC".to_vec(),
+            gen_seq(200),
+        ]
+        .concat();
+        let minimizer_size = 4;
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(11);
+        let mut palindromes = 0;
+        for ((min, pos, strand, is_palindrome), expected) in builder
+            .clone()
+            .iter_with_palindrome(seq)
+            .zip(builder.iter(seq))
+        {
+            assert_eq!((min, pos, strand), expected);
+            let mmer = &seq[pos..(pos + minimizer_size)];
+            assert_eq!(is_palindrome, mmer == rc(mmer).as_slice());
+            if is_palindrome {
+                assert_eq!(strand, Strand::Reverse);
+                palindromes += 1;
+            }
+        }
+        assert!(palindromes > 0);
+
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        assert!(builder
+            .iter_with_palindrome(seq)
+            .all(|(_, _, _, is_palindrome)| !is_palindrome));
+    }
+
+    #[test]
+    fn test_min_distance() {
+        let seq = &gen_seq(5_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        assert!(builder
+            .clone()
+            .min_distance(seq, 0)
+            .eq(mins.iter().copied()));
+        assert!(builder
+            .clone()
+            .min_distance(seq, 1)
+            .eq(mins.iter().copied()));
+
+        let distance = 25;
+        let kept: Vec<_> = builder.clone().min_distance(seq, distance).collect();
+        let mut expected = vec![mins[0]];
+        for &(min, pos) in mins.iter().skip(1) {
+            if pos - expected.last().unwrap().1 >= distance {
+                expected.push((min, pos));
+            }
+        }
+        assert_eq!(kept, expected);
+        assert!(kept.len() < mins.len() / 2);
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let kept: Vec<_> = builder.min_distance(seq, distance).collect();
+        assert!(kept
+            .windows(2)
+            .all(|pair| pair[1].1 - pair[0].1 >= distance));
+
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        let kept: Vec<_> = builder.clone().min_distance(seq, distance).collect();
+        assert_eq!(kept[0], builder.iter(seq).next().unwrap());
+        assert!(kept
+            .windows(2)
+            .all(|pair| pair[1].1.abs_diff(pair[0].1) >= distance));
+    }
+
+    #[test]
+    fn test_explain_selection() {
+        let seq = &gen_seq(3).repeat(1000);
+        for width in [11, 12] {
+            let mut iter = MinimizerBuilder::<u64>::new()
+                .canonical()
+                .even_width_canonical()
+                .minimizer_size(5)
+                .width(width)
+                .iter(seq);
+            assert_eq!(iter.explain_selection(), None);
+            let mut reasons = HashSet::new();
+            while let Some(min) = iter.next() {
+                let info = iter.explain_selection().unwrap();
+                assert_eq!(info.chosen, min);
+                assert!(info.leftmost >= info.window_start);
+                match info.reason {
+                    iterator::SelectionReason::Unique => assert_eq!(info.rightmost, None),
+                    iterator::SelectionReason::TieCanonical => {
+                        assert!(info.rightmost.unwrap() > info.leftmost);
+                        assert_eq!(min.1, info.leftmost);
+                    }
+                    iterator::SelectionReason::TieNotCanonical => {
+                        assert!(info.rightmost.unwrap() < info.window_start + width as usize);
+                        assert_eq!(Some(min.1), info.rightmost);
+                    }
+                }
+                reasons.insert(info.reason);
+            }
+            assert!(reasons.contains(&iterator::SelectionReason::TieCanonical));
+            assert!(reasons.contains(&iterator::SelectionReason::TieNotCanonical));
+        }
+
+        let seq = &gen_seq(1_000);
+        let mut iter = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(21)
+            .width(11)
+            .iter(seq);
+        while let Some(min) = iter.next() {
+            let info = iter.explain_selection().unwrap();
+            assert_eq!(info.reason, iterator::SelectionReason::Unique);
+            assert_eq!((info.chosen, info.leftmost), (min, min.1));
+        }
+    }
+
+    #[test]
+    fn test_minimizer_iter_joined() {
+        let seq = &gen_seq(1_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let expected: Vec<_> = builder.clone().iter(seq).collect();
+        for split in [0, 1, 20, 30, 31, 500, 999, 1000] {
+            let (first, second) = seq.split_at(split);
+            assert_eq!(
+                builder
+                    .clone()
+                    .iter_joined(first, second)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+        assert_eq!(builder.clone().iter_joined(b"", b"").count(), 0);
+        assert_eq!(builder.iter_joined(&seq[..15], &seq[15..30]).count(), 0);
+    }
+
+    #[test]
+    fn test_key128() {
+        assert_eq!(
+            iterator::fingerprint128(0b001111u64),
+            iterator::fingerprint128(0b001111u128)
+        );
+        assert_eq!(
+            iterator::fingerprint128(-1i32),
+            iterator::fingerprint128(u32::MAX)
+        );
+        let keys: HashSet<u128> = (0..(1u64 << 16)).map(iterator::fingerprint128).collect();
+        assert_eq!(keys.len(), 1 << 16);
+
+        let seq = &gen_seq(1_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        for ((key, min, pos), expected) in builder.clone().key128(seq).zip(builder.iter(seq)) {
+            assert_eq!((min, pos), expected);
+            assert_eq!(key, iterator::fingerprint128(min));
+        }
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        assert!(builder
+            .clone()
+            .key128(seq)
+            .map(|(_, min, pos)| (min, pos))
+            .eq(builder.iter(seq)));
+
+        // both strands share the same key
+        let builder = MinimizerBuilder::<u128>::new()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        let forward: HashSet<u128> = builder.clone().key128(seq).map(|(key, ..)| key).collect();
+        let reverse: HashSet<u128> = builder
+            .clone()
+            .key128(&rc(seq))
+            .map(|(key, ..)| key)
+            .collect();
+        assert_eq!(forward, reverse);
+        for ((key, min, pos, strand), expected) in
+            builder.clone().key128(seq).zip(builder.iter(seq))
+        {
+            assert_eq!((min, pos, strand), expected);
+            assert_eq!(key, iterator::fingerprint128(min));
+        }
+    }
+
+    #[test]
+    fn test_max_minimizer_size() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 32;
+        for (min, pos) in MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(5)
+            .iter(seq)
+        {
+            assert_eq!(min, pack(&seq[pos..(pos + minimizer_size)]));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "minimizer_size must be ≤ 16, got 20")]
+    fn test_minimizer_size_too_large() {
+        iterator::MinimizerPosIterator::<u32>::new(
+            b"ACGT",
+            20,
+            11,
+            DefaultHashBuilder::default(),
+            [0; 256],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "minimizer_size must be ≥ 1")]
+    fn test_zero_minimizer_size() {
+        MinimizerBuilder::<u64>::new().minimizer_size(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "width must be ≥ 1")]
+    fn test_zero_width() {
+        MinimizerBuilder::<u64, _>::new_mod().width(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "width must be ≥ 1")]
+    fn test_zero_width_iter() {
+        iterator::MinimizerIterator::<u64>::new(
+            b"ACGT",
+            2,
+            0,
+            DefaultHashBuilder::default(),
+            [0; 256],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "minimizer_size must be ≥ 1")]
+    fn test_zero_minimizer_size_iter() {
+        iterator::CanonicalModSamplingIterator::<u64>::new(
+            b"ACGT",
+            0,
+            3,
+            4,
+            DefaultHashBuilder::default(),
+            [0; 256],
+            iterator::COMPLEMENT,
+        );
+    }
+
+    #[test]
+    fn test_fused_iter() {
+        fn check<I: core::iter::FusedIterator>(mut iter: I) {
+            while iter.next().is_some() {}
+            for _ in 0..3 {
+                assert!(iter.next().is_none());
+            }
+        }
+
+        for seq in [&gen_seq(1_000)[..], b"ACGT"] {
+            let builder = MinimizerBuilder::<u64>::new().minimizer_size(7).width(5);
+            check(builder.clone().iter(seq));
+            check(builder.clone().iter_pos(seq));
+            check(builder.clone().iter_ties(seq));
+            check(builder.clone().iter_both_strands(seq));
+            check(builder.clone().iter_circular(seq));
+            check(builder.clone().iter_windows(seq));
+            check(builder.clone().iter_fingerprint(seq));
+            check(builder.clone().iter_kmers(seq));
+            check(builder.clone().iter_pos_centered(seq));
+            check(builder.clone().iter_pairs((seq, seq), true));
+            check(builder.clone().nthash().iter(seq));
+            check(builder.clone().canonical().iter(seq));
+            check(builder.clone().canonical().iter_pos(seq));
+            check(builder.clone().canonical().nthash().iter(seq));
+            check(builder.clone().minimap2_compat().iter(seq));
+            let builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(7)
+                .width(5);
+            check(builder.clone().iter(seq));
+            check(builder.clone().iter_pos(seq));
+            check(builder.clone().canonical().iter(seq));
+            check(builder.canonical().iter_pos(seq));
+        }
+    }
+
+    fn gen_seq(len: usize) -> Vec<u8> {
+        let mut rng = biotest::rand();
+        let mut seq = Vec::with_capacity(len);
+        let generator = biotest::Sequence::builder()
+            .sequence_len(len)
+            .build()
+            .unwrap();
+        generator.record(&mut seq, &mut rng).unwrap();
+        seq
+    }
+
+    fn pack(mmer: &[u8]) -> u64 {
+        mmer.iter().fold(0, |acc, &b| {
+            (acc << 2)
+                | match b {
+                    b'A' => 0,
+                    b'C' => 1,
+                    b'G' => 2,
+                    _ => 3,
+                }
+        })
+    }
+
+    fn rc(seq: &[u8]) -> Vec<u8> {
+        seq.iter()
+            .rev()
+            .map(|&b| match b {
+                b'A' => b'T',
+                b'a' => b't',
+                b'T' => b'A',
+                b't' => b'a',
+                b'C' => b'G',
+                b'c' => b'g',
+                b'G' => b'C',
+                b'g' => b'c',
+                b => b,
+            })
+            .collect()
+    }
+
+    /// A naive queue scanning the whole window, keeping the leftmost minimum.
+    #[derive(Clone)]
+    struct NaiveQueue {
+        window: VecDeque<(u64, u64)>,
+        width: usize,
+    }
+
+    impl MinPosWindow<u64> for NaiveQueue {
+        fn width(&self) -> usize {
+            self.width
+        }
+
+        fn is_empty(&self) -> bool {
+            self.window.is_empty()
+        }
+
+        fn insert(&mut self, x: u64) {
+            if self.window.len() == self.width {
+                self.window.pop_front();
+            }
+            let hash = DefaultHashBuilder::default().hash_one(x);
+            self.window.push_back((hash, x));
+        }
+
+        fn min_pos(&self) -> usize {
+            self.get_min_pos().1
+        }
+
+        fn multiple_mins(&self) -> bool {
+            self.get_inner_min_pos().2.is_some()
+        }
+
+        fn inner_min_pos(&self) -> (usize, Option<usize>) {
+            let (_, pos, tie) = self.get_inner_min_pos();
+            (pos, tie.map(|(_, pos)| pos))
+        }
+    }
+
+    impl MinWindow<u64> for NaiveQueue {
+        fn get_min_pos(&self) -> (u64, usize) {
+            let (pos, &(_, x)) = self
+                .window
+                .iter()
+                .enumerate()
+                .min_by_key(|&(pos, &(hash, _))| (hash, pos))
+                .unwrap();
+            (x, pos)
+        }
+
+        fn get_inner_min_pos(&self) -> (u64, usize, Option<(u64, usize)>) {
+            let (x, pos) = self.get_min_pos();
+            let hash = self.window[pos].0;
+            let last = (0..self.window.len())
+                .rev()
+                .find(|&i| self.window[i].0 == hash)
+                .unwrap();
+            (x, pos, (last != pos).then(|| (self.window[last].1, last)))
+        }
+    }
+
+    #[test]
+    fn test_centered_minimizer_iter_pos() {
+        let seq = &gen_seq(2_000);
+        let minimizer_size = 7;
+        let width = 10;
+        let base_width = minimizer_size + width as usize - 1;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+
+        let mut expected = Vec::new();
+        let mut last_pos = None;
+        for start in 0..=(seq.len() - base_width) {
+            let pos = start
+                + builder
+                    .clone()
+                    .iter_pos(&seq[start..(start + base_width)])
+                    .next()
+                    .unwrap();
+            if last_pos != Some(pos) {
+                expected.push(pos as i64 - (start + width as usize / 2) as i64);
+                last_pos = Some(pos);
+            }
+        }
+        let offsets: Vec<_> = builder.iter_pos_centered(seq).collect();
+        assert_eq!(offsets, expected);
+    }
+
+    #[test]
+    fn test_window_minimizer_iter() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        let width = 11;
+        let base_width = minimizer_size + width as usize - 1;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+
+        let windows: Vec<_> = builder.clone().iter_windows(seq).collect();
+        assert_eq!(windows.len(), seq.len() - base_width + 1);
+        assert_eq!(
+            builder.clone().iter_windows(seq).size_hint().0,
+            windows.len()
+        );
+        let mut collapsed: Vec<_> = windows
+            .iter()
+            .map(|&(i, min, offset)| (min, i + offset))
+            .collect();
+        collapsed.dedup_by_key(|&mut (_, pos)| pos);
+        assert_eq!(collapsed, builder.clone().iter(seq).collect::<Vec<_>>());
+
+        let builder = builder.canonical();
+        let windows: Vec<_> = builder.clone().iter_windows(seq).collect();
+        assert_eq!(windows.len(), seq.len() - base_width + 1);
+        let mut collapsed: Vec<_> = windows
+            .iter()
+            .map(|&(i, min, offset, strand)| (min, i + offset, strand))
+            .collect();
+        collapsed.dedup_by_key(|&mut (_, pos, _)| pos);
+        assert_eq!(collapsed, builder.iter(seq).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_strided_minimizer_iter() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        for stride in [1, 3, 11, 50] {
+            let mut expected: Vec<_> = builder
+                .clone()
+                .iter_windows(seq)
+                .step_by(stride)
+                .map(|(i, min, offset)| (min, i + offset))
+                .collect();
+            expected.dedup_by_key(|&mut (_, pos)| pos);
+            let mins: Vec<_> = builder.clone().iter_strided(seq, stride).collect();
+            assert_eq!(mins, expected);
+        }
+        assert_eq!(
+            builder.clone().iter_strided(seq, 1).collect::<Vec<_>>(),
+            builder.iter(seq).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_count() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        assert_eq!(builder.clone().count(seq), builder.iter(seq).count());
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        assert_eq!(builder.clone().count(seq), builder.iter(seq).count());
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        assert_eq!(builder.clone().count(seq), builder.iter(seq).count());
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        assert_eq!(builder.clone().count(&seq[..10]), 0);
+        assert_eq!(builder.clone().count(seq), builder.iter(seq).count());
+    }
+
+    #[test]
+    fn test_sorted_by_value() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mut expected: Vec<_> = builder.clone().iter(seq).collect();
+        expected.sort_by_key(|&(min, _)| min);
+        assert_eq!(builder.sorted_by_value(seq), expected);
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        let mut expected: Vec<_> = builder.clone().iter(seq).collect();
+        expected.sort_by_key(|&(min, _, _)| min);
+        assert_eq!(builder.sorted_by_value(seq), expected);
+    }
+
+    #[test]
+    fn test_mod_minimizer_t_equals_minimizer_size() {
+        let seq = &gen_seq(100_000);
+        // (minimizer_size - r) < width, so the default t is minimizer_size
+        for (minimizer_size, width) in [(10, 11), (4, 5), (14, 21)] {
+            let builder = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            let mod_builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            assert_eq!(
+                mod_builder.clone().iter(seq).collect::<Vec<_>>(),
+                builder.clone().iter(seq).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                mod_builder.clone().iter_pos(seq).collect::<Vec<_>>(),
+                builder.clone().iter_pos(seq).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                mod_builder.canonical().iter(seq).collect::<Vec<_>>(),
+                builder.canonical().iter(seq).collect::<Vec<_>>()
+            );
+        }
+        // explicitly setting t = minimizer_size
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mod_builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11)
+            .t(21);
+        assert_eq!(
+            mod_builder.iter(seq).collect::<Vec<_>>(),
+            builder.iter(seq).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_minimizer_iter_range() {
+        let seq = &gen_seq(100_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let all: Vec<_> = builder.clone().iter(seq).collect();
+        for (start, end) in [
+            (0, 0),
+            (0, 1_000),
+            (5, 17),
+            (50_000, 51_000),
+            (99_000, 100_000),
+            (0, 100_000),
+        ] {
+            let expected: Vec<_> = all
+                .iter()
+                .copied()
+                .filter(|&(_, pos)| start <= pos && pos < end)
+                .collect();
+            let mins: Vec<_> = builder.clone().iter_range(seq, start, end).collect();
+            assert_eq!(mins, expected, "range {start}..{end}");
+        }
+    }
+
+    #[test]
+    fn test_minimizer_iter_raw_codes() {
+        let seq = &gen_seq(10_000);
+        let codes: Vec<u8> = seq
+            .iter()
+            .map(|&b| match b {
+                b'A' => 0,
+                b'C' => 1,
+                b'G' => 2,
+                _ => 3,
+            })
+            .collect();
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        assert_eq!(
+            builder.clone().iter_raw_codes(&codes).collect::<Vec<_>>(),
+            builder.iter(seq).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_minimizer_iter_gaps() {
+        let seq = &gen_seq(10_000);
+        let width = 11;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(21)
+            .width(width);
+        let positions: Vec<_> = builder.clone().iter_pos(seq).collect();
+        let gaps: Vec<_> = builder.iter_gaps(seq).collect();
+        assert_eq!(gaps.len(), positions.len());
+        assert_eq!(gaps[0], positions[0]);
+        let mut sum = 0;
+        for (&gap, &pos) in gaps.iter().zip(&positions) {
+            sum += gap;
+            assert_eq!(sum, pos);
+        }
+        assert!(gaps[1..]
+            .iter()
+            .all(|&gap| 1 <= gap && gap <= width as usize));
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(width);
+        let positions: Vec<_> = builder.clone().iter_pos(seq).collect();
+        let sum: usize = builder.iter_gaps(seq).sum();
+        assert_eq!(sum, *positions.last().unwrap());
+    }
+
+    #[test]
+    fn test_minimizer_iter_with_secondary_hasher() {
+        use core::hash::BuildHasher;
+
+        let seq = &gen_seq(10_000);
+        let secondary_hasher = DefaultHashBuilder::default();
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(21)
+            .width(11)
+            .hasher(BuildNoHashHasher::<u64>::default());
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let mins_hashed: Vec<_> = builder
+            .iter_with_secondary_hasher(seq, secondary_hasher)
+            .collect();
+        assert_eq!(mins_hashed.len(), mins.len());
+        for (&(min, pos), &(min2, pos2, hash)) in mins.iter().zip(&mins_hashed) {
+            assert_eq!((min, pos), (min2, pos2));
+            assert_eq!(hash, secondary_hasher.hash_one(min));
+        }
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        assert!(builder
+            .clone()
+            .iter_with_secondary_hasher(seq, secondary_hasher)
+            .map(|(min, pos, _)| (min, pos))
+            .eq(builder.iter(seq)));
+    }
+
+    #[test]
+    fn test_anchors() {
+        let seq = &gen_seq(20_000);
+        let (seq_a, seq_b) = (&seq[..12_000], &seq[8_000..]);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let anchors = builder.clone().anchors(seq_a, seq_b);
+        let mut expected = Vec::new();
+        for (min_a, pos_a) in builder.clone().iter(seq_a) {
+            for (min_b, pos_b) in builder.clone().iter(seq_b) {
+                if min_a == min_b {
+                    expected.push((pos_a, pos_b));
+                }
+            }
+        }
+        assert_eq!(anchors, expected);
+        // the shared region yields a diagonal of anchors
+        assert!(anchors
+            .iter()
+            .filter(|&&(pos_a, _)| (8_100..11_900).contains(&pos_a))
+            .all(|&(pos_a, pos_b)| pos_a == pos_b + 8_000));
+
+        let seq_b_rc = &rc(seq_b);
+        let builder = builder.canonical();
+        let anchors = builder.anchors(seq_a, seq_b_rc);
+        for &(pos_a, pos_b, same_strand) in &anchors {
+            if (8_100..11_900).contains(&pos_a) {
+                assert_eq!(pos_a - 8_000, seq_b.len() - pos_b - 21);
+                assert!(!same_strand);
+            }
+        }
+        assert!(anchors.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_binary_minimizer_iter() {
+        let seq = &gen_seq(10_000);
+        let ry = |b: &u8| match b {
+            b'A' | b'G' => 0u64,
+            _ => 1,
+        };
+        let mut encoding = [0; 256];
+        encoding[b'C' as usize] = 1;
+        encoding[b'T' as usize] = 1;
+        for minimizer_size in [21, 64] {
+            let width = 11;
+            let mins: Vec<_> = iterator::BinaryMinimizerIterator::<u64>::new(
+                seq,
+                minimizer_size,
+                width,
+                DefaultHashBuilder::default(),
+                encoding,
+            )
+            .collect();
+            assert!(!mins.is_empty());
+            for &(min, pos) in &mins {
+                let expected = seq[pos..(pos + minimizer_size)]
+                    .iter()
+                    .fold(0, |mmer, b| (mmer << 1) | ry(b));
+                assert_eq!(min, expected);
+            }
+        }
+        for minimizer_size in [21, 64] {
+            let builder = MinimizerBuilder::<u64>::new()
+                .encoding(0, 1, 0, 1)
+                .binary()
+                .minimizer_size(minimizer_size)
+                .width(11);
+            assert_eq!(builder.validate(), Ok(()));
+            assert_eq!(
+                builder.iter(seq).collect::<Vec<_>>(),
+                iterator::BinaryMinimizerIterator::<u64>::new(
+                    seq,
+                    minimizer_size,
+                    11,
+                    DefaultHashBuilder::default(),
+                    encoding,
+                )
+                .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_minimizer_stream() {
+        use futures::io::{BufReader, Cursor};
+        use futures::{executor::block_on, TryStreamExt};
+
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let expected: Vec<_> = builder.clone().iter(seq).collect();
+        // small buffers make the window span several chunks
+        for capacity in [1, 7, 64, 20_000] {
+            let reader = BufReader::with_capacity(capacity, Cursor::new(seq));
+            let mins: Vec<_> =
+                block_on(builder.clone().stream(reader).try_collect::<Vec<_>>()).unwrap();
+            assert_eq!(mins, expected);
+        }
+        let reader = Cursor::new(&seq[..20]);
+        let mins: Vec<_> = block_on(builder.stream(reader).try_collect::<Vec<_>>()).unwrap();
+        assert!(mins.is_empty());
+    }
+
+    #[test]
+    fn test_lexicographic_minimizer_iter() {
+        let seq = &gen_seq(10_000);
+        for (minimizer_size, width) in [(21, 11), (32, 5), (5, 31)] {
+            let builder = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            let mins: Vec<_> = builder.clone().lexicographic().iter(seq).collect();
+            assert_eq!(
+                mins,
+                builder
+                    .clone()
+                    .hasher(BuildNoHashHasher::<u64>::default())
+                    .iter(seq)
+                    .collect::<Vec<_>>()
+            );
+            let mins_u128: Vec<_> = MinimizerBuilder::<u128>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .lexicographic()
+                .iter(seq)
+                .map(|(min, pos)| (min as u64, pos))
+                .collect();
+            assert_eq!(mins_u128, mins);
+            if minimizer_size < 32 {
+                let mins_i64: Vec<_> = MinimizerBuilder::<i64>::new()
+                    .minimizer_size(minimizer_size)
+                    .width(width)
+                    .lexicographic()
+                    .iter(seq)
+                    .map(|(min, pos)| (min as u64, pos))
+                    .collect();
+                assert_eq!(mins_i64, mins);
+            }
+            for &(min, pos) in &mins {
+                let start = pos.saturating_sub(width as usize - 1);
+                let end = (pos + 1).min(seq.len() - minimizer_size - width as usize + 2);
+                assert!((start..end).any(|window| {
+                    (window..(window + width as usize))
+                        .all(|i| pack(&seq[i..(i + minimizer_size)]) >= min)
+                }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncating_hasher() {
+        use core::hash::{BuildHasherDefault, Hasher};
+
+        /// A no-op hasher keeping only the 64 lowest bits of a `u128`.
+        #[derive(Default)]
+        struct TruncatingHasher(u64);
+
+        impl Hasher for TruncatingHasher {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                // the integers are written in native endianness
+                let mut low = [0; 8];
+                if cfg!(target_endian = "little") {
+                    low.copy_from_slice(&bytes[..8]);
+                } else {
+                    low.copy_from_slice(&bytes[(bytes.len() - 8)..]);
+                }
+                self.0 = u64::from_ne_bytes(low);
+            }
+        }
+
+        // the two m-mers only differ in their first base, beyond the 64 lowest bits,
+        // so that the smallest one is at position 1 but both hash to 0 and the first one is selected
+        let seq = [b"T".as_slice(), &[b'A'; 40]].concat();
+        let builder = MinimizerBuilder::<u128>::new()
+            .minimizer_size(40)
+            .width(2)
+            .hasher(BuildHasherDefault::<TruncatingHasher>::default());
+        let positions = || builder.clone().iter_pos(&seq).collect::<Vec<_>>();
+        if cfg!(debug_assertions) {
+            let err = std::panic::catch_unwind(positions).unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<&str>(),
+                Some(&"the hasher ignores the bits of the m-mers beyond the 64 lowest ones")
+            );
+        } else {
+            assert_eq!(positions(), vec![0]);
+        }
+
+        // up to 32 bases, the m-mers fit in the 64 lowest bits and the smallest one is selected
+        let builder = builder.minimizer_size(32);
+        assert_eq!(builder.iter_pos(&seq[..33]).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_soft_masked_minimizer_iter() {
+        use core::hash::BuildHasher;
+
+        let mut seq = gen_seq(10_000);
+        let minimizer_size = 21;
+        let width = 11;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+        assert_eq!(
+            builder.clone().iter_soft_masked(&seq).collect::<Vec<_>>(),
+            builder.clone().iter(&seq).collect::<Vec<_>>()
+        );
+
+        seq[1_000..2_000].make_ascii_lowercase();
+        seq[5_000..5_005].make_ascii_lowercase();
+        let is_masked = |pos: usize| {
+            seq[pos..(pos + minimizer_size)]
+                .iter()
+                .any(u8::is_ascii_lowercase)
+        };
+        let mins: Vec<_> = builder.clone().iter_soft_masked(&seq).collect();
+        assert!(mins.iter().all(|&(_, pos)| !is_masked(pos)));
+        // the windows selecting a masked m-mer in the full scan now select their smallest unmasked m-mer
+        let windows: Vec<_> = builder.clone().iter_windows(&seq).collect();
+        let mut expected = Vec::new();
+        for (i, _, _) in windows {
+            let best = (i..(i + width as usize))
+                .filter(|&pos| !is_masked(pos))
+                .map(|pos| {
+                    let min = builder
+                        .clone()
+                        .width(1)
+                        .iter(&seq[pos..(pos + minimizer_size)])
+                        .next()
+                        .unwrap()
+                        .0;
+                    (DefaultHashBuilder::default().hash_one(min), pos, min)
+                })
+                .min();
+            if let Some((_, pos, min)) = best {
+                if expected.last() != Some(&(min, pos)) {
+                    expected.push((min, pos));
+                }
+            }
+        }
+        assert_eq!(mins, expected);
+    }
+
+    #[test]
+    fn test_minimizers_covering() {
+        let seq = &gen_seq(1_000);
+        let (minimizer_size, width) = (21, 11);
+        let base_width = width as usize + minimizer_size - 1;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+        let windows: Vec<_> = builder.clone().iter_windows(seq).collect();
+        let canonical_builder = builder.clone().canonical();
+        let canonical_windows: Vec<_> = canonical_builder.clone().iter_windows(seq).collect();
+        for p in [0, 1, 15, 30, 500, 969, 970, 999] {
+            let covering = |i: usize| i <= p && p < i + base_width;
+            let mut expected: Vec<_> = windows
+                .iter()
+                .filter(|&&(i, _, _)| covering(i))
+                .map(|&(i, min, offset)| (min, i + offset))
+                .collect();
+            expected.dedup();
+            assert_eq!(builder.clone().minimizers_covering(seq, p), expected);
+
+            let mut expected: Vec<_> = canonical_windows
+                .iter()
+                .filter(|&&(i, _, _, _)| covering(i))
+                .map(|&(i, min, offset, strand)| (min, i + offset, strand))
+                .collect();
+            expected.dedup();
+            assert_eq!(
+                canonical_builder.clone().minimizers_covering(seq, p),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_minimizer_iter_with_queue() {
+        use minimizer_queue::{ImplicitMinimizerQueue, MinimizerQueue};
+
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let expected: Vec<_> = builder.clone().iter(seq).collect();
+        let queue = NaiveQueue {
+            window: VecDeque::new(),
+            width: 11,
+        };
+        assert_eq!(
+            builder
+                .clone()
+                .iter_with_queue(seq, queue)
+                .collect::<Vec<_>>(),
+            expected
+        );
+        let queue = MinimizerQueue::<u64>::new(11);
+        assert_eq!(
+            builder
+                .clone()
+                .iter_with_queue(seq, queue)
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        let expected: Vec<_> = builder.clone().iter_pos(seq).collect();
+        let queue = NaiveQueue {
+            window: VecDeque::new(),
+            width: 11,
+        };
+        assert_eq!(
+            builder
+                .clone()
+                .iter_pos_with_queue(seq, queue)
+                .collect::<Vec<_>>(),
+            expected
+        );
+        let queue = ImplicitMinimizerQueue::new(11);
+        assert_eq!(
+            builder
+                .clone()
+                .iter_pos_with_queue(seq, queue)
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        let naive = |width| NaiveQueue {
+            window: VecDeque::new(),
+            width,
+        };
+        // the naive queue breaks ties like the default ones, so the canonical minimizers are the same
+        let builder = builder.canonical();
+        assert_eq!(
+            builder
+                .clone()
+                .iter_with_queue(seq, naive(11))
+                .collect::<Vec<_>>(),
+            builder.clone().iter(seq).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .iter_pos_with_queue(seq, naive(11))
+                .collect::<Vec<_>>(),
+            builder.iter_pos(seq).collect::<Vec<_>>()
+        );
+
+        // the queues of mod-minimizers hold the t-mers of a window, i.e. width + minimizer_size - t of them
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11)
+            .t(10);
+        assert_eq!(
+            builder
+                .clone()
+                .iter_with_queue(seq, naive(22))
+                .collect::<Vec<_>>(),
+            builder.clone().iter(seq).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .iter_pos_with_queue(seq, naive(22))
+                .collect::<Vec<_>>(),
+            builder.clone().iter_pos(seq).collect::<Vec<_>>()
+        );
+        let builder = builder.canonical();
+        assert_eq!(
+            builder
+                .clone()
+                .iter_with_queue(seq, naive(22))
+                .collect::<Vec<_>>(),
+            builder.clone().iter(seq).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .iter_pos_with_queue(seq, naive(22))
+                .collect::<Vec<_>>(),
+            builder.iter_pos(seq).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_clone_iter() {
+        fn check<I: Iterator + Clone>(mut iter: I)
+        where
+            I::Item: PartialEq + core::fmt::Debug,
+        {
+            let expected: Vec<_> = iter.clone().collect();
+            let mid = expected.len() / 2;
+            iter.nth(mid - 1);
+            let fork = iter.clone();
+            assert_eq!(fork.collect::<Vec<_>>(), expected[mid..]);
+            assert_eq!(iter.collect::<Vec<_>>(), expected[mid..]);
+        }
+
+        let seq = &gen_seq(10_000);
+        let naive = |width| NaiveQueue {
+            window: VecDeque::new(),
+            width,
+        };
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        check(builder.clone().iter_with_queue(seq, naive(11)));
+        check(builder.clone().iter_pos_with_queue(seq, naive(11)));
+        let builder = builder.canonical();
+        check(builder.clone().iter_with_queue(seq, naive(11)));
+        check(builder.iter_pos_with_queue(seq, naive(11)));
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11)
+            .t(10);
+        check(builder.clone().iter_with_queue(seq, naive(22)));
+        check(builder.clone().iter_pos_with_queue(seq, naive(22)));
+        let mut encoding = [0; 256];
+        for (i, b) in b"ACGT".iter().enumerate() {
+            encoding[*b as usize] = i as u8;
+        }
+        check(iterator::ModSamplingTmerIterator::<
+            u64,
+            DefaultHashBuilder,
+            _,
+        >::with_queue(seq, 21, 11, 10, naive(22), encoding));
+        let builder = builder.canonical();
+        check(builder.clone().iter_with_queue(seq, naive(22)));
+        check(builder.iter_pos_with_queue(seq, naive(22)));
+    }
+
+    #[test]
+    #[should_panic(expected = "the width of the queue must be ≤ 65535")]
+    fn test_minimizer_iter_with_wide_queue() {
+        use crate::queue::MinPosWindow;
+
+        /// A queue claiming a width that does not fit in a `u16`.
+        struct WideQueue;
+
+        impl MinPosWindow<u64> for WideQueue {
+            fn width(&self) -> usize {
+                1 << 16
+            }
+
+            fn is_empty(&self) -> bool {
+                true
+            }
+
+            fn insert(&mut self, _: u64) {}
+
+            fn min_pos(&self) -> usize {
+                0
+            }
+
+            fn multiple_mins(&self) -> bool {
+                false
+            }
+
+            fn inner_min_pos(&self) -> (usize, Option<usize>) {
+                (0, None)
+            }
+        }
+
+        iterator::MinimizerPosIterator::<u64, DefaultHashBuilder, _>::with_queue(
+            b"ACGT", 21, WideQueue, [0; 256],
+        );
+    }
+
+    #[test]
+    fn test_minimizer_iter_with_qual() {
+        let seq = &gen_seq(10_000);
+        let min_q = b'!' + 20;
+        let qual: Vec<u8> = (0..seq.len())
+            .map(|i| {
+                if i % 997 < 3 || (4000..4020).contains(&i) {
+                    b'!' + 5
+                } else {
+                    b'!' + 30
+                }
+            })
+            .collect();
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mut expected = Vec::new();
+        let mut start = 0;
+        for run in qual.split(|&q| q < min_q) {
+            let end = start + run.len();
+            expected.extend(
+                builder
+                    .clone()
+                    .iter(&seq[start..end])
+                    .map(|(x, pos)| (x, start + pos)),
+            );
+            start = end + 1;
+        }
+        let mut qual_iter = builder.clone().iter_with_qual(seq, &qual, min_q);
+        let minimizers: Vec<_> = qual_iter.by_ref().collect();
+        assert_eq!(minimizers, expected);
+        assert_eq!(
+            qual_iter.valid_base_count(),
+            qual.iter().filter(|&&q| q >= min_q).count()
+        );
+        let low_qual = vec![b'!'; 10];
+        let mut qual_iter = builder.clone().iter_with_qual(&seq[..10], &low_qual, min_q);
+        assert_eq!(qual_iter.next(), None);
+        assert_eq!(qual_iter.valid_base_count(), 0);
+        let mut qual_iter = builder
+            .clone()
+            .iter_with_qual(&seq[..10], &qual[..10], min_q);
+        assert_eq!(qual_iter.next(), None);
+        assert_eq!(qual_iter.valid_base_count(), 7); // the first 3 bases have a low quality
+                                                     // an all-N record is flagged even with good qualities
+        let all_n = vec![b'N'; 100];
+        let good_qual = vec![b'!' + 30; 100];
+        let mut qual_iter = builder.clone().iter_with_qual(&all_n, &good_qual, min_q);
+        assert_eq!(qual_iter.next(), None);
+        assert_eq!(qual_iter.valid_base_count(), 0);
+        // no minimizer overlaps an N
+        let mut seq_n = seq[..100].to_vec();
+        seq_n[50] = b'n';
+        let expected: Vec<_> = builder
+            .clone()
+            .iter(&seq_n[..50])
+            .chain(
+                builder
+                    .clone()
+                    .iter(&seq_n[51..])
+                    .map(|(x, pos)| (x, pos + 51)),
+            )
+            .collect();
+        let mut qual_iter = builder.clone().iter_with_qual(&seq_n, &good_qual, min_q);
+        assert_eq!(qual_iter.by_ref().collect::<Vec<_>>(), expected);
+        assert_eq!(qual_iter.valid_base_count(), 99);
+        assert!(minimizers
+            .iter()
+            .all(|&(_, pos)| qual[pos..(pos + 21)].iter().all(|&q| q >= min_q)));
+        let qual = vec![b'!' + 30; seq.len()];
+        assert_eq!(
+            builder
+                .clone()
+                .iter_with_qual(seq, &qual, min_q)
+                .collect::<Vec<_>>(),
+            builder.iter(seq).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_spliced_large_offset() {
+        let seq = &gen_seq(1000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let offset = usize::MAX - seq.len();
+        let segments = [(&seq[..400], offset), (&seq[400..], offset + 400)];
+        let expected: Vec<_> = builder
+            .clone()
+            .iter(seq)
+            .map(|(x, pos)| (x, offset + pos))
+            .collect();
+        assert_eq!(
+            builder.iter_spliced(&segments).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_spliced_overflowing_offset() {
+        let seq = &gen_seq(1000);
+        let segments = [(&seq[..], usize::MAX - 10)];
+        MinimizerBuilder::<u64>::new()
+            .minimizer_size(21)
+            .width(11)
+            .iter_spliced(&segments);
+    }
+
+    #[test]
+    fn test_minimizer_iter_advance() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u128>::new().minimizer_size(41).width(11);
+        let expected: Vec<_> = builder.clone().iter(seq).collect();
+        let mut iter = builder.iter(seq);
+        let mut minimizers = Vec::new();
+        while let Some((&x, pos)) = iter.advance() {
+            minimizers.push((x, pos));
+        }
+        assert_eq!(minimizers, expected);
+        assert_eq!(iter.advance(), None);
+    }
+
+    #[test]
+    fn test_first() {
+        let seq = &gen_seq(1000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        assert_eq!(builder.clone().first(seq), builder.clone().iter(seq).next());
+        assert_eq!(
+            builder.clone().first(&seq[..31]),
+            builder.clone().iter(seq).next()
+        );
+        assert_eq!(builder.clone().first(&seq[..30]), None);
+        let builder = builder.canonical();
+        assert_eq!(builder.clone().first(seq), builder.clone().iter(seq).next());
+        assert_eq!(builder.first(&seq[..30]), None);
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        assert_eq!(builder.clone().first(seq), builder.clone().iter(seq).next());
+        let builder = builder.canonical();
+        assert_eq!(builder.clone().first(seq), builder.iter(seq).next());
+    }
+
+    #[test]
+    fn test_tie_break_count() {
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(5)
+            .width(11)
+            .canonical();
+        // every window of a poly-A is canonical, so ties keep the leftmost minimizer
+        let mut min_iter = builder.clone().iter(&[b'A'; 100]);
+        min_iter.by_ref().for_each(drop);
+        assert_eq!(min_iter.tie_break_count(), 0);
+        // every window of a poly-T is not canonical, so ties select the rightmost minimizer
+        let mut min_iter = builder.clone().iter(&[b'T'; 100]);
+        min_iter.by_ref().for_each(drop);
+        assert_eq!(min_iter.tie_break_count(), 100 - 15 + 1);
+        let mut min_iter = builder.iter_pos(&[b'T'; 100]);
+        min_iter.by_ref().for_each(drop);
+        assert_eq!(min_iter.tie_break_count(), 100 - 15 + 1);
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(5)
+            .width(11)
+            .canonical();
+        let mut min_iter = builder.clone().iter(&[b'A'; 100]);
+        min_iter.by_ref().for_each(drop);
+        assert_eq!(min_iter.tie_break_count(), 0);
+        let mut min_iter = builder.iter(&[b'T'; 100]);
+        min_iter.by_ref().for_each(drop);
+        assert!(min_iter.tie_break_count() > 0);
+    }
+
+    #[test]
+    fn test_k() {
+        assert_eq!(MinimizerBuilder::<u64>::DEFAULT_MINIMIZER_SIZE, 21);
+        assert_eq!(MinimizerBuilder::<u64>::DEFAULT_WIDTH, 11);
+        let seq = &gen_seq(1000);
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .k(31)
+                .iter(seq)
+                .collect::<Vec<_>>(),
+            MinimizerBuilder::<u64>::new().iter(seq).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .minimizer_size(15)
+                .k(25)
+                .iter(seq)
+                .collect::<Vec<_>>(),
+            MinimizerBuilder::<u64>::new()
+                .minimizer_size(15)
+                .width(11)
+                .iter(seq)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .minimizer_size(15)
+                .k(15)
+                .iter(seq)
+                .count(),
+            seq.len() - 15 + 1
+        );
+    }
+
+    #[test]
+    fn test_from_k_and_m() {
+        let builder = MinimizerBuilder::<u64>::new();
+        assert_eq!(builder.kmer_size(), 31);
+        let builder = builder.from_k_and_m(31, 19);
+        assert_eq!(builder.kmer_size(), 31);
+        let seq = &gen_seq(1000);
+        assert_eq!(
+            builder.iter(seq).collect::<Vec<_>>(),
+            MinimizerBuilder::<u64>::new()
+                .minimizer_size(19)
+                .width(13)
+                .iter(seq)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .from_k_and_m(63, 31)
+                .kmer_size(),
+            63
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_k_smaller_than_minimizer_size() {
+        MinimizerBuilder::<u64>::new().minimizer_size(21).k(20);
+    }
+
+    #[test]
+    fn test_minimizer_iter_per_kmer() {
+        let seq = &gen_seq(1000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let k = builder.kmer_size();
+        let per_kmer: Vec<_> = builder.clone().iter_per_kmer(seq).collect();
+        assert_eq!(per_kmer.len(), seq.len() - k + 1);
+        for (i, &(x, pos)) in per_kmer.iter().enumerate() {
+            let kmer_min = builder.clone().first(&seq[i..(i + k)]).unwrap();
+            assert_eq!((x, pos), (kmer_min.0, i + kmer_min.1));
+        }
+        let mut dedup = per_kmer;
+        dedup.dedup();
+        assert_eq!(dedup, builder.clone().iter(seq).collect::<Vec<_>>());
+        assert_eq!(builder.iter_per_kmer(&seq[..30]).next(), None);
+    }
+
+    #[test]
+    fn test_minimizer_iter_from() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        assert_eq!(
+            builder
+                .clone()
+                .iter_from(seq.iter().copied())
+                .collect::<Vec<_>>(),
+            builder.clone().iter(seq).collect::<Vec<_>>()
+        );
+        let bases = (0..1000).map(|i| b"ACGT"[(i * i + i / 7) % 4]);
+        let generated: Vec<u8> = bases.clone().collect();
+        assert_eq!(
+            builder.clone().iter_from(bases).collect::<Vec<_>>(),
+            builder.clone().iter(&generated).collect::<Vec<_>>()
+        );
+        assert_eq!(builder.iter_from(seq[..30].iter().copied()).next(), None);
+    }
+
+    #[test]
+    fn test_mod_minimizer_r() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        // r = 4 by default, so t = 4 + (17 % 11) = 10
+        assert_eq!(
+            builder.clone().r(4).iter(seq).collect::<Vec<_>>(),
+            builder.clone().iter(seq).collect::<Vec<_>>()
+        );
+        // r = 7 gives t = 7 + (14 % 11) = 10 as well
+        assert_eq!(
+            builder.clone().r(7).iter(seq).collect::<Vec<_>>(),
+            builder.clone().t(10).iter(seq).collect::<Vec<_>>()
+        );
+        // r = 12 gives t = 12 + (9 % 11) = 21
+        assert_eq!(
+            builder.clone().r(12).iter(seq).collect::<Vec<_>>(),
+            builder.clone().t(21).iter(seq).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            builder.clone().r(12).expected_density(),
+            builder.clone().t(21).expected_density()
+        );
+        assert_eq!(
+            builder.r(22).validate(),
+            Err(vec![BuildError::MinimizerSizeTooSmall {
+                minimizer_size: 21,
+                r: 22
+            }])
+        );
+    }
+
+    #[test]
+    fn test_total_minimizers() {
+        let seq = gen_seq(10_000);
+        let seqs: Vec<&[u8]> = vec![&seq[..1000], &seq[1000..1010], &seq[1010..], &[]];
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let expected: usize = seqs.iter().map(|s| builder.clone().count(s)).sum();
+        assert_eq!(
+            builder.clone().total_minimizers(seqs.iter().copied()),
+            expected
+        );
+        let builder = builder.canonical();
+        let expected: usize = seqs.iter().map(|s| builder.clone().count(s)).sum();
+        assert_eq!(builder.total_minimizers(seqs.iter().copied()), expected);
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let expected: usize = seqs.iter().map(|s| builder.clone().count(s)).sum();
+        assert_eq!(
+            builder.clone().total_minimizers(seqs.iter().copied()),
+            expected
+        );
+        let builder = builder.canonical();
+        let expected: usize = seqs.iter().map(|s| builder.clone().count(s)).sum();
+        assert_eq!(builder.total_minimizers(seqs), expected);
+    }
+
+    #[test]
+    fn test_minimizer_iter_with_gc() {
+        let seq = &gen_seq(10_000);
+        let gc = |mmer: &[u8]| mmer.iter().filter(|&&b| b == b'C' || b == b'G').count();
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        for (x, pos, gc_count) in builder.clone().iter_with_gc(seq) {
+            assert_eq!(x, pack(&seq[pos..(pos + 21)]));
+            assert_eq!(gc_count, gc(&seq[pos..(pos + 21)]));
+        }
+        let builder = builder.encoding(0b11, 0b10, 0b01, 0b00);
+        for (_, pos, gc_count) in builder.iter_with_gc(seq) {
+            assert_eq!(gc_count, gc(&seq[pos..(pos + 21)]));
+        }
+        let builder = MinimizerBuilder::<u128, _>::new_mod()
+            .minimizer_size(63)
+            .width(11)
+            .encoding(0b01, 0b11, 0b11, 0b00);
+        for (_, pos, gc_count) in builder.iter_with_gc(seq) {
+            assert_eq!(gc_count, gc(&seq[pos..(pos + 63)]));
+        }
+    }
+
+    #[test]
+    fn test_into_counter() {
+        use crate::sink::MinimizerSink;
+
+        struct Counter(HashMap<u64, usize>);
+
+        impl MinimizerSink<u64> for Counter {
+            fn accept(&mut self, value: u64, _position: usize) {
+                *self.0.entry(value).or_default() += 1;
+            }
+        }
+
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mut mins = Vec::new();
+        builder
+            .clone()
+            .into_counter(seq, &mut |min, pos| mins.push((min, pos)));
+        assert_eq!(mins, builder.clone().iter(seq).collect::<Vec<_>>());
+        let mut counter = Counter(HashMap::new());
+        builder.clone().into_counter(seq, &mut counter);
+        assert_eq!(counter.0.values().sum::<usize>(), mins.len());
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let mut mins = Vec::new();
+        builder
+            .clone()
+            .into_counter(seq, &mut |min, pos| mins.push((min, pos)));
+        assert_eq!(mins, builder.clone().iter(seq).collect::<Vec<_>>());
+        let mut iter = builder.clone().iter(seq);
+        let mut rest = Vec::new();
+        iter.nth(10);
+        iter.feed(&mut |min, pos| rest.push((min, pos)));
+        assert_eq!(rest, mins[11..]);
+        let mut short = Vec::new();
+        builder.into_counter(&seq[..30], &mut |min, pos| short.push((min, pos)));
+        assert!(short.is_empty());
+
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(21)
+            .width(11)
+            .base_order(BaseOrder::LeastSignificantFirst);
+        let mut iter = builder.clone().iter(seq);
+        let mut mins = iter.by_ref().take(5).collect::<Vec<_>>();
+        iter.feed(&mut |min, pos| mins.push((min, pos)));
+        assert_eq!(mins, builder.iter(seq).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_mod_minimizer_too_many_tmers() {
+        let builder = MinimizerBuilder::<u128, _>::new_mod()
+            .minimizer_size(60)
+            .width(65500)
+            .t(4);
+        assert_eq!(
+            builder.validate(),
+            Err(vec![BuildError::TooManyTmers {
+                t: 4,
+                minimizer_size: 60,
+                width: 65500
+            }])
+        );
+        assert!(MinimizerBuilder::<u128, _>::new_mod()
+            .minimizer_size(60)
+            .width(60000)
+            .t(4)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "width + minimizer_size - t must be ≤ 65535")]
+    fn test_mod_minimizer_width_t_overflow() {
+        MinimizerBuilder::<u128, _>::new_mod()
+            .minimizer_size(60)
+            .width(65500)
+            .t(4)
+            .iter(&gen_seq(100));
+    }
+
+    #[test]
+    fn test_minimizer_iter_transitions() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let transitions: Vec<_> = builder.clone().iter_transitions(seq).collect();
+        assert_eq!(transitions.len(), mins.len());
+        assert_eq!(transitions[0], (None, mins[0].0, mins[0].1));
+        for (i, &(prev, min, pos)) in transitions.iter().enumerate().skip(1) {
+            assert_eq!(
+                (prev, min, pos),
+                (Some(mins[i - 1].0), mins[i].0, mins[i].1)
+            );
+        }
+        let builder = builder.canonical();
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let transitions: Vec<_> = builder.iter_transitions(seq).collect();
+        assert_eq!(transitions.len(), mins.len());
+        assert_eq!(transitions[0], (None, mins[0].0, mins[0].1, mins[0].2));
+        for (i, &(prev, min, pos, strand)) in transitions.iter().enumerate().skip(1) {
+            assert_eq!(
+                (prev, min, pos, strand),
+                (Some(mins[i - 1].0), mins[i].0, mins[i].1, mins[i].2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_exclude_values() {
+        use core::hash::BuildHasher;
+
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let set: HashSet<u64> = mins.iter().step_by(3).map(|&(min, _)| min).collect();
+        let set = Arc::new(set);
+        let excluding: Vec<_> = builder.clone().exclude_values(seq, set.clone()).collect();
+        assert!(excluding.iter().all(|(min, _)| !set.contains(min)));
+        // every window selects its smallest m-mer outside of the set
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+        for (i, window) in seq.windows(31).enumerate() {
+            let candidate = (0..11)
+                .map(|j| (pack(&window[j..(j + 21)]), i + j))
+                .filter(|(mmer, _)| !set.contains(mmer))
+                .min_by_key(|&(mmer, pos)| (DefaultHashBuilder::default().hash_one(mmer), pos));
+            if let Some(candidate) = candidate {
+                if expected.last() != Some(&candidate) {
+                    expected.push(candidate);
+                }
+            }
+        }
+        assert_eq!(excluding, expected);
+        assert_eq!(
+            builder
+                .clone()
+                .exclude_values(seq, Arc::new(HashSet::new()))
+                .collect::<Vec<_>>(),
+            mins
+        );
+
+        // a predicate on the positions never selects the penalized ones
+        let penalized = |_, pos: usize| pos.is_multiple_of(3);
+        assert!(builder
+            .clone()
+            .iter_penalized(seq, penalized)
+            .all(|(_, pos)| !pos.is_multiple_of(3)));
+    }
+
+    #[test]
+    fn test_stable_hasher() {
+        use crate::hash::BuildStableHasher;
+        use core::hash::BuildHasher;
+
+        // these values are part of the stability guarantee and must never change
+        let hasher = BuildStableHasher::default();
+        assert_eq!(hasher.hash_one(0u64), 0x9ca066f1a4ab2eea);
+        assert_eq!(hasher.hash_one(0b001111u64), 0x6c45141841d3019f);
+        assert_eq!(hasher.hash_one(0b001111u32), 0x6c45141841d3019f);
+        assert_eq!(
+            BuildStableHasher::with_seed(42).hash_one(0b001111u128),
+            0xe9a7c2aaab49b00a
+        );
+        let mut min_iter = MinimizerBuilder::<u64>::new()
+            .minimizer_size(3)
+            .width(4)
+            .stable_hasher()
+            .iter(b"TGATTGCACAATC");
+        assert_eq!(min_iter.next(), Some((0b111110, 3))); // TTG
+        assert_eq!(min_iter.next(), Some((0b010001, 6))); // CAC
+        assert_eq!(min_iter.next(), Some((0b010000, 8))); // CAA
+        assert_eq!(min_iter.next(), None);
+        let mins: Vec<_> = MinimizerBuilder::<u128>::new()
+            .minimizer_size(3)
+            .width(4)
+            .hasher(BuildStableHasher::with_seed(42))
+            .iter(b"TGATTGCACAATC")
+            .collect();
+        assert_eq!(
+            mins,
+            vec![
+                (0b111000, 0), // TGA
+                (0b111110, 3), // TTG
+                (0b100100, 5), // GCA
+                (0b010001, 6), // CAC
+                (0b000011, 9), // AAT
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canonical_minimizer_iter_stranded() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(21)
+            .width(11)
+            .canonical();
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let forward: Vec<_> = builder
+            .clone()
+            .iter_stranded(seq, Strand::Forward)
+            .collect();
+        let reverse: Vec<_> = builder.iter_stranded(seq, Strand::Reverse).collect();
+        assert_eq!(forward.len(), mins.len());
+        assert_eq!(reverse.len(), mins.len());
+        for ((&(_, pos, _), &f), &r) in mins.iter().zip(forward.iter()).zip(reverse.iter()) {
+            let mmer = &seq[pos..(pos + 21)];
+            assert_eq!(f, (pack(mmer), pos, Strand::Forward));
+            assert_eq!(r, (pack(&rc(mmer)), pos, Strand::Reverse));
+        }
+    }
+
+    #[test]
+    fn test_minimizer_iter_with_running_count() {
+        let seq = [gen_seq(1000), gen_seq(1000), gen_seq(1000)].concat();
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mins: Vec<_> = builder.clone().iter(&seq).collect();
+        let counted: Vec<_> = builder.iter_with_running_count(&seq).collect();
+        assert_eq!(counted.len(), mins.len());
+        for (i, &(min, pos, count)) in counted.iter().enumerate() {
+            assert_eq!((min, pos), mins[i]);
+            assert_eq!(count, mins[..=i].iter().filter(|&&(x, _)| x == min).count());
+        }
+        assert!(counted.iter().any(|&(_, _, count)| count == 3));
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let counted: Vec<_> = builder.iter_with_running_count(&seq).collect();
+        assert!(counted.iter().any(|&(_, _, count)| count == 3));
+    }
+
+    #[test]
+    fn test_last_window_flushed() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        for len in (31..500).step_by(13) {
+            let seq = &seq[..len];
+            let (i, min, offset) = builder.clone().iter_windows(seq).last().unwrap();
+            let last_window_min = (min, i + offset);
+            assert_eq!(builder.clone().iter(seq).last(), Some(last_window_min));
+            // feeding the bases by chunks yields the same minimizers, including the last one
+            for chunk_size in [1, 7, 64] {
+                let mins: Vec<_> = builder
+                    .clone()
+                    .iter_from(seq.chunks(chunk_size).flatten().copied())
+                    .collect();
+                assert_eq!(mins, builder.clone().iter(seq).collect::<Vec<_>>());
+                assert_eq!(mins.last(), Some(&last_window_min));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_key() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(19)
+            .width(13)
+            .canonical();
+        for min in builder.iter(seq) {
+            let key = pack_key(min.0, min.1, min.2).unwrap();
+            assert_eq!(unpack_key(key), min);
+            let min = CanonicalMinimizer::from(min);
+            assert_eq!(min.pack_key(), Ok(key));
+            assert_eq!(CanonicalMinimizer::from_key(key), min);
+        }
+        let max_value = (1u64 << KEY_VALUE_BITS) - 1;
+        let max_pos = (1 << KEY_POS_BITS) - 1;
+        assert_eq!(
+            unpack_key(pack_key(max_value, max_pos, Strand::Reverse).unwrap()),
+            (max_value, max_pos, Strand::Reverse)
+        );
+        assert_eq!(pack_key(0u64, 0, Strand::Forward), Ok(0));
+        assert_eq!(pack_key(0u64, 0, Strand::Reverse), Ok(1));
+        assert_eq!(pack_key(1u64, 1, Strand::Forward), Ok(1 << 24 | 1 << 1));
+        assert_eq!(
+            pack_key(max_value + 1, 0, Strand::Forward),
+            Err(KeyError::ValueTooLarge)
+        );
+        assert_eq!(
+            pack_key(u128::MAX, 0, Strand::Forward),
+            Err(KeyError::ValueTooLarge)
+        );
+        assert_eq!(
+            pack_key(-1i64, 0, Strand::Forward),
+            Err(KeyError::NegativeValue)
+        );
+        assert_eq!(
+            pack_key(0u64, max_pos + 1, Strand::Forward),
+            Err(KeyError::PositionTooLarge { pos: max_pos + 1 })
+        );
+    }
+
+    #[test]
+    fn test_iter_ref() {
+        let seqs = [gen_seq(1000), gen_seq(500), gen_seq(20)];
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        for seq in seqs.iter() {
+            assert_eq!(
+                builder.iter_ref(seq).collect::<Vec<_>>(),
+                builder.clone().iter(seq).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                builder.iter_pos_ref(seq).collect::<Vec<_>>(),
+                builder.clone().iter_pos(seq).collect::<Vec<_>>()
+            );
+        }
+        let builder = builder.canonical();
+        for seq in seqs.iter() {
+            assert_eq!(
+                builder.iter_ref(seq).collect::<Vec<_>>(),
+                builder.clone().iter(seq).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                builder.iter_pos_ref(seq).collect::<Vec<_>>(),
+                builder.clone().iter_pos(seq).collect::<Vec<_>>()
+            );
+        }
+
+        // the borrowed tables follow the encoding and the complement of the builder
+        let mut complement = iterator::COMPLEMENT;
+        for (a, b) in [(b'A', b'C'), (b'G', b'T')] {
+            complement[a as usize] = b;
+            complement[b as usize] = a;
+        }
+        let builder = builder
+            .width(10)
+            .even_width_canonical()
+            .encoding(0b01, 0b00, 0b10, 0b11)
+            .complement(complement);
+        for seq in seqs.iter() {
+            assert_eq!(
+                builder.iter_ref(seq).collect::<Vec<_>>(),
+                builder.clone().iter(seq).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                builder.iter_pos_ref(seq).collect::<Vec<_>>(),
+                builder.clone().iter_pos(seq).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_six_frame_minimizer_iter() {
+        use crate::iterator::{translate, AMINO_ACIDS};
+        use core::hash::BuildHasher;
+
+        assert_eq!(translate(b"ATGGCCTAAtggNNN"), b"MA*WX");
+        let dna = &gen_seq(3000);
+        let (minimizer_size, width) = (7, 5);
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+        let min_iter = builder.clone().iter_six_frame(dna);
+        let mins: Vec<_> = builder.iter_six_frame(dna).collect();
+        let rc_dna = rc(dna);
+        let hasher = DefaultHashBuilder::default();
+        let mut expected = Vec::new();
+        for frame in [1i8, 2, 3, -1, -2, -3] {
+            let strand = if frame > 0 { dna } else { &rc_dna };
+            let protein = translate(&strand[(frame.unsigned_abs() as usize - 1)..]);
+            let residues: Vec<u64> = protein
+                .iter()
+                .map(|aa| AMINO_ACIDS.iter().position(|x| x == aa).unwrap() as u64)
+                .collect();
+            let mut last = None;
+            for window in 0..=(residues.len() - (width as usize + minimizer_size - 1)) {
+                let candidate = (window..(window + width as usize))
+                    .map(|i| {
+                        let x = residues[i..(i + minimizer_size)]
+                            .iter()
+                            .fold(0u64, |x, &aa| (x << 5) | aa);
+                        (x, i)
+                    })
+                    .min_by_key(|&(x, i)| (hasher.hash_one(x), i))
+                    .unwrap();
+                if last != Some(candidate.1) {
+                    last = Some(candidate.1);
+                    expected.push((frame, candidate.0, candidate.1));
+                }
+            }
+        }
+        assert_eq!(mins, expected);
+        for &(frame, _, pos) in mins.iter() {
+            let nt = min_iter.nucleotide_pos(frame, pos);
+            let span = &dna[nt..(nt + 3 * minimizer_size)];
+            let protein = if frame > 0 {
+                translate(span)
+            } else {
+                translate(&rc(span))
+            };
+            let strand = if frame > 0 { dna } else { &rc_dna };
+            let protein_frame = translate(&strand[(frame.unsigned_abs() as usize - 1)..]);
+            assert_eq!(protein, &protein_frame[pos..(pos + minimizer_size)]);
+        }
+    }
+
+    #[test]
+    fn test_position_mask() {
+        let seq = &gen_seq(1003);
+        let is_set = |mask: &[u8], i: usize| mask[i / 8] >> (i % 8) & 1 == 1;
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let positions: HashSet<usize> = builder.clone().iter_pos(seq).collect();
+        let mask = builder.clone().position_mask(seq);
+        assert_eq!(mask.len(), 126);
+        assert!((0..seq.len()).all(|i| is_set(&mask, i) == positions.contains(&i)));
+        let builder = builder.canonical();
+        let positions: HashSet<usize> = builder.clone().iter_pos(seq).map(|(pos, _)| pos).collect();
+        let mask = builder.position_mask(seq);
+        assert!((0..seq.len()).all(|i| is_set(&mask, i) == positions.contains(&i)));
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let positions: HashSet<usize> = builder.clone().iter_pos(seq).collect();
+        let mask = builder.position_mask(seq);
+        assert!((0..seq.len()).all(|i| is_set(&mask, i) == positions.contains(&i)));
+        assert!(MinimizerBuilder::<u64>::new().position_mask(b"").is_empty());
+    }
+
+    #[test]
+    fn test_seq_len_equals_base_width() {
+        let seq = &gen_seq(1000);
+        for (minimizer_size, width) in [(21, 1), (21, 11), (1, 31), (31, 1)] {
+            let len = minimizer_size + width as usize - 1;
+            let builder = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            let mod_builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .t(1);
+            // a single window yields exactly one minimizer, and a shorter sequence yields none
+            for (seq, expected) in [(&seq[..len], 1), (&seq[..(len - 1)], 0)] {
+                assert_eq!(builder.clone().iter(seq).count(), expected);
+                assert_eq!(builder.clone().iter_pos(seq).count(), expected);
+                assert_eq!(builder.clone().iter_windows(seq).count(), expected);
+                assert_eq!(builder.clone().iter_per_kmer(seq).count(), expected);
+                assert_eq!(builder.clone().iter_ties(seq).count(), expected);
+                assert_eq!(builder.clone().iter_soft_masked(seq).count(), expected);
+                assert_eq!(
+                    builder
+                        .clone()
+                        .encoding(0, 1, 0, 1)
+                        .binary()
+                        .iter(seq)
+                        .count(),
+                    expected
+                );
+                assert_eq!(
+                    builder.clone().iter_from(seq.iter().copied()).count(),
+                    expected
+                );
+                assert_eq!(
+                    builder.clone().iter_with_qual(seq, seq, 0).count(),
+                    expected
+                );
+                assert_eq!(builder.clone().iter_spliced(&[(seq, 0)]).count(), expected);
+                let codes: Vec<u8> = seq.iter().map(|&b| (pack(&[b]) & 0b11) as u8).collect();
+                assert_eq!(builder.clone().iter_raw_codes(&codes).count(), expected);
+                let packed: Vec<u8> = seq
+                    .chunks(4)
+                    .map(|chunk| {
+                        let mut bases = [b'A'; 4];
+                        bases[..chunk.len()].copy_from_slice(chunk);
+                        pack(&bases) as u8
+                    })
+                    .collect();
+                assert_eq!(
+                    builder.clone().iter_packed(&packed, seq.len()).count(),
+                    expected
+                );
+                assert_eq!(builder.clone().lexicographic().iter(seq).count(), expected);
+                assert_eq!(builder.clone().nthash().iter(seq).count(), expected);
+                assert_eq!(mod_builder.clone().iter(seq).count(), expected);
+                assert_eq!(mod_builder.clone().iter_pos(seq).count(), expected);
+                if width % 2 == 1 {
+                    let builder = builder.clone().canonical();
+                    assert_eq!(builder.clone().iter(seq).count(), expected);
+                    assert_eq!(builder.clone().iter_pos(seq).count(), expected);
+                    assert_eq!(builder.clone().iter_windows(seq).count(), expected);
+                    assert_eq!(builder.clone().nthash().iter(seq).count(), expected);
+                    let mod_builder = mod_builder.clone().t(minimizer_size).canonical();
+                    assert_eq!(mod_builder.clone().iter(seq).count(), expected);
+                    assert_eq!(mod_builder.iter_pos(seq).count(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonical_minimizer_iter() {
+        let seq_len = 1_000_000;
+        let seq = &gen_seq(seq_len);
+        let seq_rc = &rc(seq);
+        let minimizer_size = 21;
+        let width = 11;
+
+        let mins: Vec<u64> = MinimizerBuilder::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter(seq)
+            .map(|(min, _, _)| min)
+            .collect();
+        let mut mins_rc: Vec<u64> = MinimizerBuilder::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter(seq_rc)
+            .map(|(min, _, _)| min)
+            .collect();
+        mins_rc.reverse();
+
+        assert_eq!(mins, mins_rc);
+    }
+
+    #[test]
+    fn test_canonical_minimizer_iter_even_width() {
+        let random = gen_seq(100_000);
+        let repeated = gen_seq(3).repeat(1_000);
+        for (seq, minimizer_size, width) in [
+            (&random, 21, 10),
+            (&random, 21, 2),
+            (&repeated, 5, 8),
+            (&repeated, 4, 6),
+        ] {
+            let seq_rc = &rc(seq);
+            let builder = MinimizerBuilder::<u64>::new()
+                .canonical()
+                .even_width_canonical()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            assert!(builder.validate().is_ok());
+
+            let mins: Vec<_> = builder.clone().iter(seq).collect();
+            let mut mins_rc: Vec<_> = builder
+                .clone()
+                .iter(seq_rc)
+                .map(|(min, pos, _)| (min, seq.len() - pos - minimizer_size))
+                .collect();
+            mins_rc.reverse();
+            assert_eq!(
+                mins.iter()
+                    .map(|&(min, pos, _)| (min, pos))
+                    .collect::<Vec<_>>(),
+                mins_rc
+            );
+
+            let mut pos_iter = builder.clone().iter_pos(seq);
+            let positions: Vec<_> = pos_iter.by_ref().map(|(pos, _)| pos).collect();
+            let mut positions_rc: Vec<_> = builder
+                .iter_pos(seq_rc)
+                .map(|(pos, _)| seq.len() - pos - minimizer_size)
+                .collect();
+            positions_rc.reverse();
+            assert_eq!(positions, positions_rc);
+            if seq == &repeated {
+                assert!(pos_iter.tie_break_count() > 0);
+            }
+        }
+
+        // the central 4-mer TATA is its own reverse complement, so every tie keeps the leftmost minimizer on both strands
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .even_width_canonical()
+            .minimizer_size(3)
+            .width(2);
+        let seq = b"TATATATA";
+        let positions: Vec<_> = builder.clone().iter_pos(seq).map(|(pos, _)| pos).collect();
+        let mut positions_rc: Vec<_> = builder
+            .iter_pos(&rc(seq))
+            .map(|(pos, _)| seq.len() - pos - 3)
+            .collect();
+        positions_rc.reverse();
+        assert_eq!(positions, vec![0, 1, 2, 3, 4]);
+        assert_eq!(positions_rc, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "width must be odd")]
+    fn test_canonical_minimizer_iter_even_width_unset() {
+        MinimizerBuilder::<u64>::new()
+            .canonical()
+            .width(10)
+            .iter(b"ACGT");
+    }
+
+    #[test]
+    fn test_minimizer_iter_next_batch() {
+        let seq = &gen_seq(10_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let mut batched = Vec::new();
+        let mut min_iter = builder.clone().iter(seq);
+        while let Some((batch, positions, count)) = min_iter.next_batch() {
+            assert!(count > 0 && count <= 8);
+            batched.extend(batch.into_iter().zip(positions).take(count));
+        }
+        assert_eq!(batched, mins);
+        assert_eq!(min_iter.next_batch(), None);
+
+        let (batch, positions, count) = builder.clone().iter(&seq[..40]).next_batch().unwrap();
+        assert!(count < 8);
+        assert_eq!(
+            batch
+                .into_iter()
+                .zip(positions)
+                .take(count)
+                .collect::<Vec<_>>(),
+            builder.iter(&seq[..40]).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_minimizer_iter_local_minima() {
+        use core::hash::BuildHasher;
+
+        let seq = &gen_seq(100_000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let hasher = DefaultHashBuilder::default();
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        let local_minima: Vec<_> = builder.iter_local_minima(seq).collect();
+        let expected: Vec<_> = (0..mins.len())
+            .filter(|&i| {
+                let hash = hasher.hash_one(mins[i].0);
+                (i == 0 || hash < hasher.hash_one(mins[i - 1].0))
+                    && (i + 1 == mins.len() || hash < hasher.hash_one(mins[i + 1].0))
+            })
+            .map(|i| mins[i])
+            .collect();
+        assert_eq!(local_minima, expected);
+        let ratio = local_minima.len() as f64 / mins.len() as f64;
+        assert!(ratio > 0.2 && ratio < 0.33);
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(21)
+            .width(11);
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        assert!(builder
+            .iter_local_minima(seq)
+            .all(|min| mins.contains(&min)));
+
+        // a lone minimizer is always kept
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        assert_eq!(builder.iter_local_minima(&seq[..31]).count(), 1);
+    }
+
+    #[test]
+    fn test_expand_degenerate() {
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(5).width(4);
+        let mut seq = gen_seq(200);
+        let without = builder.clone().expand_degenerate(&seq, 1).unwrap();
+        assert_eq!(without, builder.clone().iter(&seq).collect::<Vec<_>>());
+
+        // with a single degenerate base, the windows are expanded like the whole sequence
+        seq[100] = b'R';
+        let mut expected = Vec::new();
+        for base in [b'A', b'G'] {
+            let mut concrete = seq.clone();
+            concrete[100] = base;
+            expected.extend(builder.clone().iter(&concrete));
+        }
+        expected.sort_unstable_by_key(|&(min, pos)| (pos, min));
+        expected.dedup();
+        assert_eq!(builder.clone().expand_degenerate(&seq, 2), Ok(expected));
+
+        seq[102] = b'n';
+        assert_eq!(
+            builder.clone().expand_degenerate(&seq, 4),
+            Err(ExpansionError {
+                pos: 95,
+                expansions: 8,
+                max_expansions: 4
+            })
+        );
+        let mins = builder.clone().expand_degenerate(&seq, 8).unwrap();
+        assert!(mins.windows(2).all(|w| w[0] < w[1] || w[0].1 < w[1].1));
+        // "ACGTRYN" forms a single window with 16 assignments
+        let mins = builder.width(3).expand_degenerate(b"ACGTRYN", 16).unwrap();
+        assert!(!mins.is_empty() && mins.len() <= 16);
+    }
+
+    #[test]
+    fn test_max_gap() {
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(3)
+            .width(4)
+            .hasher(BuildNoHashHasher::<u64>::default());
+        // minimizers at positions 2, 6, 7 and 9, and the last m-mer at position 10
+        assert_eq!(builder.clone().max_gap(b"TGATTGCACAATC"), 4);
+        assert_eq!(builder.clone().max_gap(b"TGATTGCA"), 3);
+        assert_eq!(builder.max_gap(b"TGATT"), 0);
+
+        let seq = &gen_seq(100_000);
+        for width in [1, 10, 11] {
+            let builder = MinimizerBuilder::<u64>::new()
+                .minimizer_size(21)
+                .width(width);
+            assert!(builder.clone().max_gap(seq) <= width as usize);
+            let builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(21)
+                .width(width);
+            assert!(builder.max_gap(seq) <= width as usize);
+        }
+        for width in [1, 11] {
+            let builder = MinimizerBuilder::<u64>::new()
+                .canonical()
+                .minimizer_size(21)
+                .width(width);
+            assert!(builder.clone().max_gap(seq) <= width as usize);
+            let builder = MinimizerBuilder::<u64, _>::new_mod()
+                .canonical()
+                .minimizer_size(21)
+                .width(width);
+            assert!(builder.max_gap(seq) <= width as usize);
+        }
+    }
+
+    #[test]
+    fn test_base_order() {
+        let seq = &gen_seq(10_000);
+        let (minimizer_size, width) = (21, 11);
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .hasher(BuildNoHashHasher::<u64>::default())
+            .base_order(BaseOrder::LeastSignificantFirst);
+        let lsf_values: Vec<u64> = seq
+            .windows(minimizer_size)
+            .map(|mmer| pack(&mmer.iter().rev().copied().collect::<Vec<_>>()))
+            .collect();
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+        for window in 0..=(lsf_values.len() - width as usize) {
+            let (pos, &min) = lsf_values[window..(window + width as usize)]
+                .iter()
+                .enumerate()
+                .min_by_key(|&(i, &x)| (x, i))
+                .unwrap();
+            if expected.last().map(|&(_, p)| p) != Some(window + pos) {
+                expected.push((min, window + pos));
+            }
+        }
+        assert_eq!(builder.clone().iter(seq).collect::<Vec<_>>(), expected);
+        assert!(builder
+            .clone()
+            .iter_pos(seq)
+            .eq(expected.iter().map(|&(_, pos)| pos)));
+        assert!(builder.iter_ref(seq).eq(expected.iter().copied()));
+        assert!(builder
+            .clone()
+            .iter_ties(seq)
+            .map(|(min, pos, _)| (min, pos))
+            .eq(expected.iter().copied()));
+        assert!(builder.clone().iter_range(seq, 2000, 5000).eq(expected
+            .iter()
+            .copied()
+            .filter(|&(_, pos)| (2000..5000).contains(&pos))));
+        let mut iter = builder.clone().iter(seq);
+        iter.nth(100);
+        let resumed = builder.clone().resume(seq, iter.save_state());
+        assert!(resumed.eq(expected[101..].iter().copied()));
+
+        // changing the hasher keeps the order
+        assert!(builder
+            .clone()
+            .hasher(BuildNoHashHasher::<u64>::default())
+            .iter(seq)
+            .eq(expected.iter().copied()));
+
+        // the default order keeps the first base in the most significant bits
+        let mut min_iter = MinimizerBuilder::<u64>::new()
+            .minimizer_size(3)
+            .width(1)
+            .base_order(BaseOrder::MostSignificantFirst)
+            .iter(b"ACG");
+        assert_eq!(min_iter.next(), Some((0b000110, 0)));
+        let mut min_iter = MinimizerBuilder::<u64>::new()
+            .minimizer_size(3)
+            .width(1)
+            .base_order(BaseOrder::LeastSignificantFirst)
+            .iter(b"ACG");
+        assert_eq!(min_iter.next(), Some((0b100100, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "this iterator only supports BaseOrder::MostSignificantFirst")]
+    fn test_base_order_unsupported() {
+        MinimizerBuilder::<u64>::new()
+            .base_order(BaseOrder::LeastSignificantFirst)
+            .iter_windows(&gen_seq(100));
+    }
+
+    #[test]
+    #[should_panic(expected = "canonical minimizers only support BaseOrder::MostSignificantFirst")]
+    fn test_base_order_canonical() {
+        let _ = MinimizerBuilder::<u64>::new()
+            .base_order(BaseOrder::LeastSignificantFirst)
+            .canonical();
+    }
+
+    #[test]
+    fn test_suppress_within() {
+        let seq = &gen_seq(50).repeat(20);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(11).width(5);
+        let mins: Vec<_> = builder.clone().iter(seq).collect();
+        assert!(builder
+            .clone()
+            .suppress_within(seq, 0)
+            .eq(mins.iter().copied()));
+
+        let distance = 120;
+        let mut expected: Vec<(u64, usize)> = Vec::new();
+        for &(min, pos) in mins.iter() {
+            if !expected
+                .iter()
+                .any(|&(prev, prev_pos)| prev == min && prev_pos + distance > pos)
+            {
+                expected.push((min, pos));
+            }
+        }
+        let suppressed: Vec<_> = builder.suppress_within(seq, distance).collect();
+        assert_eq!(suppressed, expected);
+        assert!(suppressed.len() < mins.len() / 2);
+
+        // canonical values suppress the occurrences on both strands
+        let seq = &[gen_seq(100), rc(&gen_seq(100))].concat();
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(11)
+            .width(5);
+        let values: HashSet<u64> = builder.clone().iter(seq).map(|(min, _, _)| min).collect();
+        let suppressed: Vec<_> = builder.suppress_within(seq, seq.len()).collect();
+        assert_eq!(suppressed.len(), values.len());
+    }
+
+    #[test]
+    fn test_hash_kmer() {
+        let seq = &gen_seq(1_000);
+        let (minimizer_size, width) = (21, 11);
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+        for (start, min, offset) in builder.clone().iter_windows(seq) {
+            let hashes: Vec<u64> = (start..(start + width as usize))
+                .map(|i| builder.hash_kmer(&seq[i..(i + minimizer_size)]))
+                .collect();
+            assert_eq!(hashes.iter().min(), Some(&hashes[offset]));
+            let pos = start + offset;
+            assert_eq!(pack(&seq[pos..(pos + minimizer_size)]), min);
+        }
+
+        let builder = builder.canonical();
+        for (_, pos, _) in builder.clone().iter(seq) {
+            let mmer = &seq[pos..(pos + minimizer_size)];
+            assert_eq!(builder.hash_kmer(mmer), builder.hash_kmer(&rc(mmer)));
+        }
+        let (min, pos, _) = builder.clone().iter(seq).next().unwrap();
+        let hashes: Vec<u64> = (0..width as usize)
+            .map(|i| builder.hash_kmer(&seq[i..(i + minimizer_size)]))
+            .collect();
+        assert_eq!(hashes.iter().min(), Some(&hashes[pos]));
+        assert_eq!(
+            builder.hash_kmer(&seq[pos..(pos + minimizer_size)]),
+            builder.clone().non_canonical().width(1).hash_kmer(&{
+                let mmer = &seq[pos..(pos + minimizer_size)];
+                let rc_mmer = rc(mmer);
+                if pack(mmer) == min {
+                    mmer.to_vec()
+                } else {
+                    rc_mmer
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_minimizer_iter_ascii() {
+        let seq = &gen_seq(10_000);
+        let lower = &seq.to_ascii_lowercase();
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let mins: Vec<_> = builder
+            .clone()
+            .encoding(0b00, 0b01, 0b11, 0b10)
+            .iter(seq)
+            .collect();
+        assert!(builder.clone().iter_ascii(seq).eq(mins.iter().copied()));
+        assert!(builder.clone().iter_ascii(lower).eq(mins.iter().copied()));
+        assert_eq!(builder.clone().iter_ascii(&seq[..30]).next(), None);
+        let mins: Vec<_> = iterator::AsciiMinimizerIterator::<u64>::new(
+            seq,
+            21,
+            11,
+            DefaultHashBuilder::default(),
+            encoding::AsciiEncoding {},
+        )
+        .collect();
+        assert!(builder.iter_ascii(seq).eq(mins));
+    }
+
+    #[test]
+    fn test_ownership_intervals() {
+        let seq = &gen_seq(500);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(11).width(9);
+        let base_width = 19;
+        let intervals: Vec<_> = builder.clone().ownership_intervals(seq).collect();
+        let mins: Vec<_> = builder.iter(seq).collect();
+        assert_eq!(intervals.len(), mins.len());
+        assert_eq!(intervals.first().unwrap().1, 0);
+        assert_eq!(intervals.last().unwrap().2, seq.len());
+        for (&(min, start, end), &(expected, pos)) in intervals.iter().zip(mins.iter()) {
+            assert_eq!(min, expected);
+            assert!(start <= pos && pos + 11 <= end);
+            assert!(end - start >= base_width);
+        }
+        for pair in intervals.windows(2) {
+            assert_eq!(pair[0].2, pair[1].1 + base_width - 1);
+        }
+
+        let seq = &gen_seq(base_width);
+        let intervals: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(11)
+            .width(9)
+            .ownership_intervals(seq)
+            .collect();
+        assert_eq!(intervals.len(), 1);
+        assert_eq!((intervals[0].1, intervals[0].2), (0, base_width));
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .minimizer_size(11)
+                .width(9)
+                .ownership_intervals(&seq[1..])
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_minimizer_iter_skip_to() {
+        let seq = &gen_seq(1000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(11).width(9);
+        for offset in [0, 1, 8, 9, 10, 19, 100, 500, 989, 990, 999, 1000, 2000] {
+            let expected: Vec<_> = builder
+                .clone()
+                .iter(seq)
+                .filter(|&(_, pos)| pos >= offset)
+                .collect();
+            let mut iter = builder.clone().iter(seq);
+            iter.skip_to(offset);
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+
+            // skipping after iteration has started
+            let mut iter = builder.clone().iter(seq);
+            let first: Vec<_> = iter.by_ref().take(5).collect();
+            iter.skip_to(offset);
+            let rest: Vec<_> = iter.collect();
+            let expected: Vec<_> = builder
+                .clone()
+                .iter(seq)
+                .skip(5)
+                .filter(|&(_, pos)| pos >= offset)
+                .collect();
+            assert_eq!(first.len(), 5);
+            assert_eq!(rest, expected);
+
+            // skipping twice, then feeding the rest
+            let mut iter = builder.clone().iter(seq);
+            iter.skip_to(offset / 2);
+            iter.skip_to(offset);
+            let mut rest = Vec::new();
+            iter.feed(&mut |min, pos| rest.push((min, pos)));
+            assert_eq!(
+                rest,
+                builder
+                    .clone()
+                    .iter(seq)
+                    .filter(|&(_, pos)| pos >= offset)
+                    .collect::<Vec<_>>()
+            );
+        }
+        let mut iter = builder
+            .clone()
+            .base_order(BaseOrder::LeastSignificantFirst)
             .iter(seq);
+        iter.skip_to(500);
+        assert!(iter.eq(builder
+            .clone()
+            .base_order(BaseOrder::LeastSignificantFirst)
+            .iter(seq)
+            .filter(|&(_, pos)| pos >= 500)));
 
-        assert_eq!(min_iter.next(), Some((0b00111110, 2))); // ATTG
-        assert_eq!(min_iter.next(), Some((0b01000100, 6))); // CACA
-        assert_eq!(min_iter.next(), Some((0b00010000, 7))); // ACAA
-        assert_eq!(min_iter.next(), Some((0b00001101, 9))); // AATC
-        assert_eq!(min_iter.next(), None);
+        // the minimizer left by the skip is yielded by a resumed iterator
+        let mut iter = builder.clone().iter(seq);
+        iter.skip_to(500);
+        let state = iter.save_state();
+        assert!(state.pending);
+        assert!(state
+            .min_pos
+            .is_some_and(|(_, pos)| pos >= 500 && pos < seq.len()));
+        let iter = builder.clone().resume(seq, state);
+        assert!(iter.eq(builder.iter(seq).filter(|&(_, pos)| pos >= 500)));
     }
 
     #[test]
-    fn test_mod_minimizer_iter_pos() {
-        let seq = b"TGATTGCACAATC";
-        let minimizer_size = 4;
-        let width = 4;
-        let hasher = BuildNoHashHasher::<u64>::default();
-        let mut min_iter = MinimizerBuilder::<u64, _>::new_mod()
-            .minimizer_size(minimizer_size)
-            .width(width)
-            .hasher(hasher)
-            .iter_pos(seq);
+    fn test_gaps_longer_than() {
+        let seq = &gen_seq(1000);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(11).width(9);
+        let gaps: Vec<_> = builder.clone().gaps_longer_than(seq, 0).collect();
+        assert_eq!(
+            gaps.iter().map(|gap| gap.len()).max().unwrap(),
+            builder.clone().max_gap(seq)
+        );
+        let positions: Vec<_> = builder.clone().iter_pos(seq).collect();
+        assert_eq!(gaps.first().unwrap().start, 0);
+        assert_eq!(gaps.last().unwrap().end, seq.len() - 11);
+        for gap in gaps.iter() {
+            assert!(!positions
+                .iter()
+                .any(|pos| gap.contains(pos) && *pos != gap.start));
+        }
 
-        assert_eq!(min_iter.next(), Some(2)); // ATTG
-        assert_eq!(min_iter.next(), Some(6)); // CACA
-        assert_eq!(min_iter.next(), Some(7)); // ACAA
-        assert_eq!(min_iter.next(), Some(9)); // AATC
-        assert_eq!(min_iter.next(), None);
+        // gaps are at most width long
+        let long_gaps: Vec<_> = builder.clone().gaps_longer_than(seq, 8).collect();
+        assert!(!long_gaps.is_empty());
+        assert!(long_gaps.iter().all(|gap| gap.len() == 9));
+        assert_eq!(builder.clone().gaps_longer_than(seq, 9).count(), 0);
+        assert_eq!(
+            long_gaps,
+            gaps.into_iter()
+                .filter(|gap| gap.len() > 8)
+                .collect::<Vec<_>>()
+        );
+
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(11)
+            .width(9);
+        assert_eq!(
+            builder
+                .clone()
+                .gaps_longer_than(seq, 0)
+                .map(|gap| gap.len())
+                .max()
+                .unwrap(),
+            builder.max_gap(seq)
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .gaps_longer_than(b"ACGT", 0)
+                .count(),
+            0
+        );
     }
 
-    fn gen_seq(len: usize) -> Vec<u8> {
-        let mut rng = biotest::rand();
-        let mut seq = Vec::with_capacity(len);
-        let generator = biotest::Sequence::builder()
-            .sequence_len(len)
-            .build()
-            .unwrap();
-        generator.record(&mut seq, &mut rng).unwrap();
-        seq
+    #[test]
+    fn test_max_minimizer_size_builder() {
+        let builder = MinimizerBuilder::<u32>::new().max_minimizer_size().width(1);
+        assert_eq!(builder.kmer_size(), 16);
+        let builder = MinimizerBuilder::<u128>::new()
+            .max_minimizer_size()
+            .width(1);
+        assert_eq!(builder.kmer_size(), 64);
+        let builder = MinimizerBuilder::<u64>::new().max_minimizer_size().k(40);
+        assert_eq!(builder.kmer_size(), 40);
+        assert!(builder.validate().is_ok());
+
+        let seq = &gen_seq(1000);
+        let builder = MinimizerBuilder::<u64>::new()
+            .max_minimizer_size()
+            .width(9)
+            .lexicographic();
+        assert!(builder.iter(seq).eq(MinimizerBuilder::<u64>::new()
+            .minimizer_size(32)
+            .width(9)
+            .lexicographic()
+            .iter(seq)));
     }
 
-    fn rc(seq: &[u8]) -> Vec<u8> {
-        seq.iter()
-            .rev()
-            .map(|&b| match b {
-                b'A' => b'T',
-                b'a' => b't',
-                b'T' => b'A',
-                b't' => b'a',
-                b'C' => b'G',
-                b'c' => b'g',
-                b'G' => b'C',
-                b'g' => b'c',
-                b => b,
+    #[test]
+    fn test_trim_ends() {
+        let seq = &gen_seq(300);
+        let width = 9;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(11)
+            .width(width);
+        let windows: Vec<_> = builder.clone().iter_windows(seq).collect();
+        let num_windows = windows.len();
+        let interior = |window: usize| window >= 9 && window + 9 < num_windows;
+        let expected: Vec<_> = builder
+            .clone()
+            .iter(seq)
+            .filter(|&(_, pos)| {
+                windows
+                    .iter()
+                    .any(|&(window, _, offset)| window + offset == pos && interior(window))
             })
-            .collect()
+            .collect();
+        let trimmed: Vec<_> = builder.clone().trim_ends(seq).collect();
+        assert_eq!(trimmed, expected);
+        assert!(trimmed.len() < builder.clone().iter(seq).count());
+
+        let seq = &gen_seq(2 * width as usize + 18);
+        assert_eq!(builder.clone().trim_ends(seq).count(), 0);
+        let seq = &gen_seq(2 * width as usize + 19);
+        assert!(builder.trim_ends(seq).count() >= 1);
     }
 
     #[test]
-    fn test_canonical_minimizer_iter() {
-        let seq_len = 1_000_000;
-        let seq = &gen_seq(seq_len);
-        let seq_rc = &rc(seq);
+    fn test_expected_distinct() {
+        let seq = &gen_seq(100_000);
+        // the space of m-mers is saturated for small minimizer sizes
+        for minimizer_size in [5, 8, 21] {
+            let builder = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(11);
+            let expected = builder.expected_distinct(seq.len());
+            let distinct: HashSet<_> = builder.iter(seq).map(|(min, _)| min).collect();
+            assert!((expected / distinct.len() as f64 - 1.0).abs() < 0.05);
+
+            let builder = MinimizerBuilder::<u64>::new()
+                .canonical()
+                .minimizer_size(minimizer_size)
+                .width(11);
+            let expected = builder.expected_distinct(seq.len());
+            let distinct: HashSet<_> = builder.iter(seq).map(|(min, _, _)| min).collect();
+            assert!((expected / distinct.len() as f64 - 1.0).abs() < 0.05);
+
+            let builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(minimizer_size)
+                .width(11);
+            let expected = builder.expected_distinct(seq.len());
+            let distinct: HashSet<_> = builder.iter(seq).map(|(min, _)| min).collect();
+            assert!((expected / distinct.len() as f64 - 1.0).abs() < 0.05);
+        }
+        // with t < minimizer_size, mod-minimizers differ from random minimizers
+        for (minimizer_size, t) in [(8, 4), (10, 5)] {
+            let builder = MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(minimizer_size)
+                .width(11)
+                .t(t);
+            let expected = builder.expected_distinct(seq.len());
+            let distinct: HashSet<_> = builder.iter(seq).map(|(min, _)| min).collect();
+            assert!((expected / distinct.len() as f64 - 1.0).abs() < 0.05);
+        }
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(21).width(11);
+        let num_mmers = (seq.len() - 20) as f64;
+        assert!(
+            (builder.expected_distinct(seq.len()) / num_mmers - builder.expected_density()).abs()
+                < 1e-6
+        );
+        assert_eq!(builder.expected_distinct(builder.kmer_size() - 1), 0.0);
+    }
+
+    #[test]
+    fn test_mod_minimizer_iter_with_tmer() {
+        let seq = gen_seq(1_000);
+        let (m, w, t) = (21, 11, 4);
+        let builder = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(m)
+            .width(w)
+            .t(t)
+            .hasher(BuildNoHashHasher::<u64>::default());
+        let with_tmer: Vec<_> = builder.clone().iter_with_tmer(&seq).collect();
+        let mins: Vec<_> = builder.iter(&seq).collect();
+        assert_eq!(
+            with_tmer
+                .iter()
+                .map(|&(mmer, pos, _, _)| (mmer, pos))
+                .collect::<Vec<_>>(),
+            mins
+        );
+        let tmers: Vec<u64> = seq
+            .windows(t)
+            .map(|tmer| {
+                tmer.iter().fold(0, |acc, &b| {
+                    let code = match b {
+                        b'A' => 0,
+                        b'C' => 1,
+                        b'G' => 2,
+                        _ => 3,
+                    };
+                    (acc << 2) | code
+                })
+            })
+            .collect();
+        let width_t = w as usize + m - t;
+        for (_, pos, tmer, tmer_offset) in with_tmer {
+            assert_eq!(tmer_offset % w as usize, 0);
+            assert!(tmer_offset < width_t);
+            assert_eq!(tmer, tmers[pos + tmer_offset]);
+            // the t-mer is the smallest one of a window in which the mod-minimizer is selected
+            let last_start = pos.min(tmers.len() - width_t);
+            assert!(
+                ((pos + 1).saturating_sub(w as usize)..=last_start).any(|start| {
+                    tmers[start..(start + width_t)].iter().min() == Some(&tmer)
+                        && (pos + tmer_offset - start) % w as usize == pos - start
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_uhs_order() {
+        let seq = gen_seq(1_000);
+        let (m, w) = (5, 12);
+        // ranking each m-mer by its packed value gives the lexicographic order
+        let ranks: Vec<u32> = (0..1 << (2 * m)).collect();
+        let uhs: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(m)
+            .width(w)
+            .uhs_order(&ranks)
+            .iter(&seq)
+            .collect();
+        let lex: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(m)
+            .width(w)
+            .hasher(BuildNoHashHasher::<u64>::default())
+            .iter(&seq)
+            .collect();
+        assert_eq!(uhs, lex);
+
+        // m-mers ranked first are selected whenever a window contains one
+        let first = 0b00_01_10_11_00u64;
+        let mut ranks: Vec<u32> = vec![1; 1 << (2 * m)];
+        ranks[first as usize] = 0;
+        let selected: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(m)
+            .width(4)
+            .uhs_order(&ranks)
+            .iter(b"GGGGGACGTAGGGGGGGGGGG")
+            .collect();
+        assert!(selected.contains(&(first, 5)));
+
+        // mod-minimizers rank the t-mers
+        let t = 3;
+        let ranks: Vec<u32> = (0..1 << (2 * t)).collect();
+        let uhs: Vec<_> = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(11)
+            .width(w)
+            .t(t)
+            .uhs_order(&ranks)
+            .iter(&seq)
+            .collect();
+        let lex: Vec<_> = MinimizerBuilder::<u64, _>::new_mod()
+            .minimizer_size(11)
+            .width(w)
+            .t(t)
+            .hasher(BuildNoHashHasher::<u64>::default())
+            .iter(&seq)
+            .collect();
+        assert_eq!(uhs, lex);
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(11)
+                .width(w)
+                .t(t)
+                .uhs_order(&ranks)
+                .validate(),
+            Ok(())
+        );
+
+        // the ranks must match the size of the ordered m-mers, or t-mers for mod-minimizers
+        assert_eq!(
+            MinimizerBuilder::<u64>::new()
+                .minimizer_size(m + 1)
+                .uhs_order(&ranks)
+                .validate(),
+            Err(vec![BuildError::RankCount {
+                ranks: ranks.len(),
+                size: m + 1
+            }])
+        );
+        assert_eq!(
+            MinimizerBuilder::<u64, _>::new_mod()
+                .minimizer_size(11)
+                .width(w)
+                .t(t + 1)
+                .uhs_order(&ranks)
+                .validate(),
+            Err(vec![BuildError::RankCount {
+                ranks: ranks.len(),
+                size: t + 1
+            }])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the ranks must have 4^6 entries")]
+    fn test_uhs_order_too_few_ranks() {
+        let ranks: Vec<u32> = (0..1 << 10).collect();
+        let _ = MinimizerBuilder::<u64>::new()
+            .uhs_order(&ranks)
+            .minimizer_size(6)
+            .iter(b"ACGTACGTACGTACGTACGT");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_uhs_order_not_power_of_4() {
+        let ranks = vec![0; 512];
+        let _ = MinimizerBuilder::<u64>::new().uhs_order(&ranks);
+    }
+
+    #[test]
+    fn test_complement() {
+        let seq = &gen_seq(100_000);
         let minimizer_size = 21;
         let width = 11;
 
-        let mins: Vec<u64> = MinimizerBuilder::new()
+        // a swapped encoding keeps the usual complement
+        let seq_rc = &rc(seq);
+        let builder = MinimizerBuilder::<u64>::new()
+            .encoding(0b01, 0b00, 0b10, 0b11)
             .canonical()
             .minimizer_size(minimizer_size)
-            .width(width)
+            .width(width);
+        let mins: Vec<_> = builder.clone().iter(seq).map(|(min, _, _)| min).collect();
+        let mut mins_rc: Vec<_> = builder.iter(seq_rc).map(|(min, _, _)| min).collect();
+        mins_rc.reverse();
+        assert_eq!(mins, mins_rc);
+
+        // a custom complement pairing A with C and G with T
+        let mut complement = iterator::COMPLEMENT;
+        for (a, b) in [(b'A', b'C'), (b'G', b'T')] {
+            complement[a as usize] = b;
+            complement[b as usize] = a;
+        }
+        let seq_rc: &Vec<_> = &seq.iter().rev().map(|&b| complement[b as usize]).collect();
+        for builder in [
+            MinimizerBuilder::<u64>::new(),
+            MinimizerBuilder::<u64>::new().encoding(0b01, 0b00, 0b10, 0b11),
+        ] {
+            let builder = builder
+                .complement(complement)
+                .canonical()
+                .minimizer_size(minimizer_size)
+                .width(width);
+            let mins: Vec<_> = builder.clone().iter(seq).map(|(min, _, _)| min).collect();
+            let mut mins_rc: Vec<_> = builder
+                .clone()
+                .iter(seq_rc)
+                .map(|(min, _, _)| min)
+                .collect();
+            mins_rc.reverse();
+            assert_eq!(mins, mins_rc);
+            for (min, pos, strand) in builder.clone().iter(seq).take(100) {
+                let mmer = builder
+                    .clone()
+                    .non_canonical()
+                    .width(1)
+                    .iter(&seq[pos..(pos + minimizer_size)])
+                    .next()
+                    .unwrap()
+                    .0;
+                assert_eq!(builder.canonical_kmer(mmer), (min, strand.is_rc()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonical_kmer() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        for builder in [
+            MinimizerBuilder::<u64>::new(),
+            MinimizerBuilder::<u64>::new().encoding(0b11, 0b00, 0b10, 0b01),
+        ] {
+            let builder = builder.canonical().minimizer_size(minimizer_size).width(11);
+            for (min, pos, strand) in builder.clone().iter(seq) {
+                let mmer = builder
+                    .clone()
+                    .non_canonical()
+                    .width(1)
+                    .iter(&seq[pos..(pos + minimizer_size)])
+                    .next()
+                    .unwrap()
+                    .0;
+                assert_eq!(builder.canonical_kmer(mmer), (min, strand.is_rc()));
+            }
+        }
+        let mut encoding = [0; 256];
+        for (i, b) in b"ACGT".iter().enumerate() {
+            encoding[*b as usize] = i as u8;
+        }
+        assert_eq!(
+            iterator::canonical_kmer(pack(b"TTTG"), 4, &encoding, &iterator::COMPLEMENT),
+            (pack(b"CAAA"), true)
+        );
+        assert_eq!(
+            iterator::canonical_kmer(pack(b"ACGG"), 4, &encoding, &iterator::COMPLEMENT),
+            (pack(b"ACGG"), false)
+        );
+    }
+
+    #[test]
+    fn test_canonical_minimizer_struct() {
+        let seq = &gen_seq(1_000);
+        let builder = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(21)
+            .width(11);
+        for (min, tuple) in builder
+            .clone()
             .iter(seq)
-            .map(|(min, _, _)| min)
+            .map(CanonicalMinimizer::from)
+            .zip(builder.iter(seq))
+        {
+            assert_eq!((min.value, min.pos, min.strand), tuple);
+            assert_eq!(min.into_tuple(), tuple);
+            assert_eq!(<(u64, usize, Strand)>::from(min), tuple);
+        }
+    }
+
+    #[test]
+    fn test_strand() {
+        assert_eq!(Strand::from(false), Strand::Forward);
+        assert_eq!(Strand::from(true), Strand::Reverse);
+        assert!(!Strand::Forward.is_rc());
+        assert!(Strand::Reverse.is_rc());
+        assert!(bool::from(Strand::Reverse));
+
+        let seq = &gen_seq(1_000);
+        let minimizer_size = 21;
+        for (min, pos, strand) in MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(11)
+            .iter(seq)
+        {
+            let mmer = &seq[pos..(pos + minimizer_size)];
+            match strand {
+                Strand::Forward => assert_eq!(min, pack(mmer)),
+                Strand::Reverse => assert_eq!(min, pack(&rc(mmer))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_nthash_minimizer_iter() {
+        let seq = b"ACTGC";
+        let hashes: Vec<_> = seq.windows(3).map(iterator::nthash_forward).collect();
+        assert_eq!(
+            hashes,
+            [0xb85d2431d9ba031e, 0xb4d7ab2f9f1306b8, 0xd4a29bf149877c5c]
+        );
+
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        let width = 11;
+        let builder = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width);
+
+        let hashes: Vec<_> = seq
+            .windows(minimizer_size)
+            .map(iterator::nthash_forward)
             .collect();
-        let mut mins_rc: Vec<u64> = MinimizerBuilder::new()
+        let mut expected: Vec<_> = builder
+            .clone()
+            .iter_windows(seq)
+            .map(|(i, _, _)| {
+                let window = &hashes[i..(i + width as usize)];
+                let offset = (0..window.len()).min_by_key(|&j| window[j]).unwrap();
+                (
+                    pack(&seq[(i + offset)..(i + offset + minimizer_size)]),
+                    i + offset,
+                )
+            })
+            .collect();
+        expected.dedup();
+        let mins: Vec<_> = builder.nthash().iter(seq).collect();
+        assert_eq!(mins, expected);
+    }
+
+    #[test]
+    fn test_canonical_nthash_minimizer_iter() {
+        let seq = b"ACTGC";
+        let hashes: Vec<_> = seq.windows(3).map(iterator::nthash).collect();
+        assert_eq!(
+            hashes,
+            [0x9b1eda9a185413ce, 0x9f6acfa2235b86fc, 0xd4a29bf149877c5c]
+        );
+
+        let seq = &gen_seq(10_000);
+        let seq_rc = &rc(seq);
+        let minimizer_size = 21;
+        let width = 11;
+        let builder = MinimizerBuilder::<u64>::new()
             .canonical()
             .minimizer_size(minimizer_size)
-            .width(width)
+            .width(width);
+
+        let hashes: Vec<_> = seq.windows(minimizer_size).map(iterator::nthash).collect();
+        let mut expected: Vec<_> = builder
+            .clone()
+            .iter_windows(seq)
+            .map(|(i, _, _, _)| {
+                let window = &hashes[i..(i + width as usize)];
+                let offset = (0..window.len()).min_by_key(|&j| window[j]).unwrap();
+                i + offset
+            })
+            .collect();
+        expected.dedup();
+        let mins: Vec<_> = builder.clone().nthash().iter(seq).collect();
+        assert_eq!(
+            mins.iter().map(|&(_, pos, _)| pos).collect::<Vec<_>>(),
+            expected
+        );
+        for &(min, pos, strand) in &mins {
+            assert_eq!(
+                builder.canonical_kmer(pack(&seq[pos..(pos + minimizer_size)])),
+                (min, strand.is_rc())
+            );
+        }
+
+        let mut mins_rc: Vec<_> = builder
+            .nthash()
             .iter(seq_rc)
             .map(|(min, _, _)| min)
             .collect();
         mins_rc.reverse();
-
-        assert_eq!(mins, mins_rc);
+        assert_eq!(
+            mins.into_iter().map(|(min, _, _)| min).collect::<Vec<_>>(),
+            mins_rc
+        );
     }
 
     #[test]
@@ -249,6 +3739,169 @@ mod tests {
         assert_eq!(mins, mins_rc);
     }
 
+    #[test]
+    fn test_minimizer_index() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        let width = 11;
+
+        let index = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .index(seq);
+        for (start, end) in [(0, 0), (0, 100), (1234, 5678), (9_900, 20_000), (50, 10)] {
+            let expected: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .iter(seq)
+                .filter(|&(_, pos)| start <= pos && pos < end)
+                .collect();
+            assert_eq!(index.minimizers_in_range(start, end), expected);
+        }
+        for pos in [0, 17, 4321, 20_000] {
+            let expected = index.iter().min_by_key(|&&(_, p)| p.abs_diff(pos)).unwrap();
+            assert_eq!(index.nearest_to(pos), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_paired_minimizer_iter() {
+        let seq = gen_seq(270);
+        let (read1, read2) = seq.split_at(150);
+        let builder = MinimizerBuilder::<u64>::new().minimizer_size(15).width(7);
+
+        for rc_mate2 in [false, true] {
+            let mate2 = if rc_mate2 { rc(read2) } else { read2.to_vec() };
+            let expected: Vec<_> = builder
+                .clone()
+                .iter(read1)
+                .map(|(min, pos)| (1, min, pos))
+                .chain(builder.clone().iter(&mate2).map(|(min, pos)| (2, min, pos)))
+                .collect();
+            let mins: Vec<_> = builder
+                .clone()
+                .iter_pairs((read1, read2), rc_mate2)
+                .collect();
+            assert_eq!(mins, expected);
+        }
+    }
+
+    #[test]
+    fn test_spliced_minimizer_iter() {
+        let seq = &gen_seq(10_000);
+        let minimizer_size = 21;
+        let width = 11;
+        let exons = [(0, 1_500), (1_500, 1_510), (1_510, 4_000), (4_000, 10_000)];
+        let genomic_starts = [100, 5_000, 5_020, 20_000];
+        let segments: Vec<_> = exons
+            .iter()
+            .zip(genomic_starts)
+            .map(|(&(start, end), genomic_start)| (&seq[start..end], genomic_start))
+            .collect();
+
+        let expected: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter(seq)
+            .map(|(min, pos)| {
+                let i = exons.iter().position(|&(_, end)| pos < end).unwrap();
+                (min, genomic_starts[i] + pos - exons[i].0)
+            })
+            .collect();
+        let mins: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter_spliced(&segments)
+            .collect();
+
+        assert_eq!(mins, expected);
+    }
+
+    #[test]
+    fn test_packed_minimizer_iter() {
+        let seq = &gen_seq(10_001);
+        let minimizer_size = 21;
+        let width = 11;
+        let packed: Vec<u8> = seq
+            .chunks(4)
+            .map(|chunk| (pack(chunk) << (2 * (4 - chunk.len()))) as u8)
+            .collect();
+        let path = std::env::temp_dir().join(format!("minimizer-iter-{}.2bit", std::process::id()));
+        std::fs::write(&path, &packed).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+
+        let expected: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter(seq)
+            .collect();
+        let mins: Vec<_> = MinimizerBuilder::<u64>::new()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter_packed(&mmap[..], seq.len())
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mins, expected);
+    }
+
+    #[test]
+    fn test_circular_minimizer_iter() {
+        let minimizer_size = 5;
+        let width = 11;
+        for seq in [&gen_seq(10_000)[..], b"ACGTTGCAACG", b"GATTC"] {
+            let len = seq.len();
+            let extended: Vec<u8> = seq
+                .iter()
+                .cycle()
+                .take(len + width as usize + minimizer_size - 2)
+                .copied()
+                .collect();
+            let mut expected: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .iter(&extended)
+                .collect();
+            let first_pos = expected[0].1 % len;
+            expected.retain(|&(_, pos)| pos < len || pos % len != first_pos);
+            expected.iter_mut().for_each(|(_, pos)| *pos %= len);
+
+            let mins: Vec<_> = MinimizerBuilder::<u64>::new()
+                .minimizer_size(minimizer_size)
+                .width(width)
+                .iter_circular(seq)
+                .collect();
+            assert_eq!(mins, expected);
+        }
+    }
+
+    #[test]
+    fn test_canonical_circular_minimizer_iter() {
+        let seq = &gen_seq(10_000);
+        let seq_rc = &rc(seq);
+        let len = seq.len();
+        let minimizer_size = 21;
+        let width = 11;
+
+        let mins: HashSet<_> = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter_circular(seq)
+            .map(|(min, pos, _)| (min, pos))
+            .collect();
+        let mins_rc: HashSet<_> = MinimizerBuilder::<u64>::new()
+            .canonical()
+            .minimizer_size(minimizer_size)
+            .width(width)
+            .iter_circular(seq_rc)
+            .map(|(min, pos, _)| (min, (2 * len - pos - minimizer_size) % len))
+            .collect();
+        assert!(mins.iter().any(|&(_, pos)| pos + minimizer_size > len));
+        assert_eq!(mins, mins_rc);
+    }
+
     #[test]
     fn test_repetitive_2_minimizer_iter_pos() {
         const SEQ_LEN: usize = 100;
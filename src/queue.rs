@@ -0,0 +1,122 @@
+//! Sliding-window minimum data structures used to select minimizers.
+
+use core::hash::{BuildHasher, Hash};
+use minimizer_queue::{ImplicitMinimizerQueue, MinimizerQueue};
+
+/// A queue maintaining the position of the minimum of a sliding window of values, relative to the start of the window.
+///
+/// It is implemented by [`ImplicitMinimizerQueue`], which does not store the values, and by [`MinimizerQueue`].
+/// It can be implemented by other data structures (e.g. with a different tie-breaking)
+/// to be used with [`MinimizerPosIterator`](crate::iterator::MinimizerPosIterator), the mod-sampling iterators
+/// and the iterators over the positions of canonical minimizers.
+pub trait MinPosWindow<T> {
+    /// Returns the width of the window.
+    fn width(&self) -> usize;
+
+    /// Returns `true` if the window is empty.
+    fn is_empty(&self) -> bool;
+
+    /// Inserts a value at the end of the window, removing the first one if the window is full.
+    fn insert(&mut self, x: T);
+
+    /// Returns the position of the minimum in the window.
+    fn min_pos(&self) -> usize;
+
+    /// Returns `true` if there are multiple minima in the window.
+    fn multiple_mins(&self) -> bool;
+
+    /// Returns the position of the leftmost minimum of the window,
+    /// with the position of the rightmost minimum if there are multiple minima.
+    fn inner_min_pos(&self) -> (usize, Option<usize>);
+}
+
+/// A queue maintaining the minimum of a sliding window of values, with their positions relative to the start of the window.
+///
+/// It is implemented by [`MinimizerQueue`], and can be implemented by other data structures
+/// (e.g. with a different tie-breaking) to be used with [`MinimizerIterator`](crate::iterator::MinimizerIterator)
+/// and [`CanonicalMinimizerIterator`](crate::iterator::CanonicalMinimizerIterator).
+pub trait MinWindow<T>: MinPosWindow<T> {
+    /// Returns the minimum of the window and its position in the window.
+    fn get_min_pos(&self) -> (T, usize);
+
+    /// Returns the leftmost minimum of the window and its position,
+    /// with the rightmost minimum and its position if there are multiple minima.
+    fn get_inner_min_pos(&self) -> (T, usize, Option<(T, usize)>);
+}
+
+impl<T: Hash, S: BuildHasher> MinPosWindow<T> for ImplicitMinimizerQueue<S> {
+    #[inline]
+    fn width(&self) -> usize {
+        ImplicitMinimizerQueue::width(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        ImplicitMinimizerQueue::is_empty(self)
+    }
+
+    #[inline]
+    fn insert(&mut self, x: T) {
+        ImplicitMinimizerQueue::insert(self, &x)
+    }
+
+    #[inline]
+    fn min_pos(&self) -> usize {
+        ImplicitMinimizerQueue::get_min_pos(self)
+    }
+
+    #[inline]
+    fn multiple_mins(&self) -> bool {
+        ImplicitMinimizerQueue::multiple_mins(self)
+    }
+
+    #[inline]
+    fn inner_min_pos(&self) -> (usize, Option<usize>) {
+        ImplicitMinimizerQueue::get_inner_min_pos(self)
+    }
+}
+
+impl<T: Hash + Copy, S: BuildHasher> MinPosWindow<T> for MinimizerQueue<T, S> {
+    #[inline]
+    fn width(&self) -> usize {
+        MinimizerQueue::width(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        MinimizerQueue::is_empty(self)
+    }
+
+    #[inline]
+    fn insert(&mut self, x: T) {
+        MinimizerQueue::insert(self, x)
+    }
+
+    #[inline]
+    fn min_pos(&self) -> usize {
+        MinimizerQueue::get_min_pos(self).1
+    }
+
+    #[inline]
+    fn multiple_mins(&self) -> bool {
+        MinimizerQueue::multiple_mins(self)
+    }
+
+    #[inline]
+    fn inner_min_pos(&self) -> (usize, Option<usize>) {
+        let (_, pos, tie) = MinimizerQueue::get_inner_min_pos(self);
+        (pos, tie.map(|(_, pos)| pos))
+    }
+}
+
+impl<T: Hash + Copy, S: BuildHasher> MinWindow<T> for MinimizerQueue<T, S> {
+    #[inline]
+    fn get_min_pos(&self) -> (T, usize) {
+        MinimizerQueue::get_min_pos(self)
+    }
+
+    #[inline]
+    fn get_inner_min_pos(&self) -> (T, usize, Option<(T, usize)>) {
+        MinimizerQueue::get_inner_min_pos(self)
+    }
+}
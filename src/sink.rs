@@ -0,0 +1,16 @@
+//! Push-based consumers of minimizers.
+
+/// A consumer of minimizers and their positions, such as a Bloom filter, a count-min sketch or a hash map.
+///
+/// It is implemented for every closure taking a minimizer and its position.
+pub trait MinimizerSink<T> {
+    /// Receives the next minimizer and its position.
+    fn accept(&mut self, value: T, position: usize);
+}
+
+impl<T, F: FnMut(T, usize)> MinimizerSink<T> for F {
+    #[inline]
+    fn accept(&mut self, value: T, position: usize) {
+        self(value, position)
+    }
+}